@@ -0,0 +1,16 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate bson;
+use bson::raw::{RawDocument, ValidationOptions};
+
+fuzz_target!(|buf: &[u8]| {
+    if let Ok(doc) = RawDocument::from_bytes(buf) {
+        let options = ValidationOptions::builder().max_depth(100).build();
+        if doc.validate(options).is_ok() {
+            for elem in doc {
+                elem.expect("validated document should iterate without error");
+            }
+        }
+    }
+});
@@ -27,7 +27,7 @@ impl Display for Binary {
             fmt,
             "Binary({:#x}, {})",
             u8::from(self.subtype),
-            base64::encode(&self.bytes)
+            self.to_base64()
         )
     }
 }
@@ -61,6 +61,20 @@ impl Binary {
         Ok(Binary { subtype, bytes })
     }
 
+    /// Encodes the bytes of this [`Binary`] as a standard, padded base64 string. The subtype is
+    /// not included; [`Binary::from_base64`] reverses the conversion given the same subtype.
+    ///
+    /// ```rust
+    /// # use bson::{Binary, spec::BinarySubtype};
+    /// let binary = Binary { subtype: BinarySubtype::Generic, bytes: b"hello".to_vec() };
+    /// assert_eq!(binary.to_base64(), "aGVsbG8=");
+    /// assert_eq!(Binary::from_base64(binary.to_base64(), BinarySubtype::Generic)?, binary);
+    /// # Ok::<(), bson::binary::Error>(())
+    /// ```
+    pub fn to_base64(&self) -> String {
+        base64::encode(&self.bytes)
+    }
+
     pub(crate) fn from_extended_doc(doc: &Document) -> Option<Self> {
         let binary_doc = doc.get_document("$binary").ok()?;
 
@@ -89,6 +103,25 @@ impl Binary {
         }
     }
 
+    /// Returns a value whose [`Debug`](fmt::Debug) output is the same as this [`Binary`]'s, except
+    /// that only the first `limit` bytes are printed, followed by a count of the bytes omitted.
+    /// This is useful for logging values that may contain large amounts of binary data.
+    ///
+    /// ```rust
+    /// # use bson::{Binary, spec::BinarySubtype};
+    /// let binary = Binary { subtype: BinarySubtype::Generic, bytes: vec![1; 1024] };
+    /// assert_eq!(
+    ///     format!("{:?}", binary.debug_truncated(3)),
+    ///     "Binary { subtype: Generic, bytes: [1, 1, 1, ... (1021 more)] }"
+    /// );
+    /// ```
+    pub fn debug_truncated(&self, limit: usize) -> impl fmt::Debug + '_ {
+        TruncatedBinary {
+            binary: self,
+            limit,
+        }
+    }
+
     /// Borrow the contents as a [`RawBinaryRef`].
     pub fn as_raw_binary(&self) -> RawBinaryRef<'_> {
         RawBinaryRef {
@@ -98,6 +131,35 @@ impl Binary {
     }
 }
 
+struct TruncatedBinary<'a> {
+    binary: &'a Binary,
+    limit: usize,
+}
+
+impl fmt::Debug for TruncatedBinary<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        struct Bytes<'a>(&'a [u8], usize);
+
+        impl fmt::Debug for Bytes<'_> {
+            fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                let mut list = fmt.debug_list();
+                list.entries(self.0);
+                if self.1 > 0 {
+                    list.entry(&format_args!("... ({} more)", self.1));
+                }
+                list.finish()
+            }
+        }
+
+        let bytes = &self.binary.bytes;
+        let shown = &bytes[..bytes.len().min(self.limit)];
+        fmt.debug_struct("Binary")
+            .field("subtype", &self.binary.subtype)
+            .field("bytes", &Bytes(shown, bytes.len() - shown.len()))
+            .finish()
+    }
+}
+
 /// Possible errors that can arise during [`Binary`] construction.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -188,6 +188,78 @@ impl Vector {
     }
 }
 
+impl Binary {
+    /// Construct a [`Binary`] of subtype [`BinarySubtype::Vector`] holding the given `f32`
+    /// values.
+    ///
+    /// ```rust
+    /// # use bson::binary::{Binary, Result};
+    /// # fn main() -> Result<()> {
+    /// let binary = Binary::from_f32_vector(&[1.5, -2.0]);
+    /// assert_eq!(binary.to_f32_vector()?, vec![1.5, -2.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_f32_vector(vector: &[f32]) -> Self {
+        Self::from(Vector::Float32(vector.to_vec()))
+    }
+
+    /// Parse this [`Binary`] as a vector of `f32` values. Returns an error if this is not a
+    /// [`BinarySubtype::Vector`] binary or if it holds a different element type.
+    pub fn to_f32_vector(&self) -> Result<Vec<f32>> {
+        match Vector::try_from(self)? {
+            Vector::Float32(vector) => Ok(vector),
+            other => Err(Error::Vector {
+                message: format!(
+                    "expected a float32 vector (data type {}), got data type {}",
+                    FLOAT32,
+                    other.d_type()
+                ),
+            }),
+        }
+    }
+
+    /// Construct a [`Binary`] of subtype [`BinarySubtype::Vector`] holding the given `i8` values.
+    pub fn from_int8_vector(vector: &[i8]) -> Self {
+        Self::from(Vector::Int8(vector.to_vec()))
+    }
+
+    /// Parse this [`Binary`] as a vector of `i8` values. Returns an error if this is not a
+    /// [`BinarySubtype::Vector`] binary or if it holds a different element type.
+    pub fn to_int8_vector(&self) -> Result<Vec<i8>> {
+        match Vector::try_from(self)? {
+            Vector::Int8(vector) => Ok(vector),
+            other => Err(Error::Vector {
+                message: format!(
+                    "expected an int8 vector (data type {}), got data type {}",
+                    INT8,
+                    other.d_type()
+                ),
+            }),
+        }
+    }
+
+    /// Construct a [`Binary`] of subtype [`BinarySubtype::Vector`] holding the given packed bits.
+    pub fn from_packed_bit_vector(vector: PackedBitVector) -> Self {
+        Self::from(Vector::PackedBit(vector))
+    }
+
+    /// Parse this [`Binary`] as a [`PackedBitVector`]. Returns an error if this is not a
+    /// [`BinarySubtype::Vector`] binary or if it holds a different element type.
+    pub fn to_packed_bit_vector(&self) -> Result<PackedBitVector> {
+        match Vector::try_from(self)? {
+            Vector::PackedBit(vector) => Ok(vector),
+            other => Err(Error::Vector {
+                message: format!(
+                    "expected a packed bit vector (data type {}), got data type {}",
+                    PACKED_BIT,
+                    other.d_type()
+                ),
+            }),
+        }
+    }
+}
+
 impl From<&Vector> for Binary {
     fn from(vector: &Vector) -> Self {
         let d_type = vector.d_type();
@@ -22,22 +22,27 @@
 //! BSON definition
 
 use std::{
+    cmp::Ordering,
     convert::{TryFrom, TryInto},
     fmt::{self, Debug, Display, Formatter},
     hash::Hash,
     ops::Index,
 };
 
-use serde_json::{json, Value};
+use serde_json::Value;
 
 pub use crate::document::Document;
 use crate::{
+    document::UnexpectedTypeError,
     oid::{self, ObjectId},
     spec::{BinarySubtype, ElementType},
     Binary,
     Decimal128,
 };
 
+const PORTABLE_TYPE_KEY: &str = "__bson_type";
+const PORTABLE_VALUE_KEY: &str = "value";
+
 /// Possible BSON value types.
 #[derive(Clone, Default, PartialEq)]
 pub enum Bson {
@@ -89,6 +94,10 @@ pub enum Bson {
 /// Alias for `Vec<Bson>`.
 pub type Array = Vec<Bson>;
 
+/// Hashing is consistent with [`PartialEq`]: values that compare equal hash identically,
+/// including `Double(0.0)` and `Double(-0.0)`. `Double(NaN)` values hash by their bit pattern, but
+/// because NaN never compares equal to itself, a NaN (or a document or array containing one) can
+/// be inserted into a `HashMap` or `HashSet` but never looked up again.
 #[cfg(feature = "hashable")]
 impl Hash for Bson {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -471,131 +480,233 @@ impl Bson {
     ///
     /// Note: If this method is called on a case which contains a `Decimal128` value, it will panic.
     pub fn into_relaxed_extjson(self) -> Value {
-        match self {
-            Bson::Double(v) if v.is_nan() => {
-                let s = if v.is_sign_negative() { "-NaN" } else { "NaN" };
+        self.to_extjson_value(false)
+    }
 
-                json!({ "$numberDouble": s })
-            }
-            Bson::Double(v) if v.is_infinite() => {
-                let s = if v.is_sign_negative() {
-                    "-Infinity"
-                } else {
-                    "Infinity"
-                };
+    /// Converts the Bson value into its [canonical extended JSON representation](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/).
+    pub fn into_canonical_extjson(self) -> Value {
+        self.to_extjson_value(true)
+    }
 
-                json!({ "$numberDouble": s })
-            }
-            Bson::Double(v) => json!(v),
-            Bson::String(v) => json!(v),
-            Bson::Array(v) => Value::Array(v.into_iter().map(Bson::into_relaxed_extjson).collect()),
-            Bson::Document(v) => Value::Object(
-                v.into_iter()
-                    .map(|(k, v)| (k, v.into_relaxed_extjson()))
-                    .collect(),
-            ),
-            Bson::Boolean(v) => json!(v),
-            Bson::Null => Value::Null,
-            Bson::RegularExpression(Regex { pattern, options }) => {
-                let mut chars: Vec<_> = options.chars().collect();
-                chars.sort_unstable();
+    /// Serializes this value directly to a string in its [relaxed extended JSON representation](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/).
+    ///
+    /// This produces the same output as calling `to_string` on the result of
+    /// [`Bson::into_relaxed_extjson`], but borrows the value and avoids building an intermediate
+    /// [`serde_json::Value`].
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// let value = bson!({ "x": 1, "y": [1.5] });
+    /// assert_eq!(value.to_relaxed_extjson_string(), r#"{"x":1,"y":[1.5]}"#);
+    /// ```
+    pub fn to_relaxed_extjson_string(&self) -> String {
+        self.to_extjson_string(false)
+    }
 
-                let options: String = chars.into_iter().collect();
+    /// Serializes this value directly to a string in its [canonical extended JSON representation](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/).
+    ///
+    /// This produces the same output as calling `to_string` on the result of
+    /// [`Bson::into_canonical_extjson`], but borrows the value and avoids building an intermediate
+    /// [`serde_json::Value`].
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// let value = bson!({ "x": 1 });
+    /// assert_eq!(value.to_canonical_extjson_string(), r#"{"x":{"$numberInt":"1"}}"#);
+    /// ```
+    pub fn to_canonical_extjson_string(&self) -> String {
+        self.to_extjson_string(true)
+    }
 
-                json!({
-                    "$regularExpression": {
-                        "pattern": pattern,
-                        "options": options,
-                    }
-                })
-            }
-            Bson::JavaScriptCode(code) => json!({ "$code": code }),
-            Bson::JavaScriptCodeWithScope(JavaScriptCodeWithScope { code, scope }) => json!({
-                "$code": code,
-                "$scope": scope,
-            }),
-            Bson::Int32(v) => v.into(),
-            Bson::Int64(v) => v.into(),
-            Bson::Timestamp(Timestamp { time, increment }) => json!({
-                "$timestamp": {
-                    "t": time,
-                    "i": increment,
+    fn to_extjson_value(&self, canonical: bool) -> Value {
+        let value = crate::extjson::ser::ExtJson {
+            value: self,
+            canonical,
+        };
+        // Every map key is a string, so converting to a `Value` cannot fail.
+        serde_json::to_value(value).expect("extended JSON serialization should not fail")
+    }
+
+    fn to_extjson_string(&self, canonical: bool) -> String {
+        let value = crate::extjson::ser::ExtJson {
+            value: self,
+            canonical,
+        };
+        // Every map key is a string, so writing to an in-memory buffer cannot fail.
+        serde_json::to_string(&value).expect("extended JSON serialization should not fail")
+    }
+
+    /// Rewrites any [`Bson::ObjectId`], [`Bson::DateTime`], [`Bson::Binary`], and
+    /// [`Bson::Decimal128`] values within this value (including inside nested documents and
+    /// arrays) into plain documents built only from strings and integers, so that they survive
+    /// serialization to other serde formats such as CBOR or MessagePack without relying on BSON's
+    /// extended JSON conventions. [`Bson::from_portable`] reverses the conversion.
+    ///
+    /// Each converted value becomes a document of the form
+    /// `{ "__bson_type": <name>, "value": <value> }`, where the name is the value's
+    /// [`Bson::type_name`]:
+    ///
+    /// | Type         | `"__bson_type"` | `"value"`                                      |
+    /// |--------------|-----------------|------------------------------------------------|
+    /// | `ObjectId`   | `"objectId"`    | the 24-character hex string                    |
+    /// | `DateTime`   | `"date"`        | milliseconds since the Unix epoch as an int64  |
+    /// | `Binary`     | `"binData"`     | the base64-encoded bytes, plus an int32 `"subtype"` field |
+    /// | `Decimal128` | `"decimal"`     | the decimal string                             |
+    /// | `Document`   | `"object"`      | the document, only if it has its own `"__bson_type"` key |
+    ///
+    /// Other documents are left in place with their contents converted; a document that already
+    /// has a `"__bson_type"` key is wrapped so that it isn't mistaken for a converted value.
+    /// Because other formats may narrow integers that fit in 32 bits, [`Bson::from_portable`]
+    /// accepts either an int32 or an int64 wherever an integer is expected.
+    ///
+    /// ```
+    /// use bson::{doc, oid::ObjectId, Bson};
+    ///
+    /// let oid = ObjectId::new();
+    /// let portable = Bson::from(doc! { "_id": oid }).to_portable();
+    /// assert_eq!(
+    ///     portable,
+    ///     Bson::from(doc! { "_id": { "__bson_type": "objectId", "value": oid.to_hex() } })
+    /// );
+    /// assert_eq!(Bson::from_portable(portable)?, Bson::from(doc! { "_id": oid }));
+    /// # Ok::<(), bson::de::Error>(())
+    /// ```
+    pub fn to_portable(self) -> Bson {
+        let type_name = self.type_name();
+        let doc = match self {
+            Bson::Document(doc) => {
+                let escape = doc.contains_key(PORTABLE_TYPE_KEY);
+                let doc: Document = doc.into_iter().map(|(k, v)| (k, v.to_portable())).collect();
+                if !escape {
+                    return Bson::Document(doc);
+                }
+                doc! {
+                    PORTABLE_TYPE_KEY: type_name,
+                    PORTABLE_VALUE_KEY: doc,
                 }
-            }),
-            Bson::Binary(Binary { subtype, ref bytes }) => {
-                let tval: u8 = From::from(subtype);
-                json!({
-                    "$binary": {
-                        "base64": base64::encode(bytes),
-                        "subType": hex::encode([tval]),
-                    }
-                })
             }
-            Bson::ObjectId(v) => json!({"$oid": v.to_hex()}),
-            Bson::DateTime(v) if v.timestamp_millis() >= 0 && v.to_time_0_3().year() <= 9999 => {
-                json!({
-                    // Unwrap safety: timestamps in the guarded range can always be formatted.
-                    "$date": v.try_to_rfc3339_string().unwrap(),
-                })
+            Bson::Array(arr) => {
+                return Bson::Array(arr.into_iter().map(Bson::to_portable).collect())
             }
-            Bson::DateTime(v) => json!({
-                "$date": { "$numberLong": v.timestamp_millis().to_string() },
-            }),
-            Bson::Symbol(v) => json!({ "$symbol": v }),
-            Bson::Decimal128(v) => json!({ "$numberDecimal": v.to_string() }),
-            Bson::Undefined => json!({ "$undefined": true }),
-            Bson::MinKey => json!({ "$minKey": 1 }),
-            Bson::MaxKey => json!({ "$maxKey": 1 }),
-            Bson::DbPointer(DbPointer {
-                ref namespace,
-                ref id,
-            }) => json!({
-                "$dbPointer": {
-                    "$ref": namespace,
-                    "$id": {
-                        "$oid": id.to_hex()
-                    }
-                }
-            }),
-        }
+            Bson::ObjectId(oid) => doc! {
+                PORTABLE_TYPE_KEY: type_name,
+                PORTABLE_VALUE_KEY: oid.to_hex(),
+            },
+            Bson::DateTime(dt) => doc! {
+                PORTABLE_TYPE_KEY: type_name,
+                PORTABLE_VALUE_KEY: dt.timestamp_millis(),
+            },
+            Bson::Binary(Binary { subtype, bytes }) => doc! {
+                PORTABLE_TYPE_KEY: type_name,
+                PORTABLE_VALUE_KEY: base64::encode(bytes),
+                "subtype": u8::from(subtype) as i32,
+            },
+            Bson::Decimal128(d) => doc! {
+                PORTABLE_TYPE_KEY: type_name,
+                PORTABLE_VALUE_KEY: d.to_string(),
+            },
+            other => return other,
+        };
+        Bson::Document(doc)
     }
 
-    /// Converts the Bson value into its [canonical extended JSON representation](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/).
-    pub fn into_canonical_extjson(self) -> Value {
-        match self {
-            Bson::Int32(i) => json!({ "$numberInt": i.to_string() }),
-            Bson::Int64(i) => json!({ "$numberLong": i.to_string() }),
-            Bson::Double(f) if f.is_normal() => {
-                let mut s = f.to_string();
-                if f.fract() == 0.0 {
-                    s.push_str(".0");
-                }
-
-                json!({ "$numberDouble": s })
+    /// Reverses the conversion performed by [`Bson::to_portable`], restoring the BSON-specific
+    /// values it rewrote in `portable`. Documents that don't have a `"__bson_type"` key are left
+    /// unchanged (though their contents are converted), while an error is returned for any
+    /// document that has one but doesn't match the format described in [`Bson::to_portable`].
+    pub fn from_portable(portable: Bson) -> crate::de::Result<Bson> {
+        let mut doc = match portable {
+            Bson::Document(doc) if doc.contains_key(PORTABLE_TYPE_KEY) => doc,
+            Bson::Document(doc) => return Bson::portable_document_contents(doc),
+            Bson::Array(arr) => {
+                return arr
+                    .into_iter()
+                    .map(Bson::from_portable)
+                    .collect::<crate::de::Result<Array>>()
+                    .map(Bson::Array)
             }
-            Bson::Double(f) if f == 0.0 => {
-                let s = if f.is_sign_negative() { "-0.0" } else { "0.0" };
+            other => return Ok(other),
+        };
 
-                json!({ "$numberDouble": s })
+        let invalid = |doc: &Document| {
+            crate::de::Error::deserialization(format!("invalid portable BSON value: {}", doc))
+        };
+        let expected_len = match doc.get(PORTABLE_TYPE_KEY) {
+            Some(Bson::String(s)) if s == "binData" => 3,
+            _ => 2,
+        };
+        if doc.len() != expected_len {
+            return Err(invalid(&doc));
+        }
+        if let (Some(Bson::String(t)), Some(Bson::Document(_))) =
+            (doc.get(PORTABLE_TYPE_KEY), doc.get(PORTABLE_VALUE_KEY))
+        {
+            if t == "object" {
+                if let Some(Bson::Document(inner)) = doc.remove(PORTABLE_VALUE_KEY) {
+                    return Bson::portable_document_contents(inner);
+                }
             }
-            Bson::DateTime(date) => {
-                json!({ "$date": { "$numberLong": date.timestamp_millis().to_string() } })
+        }
+        let value = match (doc.get(PORTABLE_TYPE_KEY), doc.get(PORTABLE_VALUE_KEY)) {
+            (Some(Bson::String(t)), Some(Bson::String(v))) if t == "objectId" => {
+                ObjectId::parse_str(v).ok().map(Bson::ObjectId)
             }
-            Bson::Array(arr) => {
-                Value::Array(arr.into_iter().map(Bson::into_canonical_extjson).collect())
+            (Some(Bson::String(t)), Some(&Bson::Int32(v))) if t == "date" => {
+                Some(Bson::DateTime(crate::DateTime::from_millis(v.into())))
             }
-            Bson::Document(arr) => Value::Object(
-                arr.into_iter()
-                    .map(|(k, v)| (k, v.into_canonical_extjson()))
-                    .collect(),
-            ),
-            Bson::JavaScriptCodeWithScope(JavaScriptCodeWithScope { code, scope }) => json!({
-                "$code": code,
-                "$scope": Bson::Document(scope).into_canonical_extjson(),
-            }),
-
-            other => other.into_relaxed_extjson(),
-        }
+            (Some(Bson::String(t)), Some(&Bson::Int64(v))) if t == "date" => {
+                Some(Bson::DateTime(crate::DateTime::from_millis(v)))
+            }
+            (Some(Bson::String(t)), Some(Bson::String(v))) if t == "binData" => {
+                let subtype = match doc.get("subtype") {
+                    Some(&Bson::Int32(subtype)) => u8::try_from(subtype).ok(),
+                    Some(&Bson::Int64(subtype)) => u8::try_from(subtype).ok(),
+                    _ => None,
+                };
+                match (base64::decode(v), subtype) {
+                    (Ok(bytes), Some(subtype)) => Some(Bson::Binary(Binary {
+                        subtype: subtype.into(),
+                        bytes,
+                    })),
+                    _ => None,
+                }
+            }
+            (Some(Bson::String(t)), Some(Bson::String(v))) if t == "decimal" => {
+                v.parse().ok().map(Bson::Decimal128)
+            }
+            _ => None,
+        };
+        value.ok_or_else(|| invalid(&doc))
+    }
+
+    /// Applies [`Bson::from_portable`] to each value in `doc`, without treating `doc` itself as a
+    /// converted value.
+    fn portable_document_contents(doc: Document) -> crate::de::Result<Bson> {
+        doc.into_iter()
+            .map(|(k, v)| Ok((k, Bson::from_portable(v)?)))
+            .collect::<crate::de::Result<Document>>()
+            .map(Bson::Document)
+    }
+
+    /// Returns the number of bytes this value occupies when encoded as a BSON element value,
+    /// excluding the element's type byte and key. This is computed without holding the encoded
+    /// value in memory.
+    ///
+    /// See [`Document::byte_size`] for details.
+    ///
+    /// ```
+    /// use bson::{bson, Bson};
+    ///
+    /// assert_eq!(Bson::Int32(1).byte_size()?, 4);
+    /// // int32 length + "abc" + null terminator
+    /// assert_eq!(Bson::String("abc".to_string()).byte_size()?, 8);
+    /// assert_eq!(bson!([1]).byte_size()?, bson::to_vec(&bson::doc! { "0": 1 })?.len());
+    /// # Ok::<(), bson::ser::Error>(())
+    /// ```
+    pub fn byte_size(&self) -> crate::ser::Result<usize> {
+        crate::ser::serialized_value_len(self)
     }
 
     /// Get the [`ElementType`] of this value.
@@ -625,6 +736,151 @@ impl Bson {
         }
     }
 
+    /// Get the name of this value's type, as used by MongoDB's
+    /// [`$type`](https://www.mongodb.com/docs/manual/reference/operator/query/type/) operator
+    /// (e.g. `"string"`, `"int"`, `"long"`, or `"objectId"`).
+    ///
+    /// These names are stable and suitable for surfacing in error messages.
+    ///
+    /// ```
+    /// use bson::{oid::ObjectId, Bson};
+    ///
+    /// assert_eq!(Bson::Int32(1).type_name(), "int");
+    /// assert_eq!(Bson::ObjectId(ObjectId::new()).type_name(), "objectId");
+    /// ```
+    pub fn type_name(&self) -> &'static str {
+        self.element_type().name()
+    }
+
+    /// Returns whether this value matches the given type, following the semantics of MongoDB's
+    /// [`$type`](https://www.mongodb.com/docs/manual/reference/operator/query/type/) operator.
+    ///
+    /// `type_alias` may be one of the names returned by [`Bson::type_name`], the alias `"number"`
+    /// (which matches any double, 32-bit integer, 64-bit integer, or decimal128), or a numeric type
+    /// code such as `"2"` for strings, `"-1"` for min key, and `"127"` for max key. Unrecognized
+    /// aliases match nothing.
+    ///
+    /// Unlike the server, this does not match arrays by the types of their elements.
+    ///
+    /// ```
+    /// use bson::Bson;
+    ///
+    /// assert!(Bson::Int64(1).matches_type("long"));
+    /// assert!(Bson::Int64(1).matches_type("number"));
+    /// assert!(Bson::Int64(1).matches_type("18"));
+    /// assert!(!Bson::String("1".to_string()).matches_type("number"));
+    /// ```
+    pub fn matches_type(&self, type_alias: &str) -> bool {
+        if type_alias == "number" {
+            return matches!(
+                self,
+                Bson::Double(..) | Bson::Int32(..) | Bson::Int64(..) | Bson::Decimal128(..)
+            );
+        }
+        let element_type = match type_alias.parse::<i32>() {
+            Ok(-1) => Some(ElementType::MinKey),
+            Ok(code @ 1..=127) => ElementType::from(code as u8),
+            Ok(_) => None,
+            Err(_) => ElementType::try_from(type_alias).ok(),
+        };
+        element_type == Some(self.element_type())
+    }
+
+    /// Returns true if this value is of a type that the BSON specification has deprecated, namely
+    /// [`Bson::Symbol`], [`Bson::Undefined`], or [`Bson::DbPointer`].
+    ///
+    /// This only inspects the value itself; use [`Document::contains_deprecated_types`] to also
+    /// check the contents of nested documents and arrays.
+    ///
+    /// ```
+    /// use bson::Bson;
+    ///
+    /// assert!(Bson::Undefined.is_deprecated_type());
+    /// assert!(!Bson::Null.is_deprecated_type());
+    /// ```
+    pub fn is_deprecated_type(&self) -> bool {
+        matches!(
+            self,
+            Bson::Symbol(..) | Bson::Undefined | Bson::DbPointer(..)
+        )
+    }
+
+    /// Returns true if this value is an empty [`Bson::Document`] or [`Bson::Array`]. All other
+    /// values, including [`Bson::Null`] and empty strings, return false.
+    ///
+    /// This can be used with `#[serde(skip_serializing_if)]` to omit empty containers:
+    ///
+    /// ```
+    /// use bson::{doc, Bson};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Event {
+    ///     name: String,
+    ///     #[serde(skip_serializing_if = "Bson::is_empty_container")]
+    ///     tags: Bson,
+    /// }
+    ///
+    /// let event = Event { name: "start".to_string(), tags: Bson::Array(vec![]) };
+    /// assert_eq!(bson::to_document(&event)?, doc! { "name": "start" });
+    /// # Ok::<(), bson::ser::Error>(())
+    /// ```
+    pub fn is_empty_container(&self) -> bool {
+        match self {
+            Bson::Document(doc) => doc.is_empty(),
+            Bson::Array(arr) => arr.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Returns true if this value or any value nested within it is of a deprecated type.
+    pub(crate) fn contains_deprecated_types(&self) -> bool {
+        match self {
+            Bson::Document(doc) => doc.contains_deprecated_types(),
+            Bson::Array(arr) => arr.iter().any(Bson::contains_deprecated_types),
+            Bson::JavaScriptCodeWithScope(code) => code.scope.contains_deprecated_types(),
+            other => other.is_deprecated_type(),
+        }
+    }
+
+    /// Returns the total number of values in this value, counting the value itself and every
+    /// value nested within it, including those in code-with-scope scopes.
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// assert_eq!(bson!(1).node_count(), 1);
+    /// assert_eq!(bson!({ "a": 1, "b": [2, 3] }).node_count(), 5);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        1 + match self {
+            Bson::Document(doc) => doc.node_count(),
+            Bson::Array(arr) => arr.iter().map(Bson::node_count).sum(),
+            Bson::JavaScriptCodeWithScope(code) => code.scope.node_count(),
+            _ => 0,
+        }
+    }
+
+    /// Returns the maximum nesting depth of this value. Scalar values have a depth of 0, and each
+    /// document, array, or code-with-scope scope adds one level, matching the depth counted by
+    /// [`ValidationOptions::max_depth`](crate::raw::ValidationOptions::max_depth).
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// assert_eq!(bson!("a").max_depth(), 0);
+    /// assert_eq!(bson!({}).max_depth(), 1);
+    /// assert_eq!(bson!({ "a": [{ "b": 1 }] }).max_depth(), 3);
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        match self {
+            Bson::Document(doc) => doc.max_depth(),
+            Bson::Array(arr) => 1 + arr.iter().map(Bson::max_depth).max().unwrap_or(0),
+            Bson::JavaScriptCodeWithScope(code) => code.scope.max_depth(),
+            _ => 0,
+        }
+    }
+
     /// Converts to extended format.
     /// This function mainly used for [extended JSON format](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/).
     // TODO RUST-426: Investigate either removing this from the serde implementation or unifying
@@ -991,6 +1247,204 @@ impl Bson {
         }
     }
 
+    /// The error returned by [`Bson::into_array`] and [`Bson::into_document`] when `self` is not of
+    /// the `expected` type.
+    fn invalid_type_error(&self, expected: &'static str) -> crate::de::Error {
+        serde::de::Error::invalid_type(self.as_unexpected(), &expected)
+    }
+
+    /// The error returned by the methods below that modify `self` in place when it is not of the
+    /// `expected` type.
+    fn unexpected_type_error(&self, expected: ElementType) -> UnexpectedTypeError {
+        UnexpectedTypeError {
+            expected,
+            actual: self.element_type(),
+        }
+    }
+
+    /// If `self` is [`Array`](Bson::Array), return its value without cloning. Returns an error
+    /// describing the actual type of `self` otherwise.
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// assert_eq!(bson!([1, 2]).into_array().unwrap(), vec![bson!(1), bson!(2)]);
+    /// assert!(bson!("x").into_array().is_err());
+    /// ```
+    pub fn into_array(self) -> crate::de::Result<Array> {
+        match self {
+            Bson::Array(v) => Ok(v),
+            other => Err(other.invalid_type_error("an array")),
+        }
+    }
+
+    /// If `self` is [`Document`](Bson::Document), return its value without cloning. Returns an
+    /// error describing the actual type of `self` otherwise.
+    ///
+    /// ```
+    /// use bson::{bson, doc};
+    ///
+    /// assert_eq!(bson!({ "a": 1 }).into_document().unwrap(), doc! { "a": 1 });
+    /// assert!(bson!(1).into_document().is_err());
+    /// ```
+    pub fn into_document(self) -> crate::de::Result<Document> {
+        match self {
+            Bson::Document(v) => Ok(v),
+            other => Err(other.invalid_type_error("a document")),
+        }
+    }
+
+    /// If `self` is [`Document`](Bson::Document), return an iterator over its owned entries.
+    /// Returns an error describing the actual type of `self` otherwise.
+    ///
+    /// ```
+    /// use bson::{bson, Bson};
+    ///
+    /// let entries: Vec<(String, Bson)> = bson!({ "a": 1, "b": "two" })
+    ///     .into_document_iter()
+    ///     .unwrap()
+    ///     .collect();
+    /// assert_eq!(entries, vec![("a".to_string(), bson!(1)), ("b".to_string(), bson!("two"))]);
+    /// assert!(bson!([1]).into_document_iter().is_err());
+    /// ```
+    pub fn into_document_iter(self) -> crate::de::Result<crate::document::IntoIter> {
+        self.into_document().map(IntoIterator::into_iter)
+    }
+
+    /// If `self` is [`Array`](Bson::Array), append `value` to it. Returns an
+    /// [`UnexpectedTypeError`] describing the actual type of `self` otherwise.
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// let mut array = bson!([1]);
+    /// array.array_push("two").unwrap();
+    /// assert_eq!(array, bson!([1, "two"]));
+    /// assert!(bson!(1).array_push(2).is_err());
+    /// ```
+    pub fn array_push(&mut self, value: impl Into<Bson>) -> Result<(), UnexpectedTypeError> {
+        match self {
+            Bson::Array(v) => {
+                v.push(value.into());
+                Ok(())
+            }
+            other => Err(other.unexpected_type_error(ElementType::Array)),
+        }
+    }
+
+    /// If `self` is [`Array`](Bson::Array), sort its elements in place using the comparator
+    /// `compare`. Returns an [`UnexpectedTypeError`] describing the actual type of `self`
+    /// otherwise.
+    ///
+    /// The sort is stable. See [`slice::sort_by`] for details.
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// let mut array = bson!(["b", "c", "a"]);
+    /// array.sort_array(|a, b| a.as_str().cmp(&b.as_str())).unwrap();
+    /// assert_eq!(array, bson!(["a", "b", "c"]));
+    /// assert!(bson!("abc").sort_array(|_, _| std::cmp::Ordering::Equal).is_err());
+    /// ```
+    pub fn sort_array<F>(&mut self, compare: F) -> Result<(), UnexpectedTypeError>
+    where
+        F: FnMut(&Bson, &Bson) -> Ordering,
+    {
+        match self {
+            Bson::Array(v) => {
+                v.sort_by(compare);
+                Ok(())
+            }
+            other => Err(other.unexpected_type_error(ElementType::Array)),
+        }
+    }
+
+    /// If `self` is [`Array`](Bson::Array), sort its elements in place by the key extracted by
+    /// `key`. Returns an [`UnexpectedTypeError`] describing the actual type of `self` otherwise.
+    ///
+    /// The sort is stable. See [`slice::sort_by_key`] for details.
+    ///
+    /// This can be used to sort an array of documents by one of their fields when the field's
+    /// type is known. See [`Bson::sort_array_by_field`] to sort by a field of any type.
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// let mut people = bson!([
+    ///     { "name": "b", "age": 40 },
+    ///     { "name": "a", "age": 25 },
+    ///     { "name": "c" },
+    /// ]);
+    /// people
+    ///     .sort_array_by_key(|p| p.as_document().and_then(|d| d.get_i32("age").ok()))
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     people,
+    ///     bson!([{ "name": "c" }, { "name": "a", "age": 25 }, { "name": "b", "age": 40 }])
+    /// );
+    /// ```
+    pub fn sort_array_by_key<K, F>(&mut self, key: F) -> Result<(), UnexpectedTypeError>
+    where
+        F: FnMut(&Bson) -> K,
+        K: Ord,
+    {
+        match self {
+            Bson::Array(v) => {
+                v.sort_by_key(key);
+                Ok(())
+            }
+            other => Err(other.unexpected_type_error(ElementType::Array)),
+        }
+    }
+
+    /// If `self` is [`Array`](Bson::Array), sort its elements in place by the value of `field` in
+    /// each element. Returns an [`UnexpectedTypeError`] describing the actual type of `self`
+    /// otherwise.
+    ///
+    /// Values are ordered as MongoDB orders them in a sort: first by type, in the order MinKey,
+    /// null, numbers, strings and symbols, documents, arrays, binary, ObjectId, booleans,
+    /// datetimes, timestamps, regular expressions, DBPointers, JavaScript code, JavaScript code
+    /// with scope, and MaxKey, and then by value. Numbers of different types are compared by
+    /// their numeric value, with NaN before all other numbers. Elements that are not documents or
+    /// do not contain `field` sort as if the field were null.
+    ///
+    /// The sort is stable. See [`slice::sort_by`] for details.
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// let mut people = bson!([
+    ///     { "name": "b", "age": 40 },
+    ///     { "name": "a", "age": 25.5 },
+    ///     { "name": "c" },
+    /// ]);
+    /// people.sort_array_by_field("age").unwrap();
+    /// assert_eq!(
+    ///     people,
+    ///     bson!([{ "name": "c" }, { "name": "a", "age": 25.5 }, { "name": "b", "age": 40 }])
+    /// );
+    /// ```
+    pub fn sort_array_by_field(&mut self, field: &str) -> Result<(), UnexpectedTypeError> {
+        fn value<'a>(element: &'a Bson, field: &str) -> &'a Bson {
+            element
+                .as_document()
+                .and_then(|doc| doc.get(field))
+                .unwrap_or(&Bson::Null)
+        }
+        self.sort_array(|a, b| cmp_sort_order(value(a, field), value(b, field)))
+    }
+
+    /// If `self` is [`Array`](Bson::Array), return the element at index `i`. Returns [`None`] if
+    /// `self` is not an array or the index is out of bounds.
+    pub fn array_get(&self, i: usize) -> Option<&Bson> {
+        self.as_array().and_then(|v| v.get(i))
+    }
+
+    /// If `self` is [`Array`](Bson::Array), return its length. Returns [`None`] otherwise.
+    pub fn array_len(&self) -> Option<usize> {
+        self.as_array().map(|v| v.len())
+    }
+
     /// If `self` is [`Boolean`](Bson::Boolean), return its value. Returns [`None`] otherwise.
     pub fn as_bool(&self) -> Option<bool> {
         match *self {
@@ -1007,6 +1461,33 @@ impl Bson {
         }
     }
 
+    /// Returns the value of `self` as an `i32` if it is an [`Int32`](Bson::Int32), or an
+    /// [`Int64`](Bson::Int64) or a [`Double`](Bson::Double) with no fractional part whose value is
+    /// within the range of `i32`. Returns [`None`] otherwise.
+    ///
+    /// Use [`Bson::as_i32`] to only accept [`Int32`](Bson::Int32) values.
+    ///
+    /// ```
+    /// use bson::Bson;
+    ///
+    /// assert_eq!(Bson::Int64(5).as_i32_checked(), Some(5));
+    /// assert_eq!(Bson::Double(5.0).as_i32_checked(), Some(5));
+    /// assert_eq!(Bson::Int64(i64::MAX).as_i32_checked(), None);
+    /// assert_eq!(Bson::Double(5.5).as_i32_checked(), None);
+    /// ```
+    pub fn as_i32_checked(&self) -> Option<i32> {
+        match *self {
+            Bson::Int32(v) => Some(v),
+            Bson::Int64(v) => i32::try_from(v).ok(),
+            Bson::Double(v)
+                if v.fract() == 0.0 && v >= f64::from(i32::MIN) && v <= f64::from(i32::MAX) =>
+            {
+                Some(v as i32)
+            }
+            _ => None,
+        }
+    }
+
     /// If `self` is [`Int64`](Bson::Int64), return its value. Returns [`None`] otherwise.
     pub fn as_i64(&self) -> Option<i64> {
         match *self {
@@ -1089,6 +1570,252 @@ impl Bson {
             _ => None,
         }
     }
+
+    /// Looks up a value by a [JSON Pointer](https://tools.ietf.org/html/rfc6901).
+    ///
+    /// A JSON Pointer is a Unicode string of reference tokens, each prefixed by `/`. Tokens are
+    /// matched against the keys of documents and the indices of arrays, with `~1` and `~0` in a
+    /// token unescaped to `/` and `~` respectively. The empty string refers to `self`. Returns
+    /// [`None`] if the pointer is malformed or does not resolve to a value.
+    ///
+    /// This mirrors [`serde_json::Value::pointer`].
+    ///
+    /// ```
+    /// use bson::{bson, Bson};
+    ///
+    /// let value = bson!({
+    ///     "x": { "y": ["z", "zz"] },
+    ///     "a/b": { "~c": 1 },
+    /// });
+    ///
+    /// assert_eq!(value.pointer("/x/y/1"), Some(&Bson::String("zz".to_string())));
+    /// assert_eq!(value.pointer("/a~1b/~0c"), Some(&Bson::Int32(1)));
+    /// assert_eq!(value.pointer("/x/y/2"), None);
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Bson> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        pointer
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .try_fold(self, |target, token| match target {
+                Bson::Document(doc) => doc.get(&token),
+                Bson::Array(arr) => parse_pointer_index(&token).and_then(|i| arr.get(i)),
+                _ => None,
+            })
+    }
+
+    /// Replaces the value of every entry whose key matches `should_redact` with
+    /// `Bson::String("***")`, searching recursively through nested documents and arrays. This is
+    /// useful for removing sensitive data from values before logging them.
+    ///
+    /// [`Bson::is_sensitive_key`] can be used as a predicate that matches commonly used names for
+    /// sensitive fields.
+    ///
+    /// ```
+    /// use bson::{bson, Bson};
+    ///
+    /// let mut value = bson!({ "user": "alice", "auth": { "password": "hunter2" } });
+    /// value.redact(Bson::is_sensitive_key);
+    /// assert_eq!(value, bson!({ "user": "alice", "auth": { "password": "***" } }));
+    /// ```
+    pub fn redact<F: Fn(&str) -> bool>(&mut self, should_redact: F) {
+        self.redact_with(&should_redact)
+    }
+
+    pub(crate) fn redact_with(&mut self, should_redact: &dyn Fn(&str) -> bool) {
+        match self {
+            Bson::Document(doc) => doc.redact_with(should_redact),
+            Bson::Array(arr) => arr.iter_mut().for_each(|v| v.redact_with(should_redact)),
+            _ => {}
+        }
+    }
+
+    /// Removes every entry whose value is [`Bson::Null`], searching recursively through nested
+    /// documents and arrays. Null elements of arrays are kept so that the indexes of the remaining
+    /// elements are unchanged.
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// let mut value = bson!({ "a": null, "b": { "c": null, "d": 1 }, "e": [null, { "f": null }] });
+    /// value.remove_nulls();
+    /// assert_eq!(value, bson!({ "b": { "d": 1 }, "e": [null, {}] }));
+    /// ```
+    pub fn remove_nulls(&mut self) {
+        self.remove_nulls_with(false)
+    }
+
+    /// Like [`Bson::remove_nulls`], but also removes entries whose values are empty documents or
+    /// arrays, including those that become empty once their own null entries are removed.
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// let mut value = bson!({ "a": null, "b": { "c": null }, "d": [], "e": [{ "f": null }] });
+    /// value.remove_nulls_and_empty();
+    /// assert_eq!(value, bson!({ "e": [{}] }));
+    /// ```
+    pub fn remove_nulls_and_empty(&mut self) {
+        self.remove_nulls_with(true)
+    }
+
+    pub(crate) fn remove_nulls_with(&mut self, remove_empty: bool) {
+        match self {
+            Bson::Document(doc) => doc.remove_nulls_with(remove_empty),
+            Bson::Array(arr) => arr
+                .iter_mut()
+                .for_each(|v| v.remove_nulls_with(remove_empty)),
+            _ => {}
+        }
+    }
+
+    /// Returns whether `key` looks like the name of a field holding sensitive data, for use with
+    /// [`Bson::redact`]. Keys are matched case-insensitively, ignoring `_` and `-`, and match if
+    /// they contain any of `password`, `passwd`, `secret`, `token`, `apikey`, `authorization`,
+    /// `credential`, or `privatekey`.
+    ///
+    /// ```
+    /// use bson::Bson;
+    ///
+    /// assert!(Bson::is_sensitive_key("accessToken"));
+    /// assert!(Bson::is_sensitive_key("API_KEY"));
+    /// assert!(!Bson::is_sensitive_key("username"));
+    /// ```
+    pub fn is_sensitive_key(key: &str) -> bool {
+        const SENSITIVE: &[&str] = &[
+            "password",
+            "passwd",
+            "secret",
+            "token",
+            "apikey",
+            "authorization",
+            "credential",
+            "privatekey",
+        ];
+
+        let normalized: String = key
+            .chars()
+            .filter(|c| *c != '_' && *c != '-')
+            .flat_map(char::to_lowercase)
+            .collect();
+        SENSITIVE.iter().any(|s| normalized.contains(s))
+    }
+
+    /// Returns whether `self` and `other` are equal, except that [`Bson::Double`] values are
+    /// considered equal if they differ by no more than `epsilon`. Documents and arrays are
+    /// compared element by element in the same way, and all other values must be exactly equal.
+    ///
+    /// As with [`PartialEq`], the order of keys within documents is ignored and NaN is not equal
+    /// to anything, including itself. This is primarily intended for use in test assertions.
+    ///
+    /// ```
+    /// use bson::bson;
+    ///
+    /// let a = bson!({ "x": 0.1 + 0.2, "y": [1.0, "z"] });
+    /// let b = bson!({ "x": 0.3, "y": [1.0, "z"] });
+    /// assert_ne!(a, b);
+    /// assert!(a.approx_eq(&b, 1e-9));
+    /// ```
+    pub fn approx_eq(&self, other: &Bson, epsilon: f64) -> bool {
+        match (self, other) {
+            (Bson::Double(a), Bson::Double(b)) => a == b || (a - b).abs() <= epsilon,
+            (Bson::Array(a), Bson::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.approx_eq(b, epsilon))
+            }
+            (Bson::Document(a), Bson::Document(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(key, a)| b.get(key).is_some_and(|b| a.approx_eq(b, epsilon)))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Visits `self` and every value nested within it in depth-first order, calling `visitor`
+    /// with the path to each value and the value itself. The path of `self` is empty; values
+    /// within documents are identified by [`PathSegment::Key`] and values within arrays by
+    /// [`PathSegment::Index`]. The scopes of code-with-scope values are not traversed.
+    ///
+    /// ```
+    /// use bson::{bson, PathSegment};
+    ///
+    /// let value = bson!({ "a": { "b": [1, 2] } });
+    /// let mut paths = Vec::new();
+    /// value.walk(|path, _| {
+    ///     let path: Vec<String> = path
+    ///         .iter()
+    ///         .map(|segment| match segment {
+    ///             PathSegment::Key(key) => key.to_string(),
+    ///             PathSegment::Index(index) => index.to_string(),
+    ///         })
+    ///         .collect();
+    ///     paths.push(path.join("."));
+    /// });
+    /// assert_eq!(paths, vec!["", "a", "a.b", "a.b.0", "a.b.1"]);
+    /// ```
+    pub fn walk<'a, F>(&'a self, mut visitor: F)
+    where
+        F: FnMut(&[PathSegment<'a>], &'a Bson),
+    {
+        walk_value(self, &mut Vec::new(), &mut visitor);
+    }
+}
+
+/// A single step in the path to a value visited by [`Bson::walk`] or [`Document::walk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathSegment<'a> {
+    /// The key of a value within a document.
+    Key(&'a str),
+
+    /// The index of a value within an array.
+    Index(usize),
+}
+
+pub(crate) fn walk_value<'a, F>(value: &'a Bson, path: &mut Vec<PathSegment<'a>>, visitor: &mut F)
+where
+    F: FnMut(&[PathSegment<'a>], &'a Bson),
+{
+    visitor(path, value);
+    match value {
+        Bson::Document(doc) => walk_document(doc, path, visitor),
+        Bson::Array(arr) => {
+            for (index, item) in arr.iter().enumerate() {
+                path.push(PathSegment::Index(index));
+                walk_value(item, path, visitor);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn walk_document<'a, F>(
+    doc: &'a Document,
+    path: &mut Vec<PathSegment<'a>>,
+    visitor: &mut F,
+) where
+    F: FnMut(&[PathSegment<'a>], &'a Bson),
+{
+    for (key, item) in doc {
+        path.push(PathSegment::Key(key));
+        walk_value(item, path, visitor);
+        path.pop();
+    }
+}
+
+/// Parse an array index from a JSON Pointer token, rejecting leading `+` signs and leading zeros
+/// as required by RFC 6901.
+fn parse_pointer_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() != 1) {
+        return None;
+    }
+    token.parse().ok()
 }
 
 /// Represents a BSON timestamp value.
@@ -1109,6 +1836,68 @@ impl Display for Timestamp {
 }
 
 impl Timestamp {
+    /// Returns the [`crate::DateTime`] corresponding to the `time` field of this timestamp, which
+    /// counts seconds since the Unix epoch. The `increment` is not represented in the result.
+    ///
+    /// BSON timestamps are primarily used internally by MongoDB, for example to order oplog
+    /// entries, and have different semantics than BSON datetimes; this conversion is mainly
+    /// useful for correlating such timestamps with wall-clock times.
+    ///
+    /// ```
+    /// use bson::{DateTime, Timestamp};
+    ///
+    /// let ts = Timestamp { time: 1_672_531_200, increment: 7 };
+    /// assert_eq!(ts.to_datetime(), DateTime::from_millis(1_672_531_200_000));
+    /// ```
+    pub fn to_datetime(&self) -> crate::DateTime {
+        crate::DateTime::from_millis(i64::from(self.time) * 1000)
+    }
+
+    /// Constructs a [`Timestamp`] whose `time` field is the number of whole seconds between the
+    /// Unix epoch and `dt`, with the given `increment`.
+    ///
+    /// Any sub-second precision in `dt` is truncated. Datetimes before the Unix epoch or after the
+    /// largest time representable by a `u32` number of seconds are clamped to the nearest
+    /// representable value.
+    ///
+    /// ```
+    /// use bson::{DateTime, Timestamp};
+    ///
+    /// let dt = DateTime::from_millis(1_672_531_200_999);
+    /// let ts = Timestamp::from_datetime(dt, 1);
+    /// assert_eq!(ts, Timestamp { time: 1_672_531_200, increment: 1 });
+    /// ```
+    pub fn from_datetime(dt: crate::DateTime, increment: u32) -> Self {
+        let seconds = dt.timestamp_millis().div_euclid(1000);
+        let time = seconds.clamp(0, i64::from(u32::MAX)) as u32;
+        Self { time, increment }
+    }
+
+    /// Parses a [`Timestamp`] from its [extended JSON](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/)
+    /// representation, `{ "$timestamp": { "t": <u32>, "i": <u32> } }`. This is the same format in
+    /// both canonical and relaxed mode.
+    ///
+    /// As required by the extended JSON spec, `t` and `i` must be JSON integers; string values
+    /// are rejected.
+    ///
+    /// ```
+    /// use bson::Timestamp;
+    /// use serde_json::json;
+    ///
+    /// let value = json!({ "$timestamp": { "t": 1_672_531_200u32, "i": 7 } });
+    /// let ts = Timestamp::from_extjson(&value)?;
+    /// assert_eq!(ts, Timestamp { time: 1_672_531_200, increment: 7 });
+    ///
+    /// assert!(Timestamp::from_extjson(&json!({ "$timestamp": { "t": "1", "i": "1" } })).is_err());
+    /// # Ok::<(), bson::extjson::de::Error>(())
+    /// ```
+    pub fn from_extjson(value: &Value) -> crate::extjson::de::Result<Self> {
+        use serde::Deserialize;
+
+        let ts = crate::extjson::models::Timestamp::deserialize(value)?;
+        Ok(ts.parse())
+    }
+
     pub(crate) fn to_le_bytes(self) -> [u8; 8] {
         let mut out = [0; 8];
         out[0..4].copy_from_slice(&self.increment.to_le_bytes());
@@ -1173,6 +1962,170 @@ pub struct JavaScriptCodeWithScope {
     pub scope: Document,
 }
 
+/// The rank of `value`'s type in MongoDB's sort order, with numeric types sharing a rank.
+fn sort_type_rank(value: &Bson) -> u8 {
+    match value {
+        Bson::MinKey => 0,
+        Bson::Undefined | Bson::Null => 1,
+        Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) | Bson::Decimal128(_) => 2,
+        Bson::String(_) | Bson::Symbol(_) => 3,
+        Bson::Document(_) => 4,
+        Bson::Array(_) => 5,
+        Bson::Binary(_) => 6,
+        Bson::ObjectId(_) => 7,
+        Bson::Boolean(_) => 8,
+        Bson::DateTime(_) => 9,
+        Bson::Timestamp(_) => 10,
+        Bson::RegularExpression(_) => 11,
+        Bson::DbPointer(_) => 12,
+        Bson::JavaScriptCode(_) => 13,
+        Bson::JavaScriptCodeWithScope(_) => 14,
+        Bson::MaxKey => 15,
+    }
+}
+
+/// Compares two values in MongoDB's sort order, as described in [`Bson::sort_array_by_field`].
+fn cmp_sort_order(a: &Bson, b: &Bson) -> Ordering {
+    fn as_i64(value: &Bson) -> Option<i64> {
+        match *value {
+            Bson::Int32(v) => Some(v.into()),
+            Bson::Int64(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn as_f64(value: &Bson) -> f64 {
+        match *value {
+            Bson::Int32(v) => v.into(),
+            Bson::Int64(v) => v as f64,
+            Bson::Double(v) => v,
+            Bson::Decimal128(v) => v.to_string().parse().unwrap_or(f64::NAN),
+            _ => f64::NAN,
+        }
+    }
+
+    let by_type = sort_type_rank(a).cmp(&sort_type_rank(b));
+    if by_type != Ordering::Equal {
+        return by_type;
+    }
+
+    match (a, b) {
+        (Bson::String(a) | Bson::Symbol(a), Bson::String(b) | Bson::Symbol(b)) => a.cmp(b),
+        (Bson::Document(a), Bson::Document(b)) => cmp_documents_sort_order(a, b),
+        (Bson::Array(a), Bson::Array(b)) => a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| cmp_sort_order(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        (Bson::Binary(a), Bson::Binary(b)) => a
+            .bytes
+            .len()
+            .cmp(&b.bytes.len())
+            .then_with(|| u8::from(a.subtype).cmp(&u8::from(b.subtype)))
+            .then_with(|| a.bytes.cmp(&b.bytes)),
+        (Bson::ObjectId(a), Bson::ObjectId(b)) => a.cmp(b),
+        (Bson::Boolean(a), Bson::Boolean(b)) => a.cmp(b),
+        (Bson::DateTime(a), Bson::DateTime(b)) => a.cmp(b),
+        (Bson::Timestamp(a), Bson::Timestamp(b)) => a.cmp(b),
+        (Bson::RegularExpression(a), Bson::RegularExpression(b)) => a
+            .pattern
+            .cmp(&b.pattern)
+            .then_with(|| a.options.cmp(&b.options)),
+        (Bson::DbPointer(a), Bson::DbPointer(b)) => {
+            a.namespace.cmp(&b.namespace).then_with(|| a.id.cmp(&b.id))
+        }
+        (Bson::JavaScriptCode(a), Bson::JavaScriptCode(b)) => a.cmp(b),
+        (Bson::JavaScriptCodeWithScope(a), Bson::JavaScriptCodeWithScope(b)) => a
+            .code
+            .cmp(&b.code)
+            .then_with(|| cmp_documents_sort_order(&a.scope, &b.scope)),
+        _ => match (as_i64(a), as_i64(b)) {
+            // compare integers exactly, since converting them to f64 can lose precision
+            (Some(a), Some(b)) => a.cmp(&b),
+            // NaN sorts before all other numbers
+            _ => {
+                let (a, b) = (as_f64(a), as_f64(b));
+                match (a.is_nan(), b.is_nan()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Less,
+                    (false, true) => Ordering::Greater,
+                    (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                }
+            }
+        },
+    }
+}
+
+/// Compares two documents in MongoDB's sort order: field by field, first by the type of the value,
+/// then by the key, and then by the value itself.
+fn cmp_documents_sort_order(a: &Document, b: &Document) -> Ordering {
+    a.iter()
+        .zip(b.iter())
+        .map(|((ka, va), (kb, vb))| {
+            sort_type_rank(va)
+                .cmp(&sort_type_rank(vb))
+                .then_with(|| ka.cmp(kb))
+                .then_with(|| cmp_sort_order(va, vb))
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+impl JavaScriptCodeWithScope {
+    /// Construct a new [`JavaScriptCodeWithScope`] from the given code and scope.
+    pub fn new(code: impl Into<String>, scope: Document) -> Self {
+        Self {
+            code: code.into(),
+            scope,
+        }
+    }
+
+    /// Returns the number of bytes this value occupies when encoded as a BSON code-with-scope
+    /// element value. As required by the BSON specification, this includes the leading `int32`
+    /// that holds the total length itself.
+    ///
+    /// Returns an error if the scope cannot be encoded, e.g. if one of its keys contains a null
+    /// byte, or if the encoded value would exceed the maximum BSON size of 16MiB.
+    ///
+    /// ```
+    /// # use bson::{doc, JavaScriptCodeWithScope};
+    /// let code = JavaScriptCodeWithScope::new("x", doc! {});
+    /// // int32 total length + int32 string length + "x\0" + empty document
+    /// assert_eq!(code.encoded_len()?, 4 + 4 + 2 + 5);
+    /// # Ok::<(), bson::ser::Error>(())
+    /// ```
+    pub fn encoded_len(&self) -> crate::ser::Result<usize> {
+        let len = crate::ser::serialized_value_len(self)?;
+        if len > crate::de::MAX_BSON_SIZE as usize {
+            return Err(crate::ser::Error::SerializationError {
+                message: format!(
+                    "code with scope length {} exceeds maximum size of {}",
+                    len,
+                    crate::de::MAX_BSON_SIZE
+                ),
+            });
+        }
+        Ok(len)
+    }
+
+    /// Encodes this value as the bytes of a BSON code-with-scope element value: the total length,
+    /// the code string, and the scope document.
+    ///
+    /// Returns an error if the scope cannot be serialized or if the encoded value would exceed
+    /// the maximum BSON size of 16MiB.
+    pub fn try_to_vec(&self) -> crate::ser::Result<Vec<u8>> {
+        let len = self.encoded_len()?;
+        let scope = crate::to_vec(&self.scope)?;
+
+        let mut buf = Vec::with_capacity(len);
+        buf.extend(&(len as i32).to_le_bytes());
+        crate::ser::write_string(&mut buf, &self.code);
+        buf.extend(scope);
+        Ok(buf)
+    }
+}
+
 impl Display for JavaScriptCodeWithScope {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.write_str(&self.code)
@@ -191,11 +191,42 @@ impl crate::DateTime {
 
     /// Makes a new [`DateTime`] from the number of non-leap milliseconds since
     /// January 1, 1970 0:00:00 UTC (aka "UNIX timestamp").
+    ///
+    /// This is the lossless inverse of [`DateTime::as_millis`]. Note that the argument is in
+    /// milliseconds, not seconds.
     pub const fn from_millis(date: i64) -> Self {
         Self(date)
     }
 
+    /// Makes a new [`DateTime`] from a number of milliseconds since January 1, 1970 0:00:00 UTC
+    /// that may lie outside the range BSON can represent, clamping it to [`DateTime::MIN`] or
+    /// [`DateTime::MAX`] instead of overflowing.
+    ///
+    /// ```
+    /// use bson::DateTime;
+    ///
+    /// assert_eq!(DateTime::from_millis_saturating(1_000), DateTime::from_millis(1_000));
+    /// assert_eq!(DateTime::from_millis_saturating(i128::MAX), DateTime::MAX);
+    /// assert_eq!(DateTime::from_millis_saturating(i128::MIN), DateTime::MIN);
+    /// ```
+    pub const fn from_millis_saturating(millis: i128) -> Self {
+        // i64::try_from isn't const
+        if millis > i64::MAX as i128 {
+            Self::MAX
+        } else if millis < i64::MIN as i128 {
+            Self::MIN
+        } else {
+            Self::from_millis(millis as i64)
+        }
+    }
+
     /// Returns a [`DateTime`] which corresponds to the current date and time.
+    ///
+    /// The current time is read from the system's wall clock via [`SystemTime::now`] and
+    /// truncated, not rounded, to millisecond precision, so any sub-millisecond component reported
+    /// by the platform is discarded. The wall clock is not
+    /// monotonic: successive calls may return equal values, and may even go backwards if the
+    /// system clock is adjusted. Use [`std::time::Instant`] for measuring elapsed time.
     pub fn now() -> DateTime {
         Self::from_system_time(SystemTime::now())
     }
@@ -217,6 +248,42 @@ impl crate::DateTime {
         DateTimeBuilder::default()
     }
 
+    /// Constructs a [`DateTime`] from the given UTC calendar date and time of day. This is
+    /// equivalent to setting every field of a [`DateTime::builder`].
+    ///
+    /// Returns an error if any component is out of range, including days that do not exist in the
+    /// given month (e.g. February 29 in a non-leap year), or if the year is outside of ±9999.
+    ///
+    /// ```
+    /// use bson::DateTime;
+    ///
+    /// let dt = DateTime::from_ymd_hms_milli(2024, 2, 29, 12, 30, 15, 250)?;
+    /// assert_eq!(dt.try_to_rfc3339_string()?, "2024-02-29T12:30:15.25Z");
+    ///
+    /// assert!(DateTime::from_ymd_hms_milli(2023, 2, 29, 0, 0, 0, 0).is_err());
+    /// assert!(DateTime::from_ymd_hms_milli(2024, 1, 1, 24, 0, 0, 0).is_err());
+    /// # Ok::<(), bson::datetime::Error>(())
+    /// ```
+    pub fn from_ymd_hms_milli(
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        min: u8,
+        sec: u8,
+        milli: u16,
+    ) -> Result<Self> {
+        Self::builder()
+            .year(year)
+            .month(month)
+            .day(day)
+            .hour(hour)
+            .minute(min)
+            .second(sec)
+            .millisecond(milli)
+            .build()
+    }
+
     /// Convert this [`DateTime`] to a [`chrono::DateTime<Utc>`].
     ///
     /// Note: Not every BSON datetime can be represented as a [`chrono::DateTime`]. For such dates,
@@ -359,10 +426,26 @@ impl crate::DateTime {
     }
 
     /// Returns the number of non-leap-milliseconds since January 1, 1970 UTC.
+    ///
+    /// This is equivalent to [`DateTime::as_millis`].
     pub const fn timestamp_millis(self) -> i64 {
         self.0
     }
 
+    /// Returns the number of non-leap milliseconds since January 1, 1970 UTC.
+    ///
+    /// This is the lossless inverse of [`DateTime::from_millis`]. Note that the returned value is
+    /// in milliseconds, not seconds.
+    ///
+    /// ```
+    /// let dt = bson::DateTime::from_millis(1_600_000_000_123);
+    /// assert_eq!(dt.as_millis(), 1_600_000_000_123);
+    /// assert_eq!(dt.as_millis(), dt.timestamp_millis());
+    /// ```
+    pub const fn as_millis(self) -> i64 {
+        self.0
+    }
+
     /// Adds `millis` milliseconds to the [`DateTime`] saturating at [`DateTime::MIN`] and
     /// [`DateTime::MAX`].
     pub const fn saturating_add_millis(self, millis: i64) -> Self {
@@ -399,6 +482,54 @@ impl crate::DateTime {
             })
     }
 
+    /// Convert this [`DateTime`] to an RFC 3339 formatted string whose fractional seconds have
+    /// exactly the number of digits given by `precision`.
+    ///
+    /// Since BSON datetimes only store milliseconds, [`TimePrecision::Micros`] and
+    /// [`TimePrecision::Nanos`] pad the fraction with zeros, and [`TimePrecision::Seconds`]
+    /// truncates the milliseconds.
+    ///
+    /// ```
+    /// use bson::{datetime::TimePrecision, DateTime};
+    ///
+    /// let dt = DateTime::from_millis(1_672_531_200_120);
+    /// assert_eq!(
+    ///     dt.to_rfc3339_string_with_precision(TimePrecision::Seconds)?,
+    ///     "2023-01-01T00:00:00Z"
+    /// );
+    /// assert_eq!(
+    ///     dt.to_rfc3339_string_with_precision(TimePrecision::Millis)?,
+    ///     "2023-01-01T00:00:00.120Z"
+    /// );
+    /// assert_eq!(
+    ///     dt.to_rfc3339_string_with_precision(TimePrecision::Micros)?,
+    ///     "2023-01-01T00:00:00.120000Z"
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_rfc3339_string_with_precision(self, precision: TimePrecision) -> Result<String> {
+        let millis = self.0.rem_euclid(1000);
+        let seconds = self
+            .0
+            .checked_sub(millis)
+            .ok_or_else(|| Error::CannotFormat {
+                message: format!("{} is out of range for RFC 3339 formatting", self.0),
+            })?;
+        let mut s = Self::from_millis(seconds).try_to_rfc3339_string()?;
+
+        // The UTC offset is always formatted as "Z", and no fraction is emitted for a whole
+        // number of seconds, so the fraction can be inserted before the final character.
+        let offset = s.split_off(s.len() - 1);
+        match precision {
+            TimePrecision::Seconds => {}
+            TimePrecision::Millis => s.push_str(&format!(".{:03}", millis)),
+            TimePrecision::Micros => s.push_str(&format!(".{:03}000", millis)),
+            TimePrecision::Nanos => s.push_str(&format!(".{:03}000000", millis)),
+        }
+        s.push_str(&offset);
+        Ok(s)
+    }
+
     /// Convert the given RFC 3339 formatted string to a [`DateTime`], truncating it to millisecond
     /// precision.
     pub fn parse_rfc3339_str(s: impl AsRef<str>) -> Result<Self> {
@@ -479,6 +610,42 @@ impl<T: chrono::TimeZone> From<chrono::DateTime<T>> for crate::DateTime {
     }
 }
 
+/// Compares at millisecond granularity: `other` is truncated to milliseconds as by
+/// [`DateTime::from_chrono`] before comparing, so values that differ only in sub-millisecond
+/// precision compare as equal.
+#[cfg(feature = "chrono-0_4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono-0_4")))]
+impl PartialEq<chrono::DateTime<Utc>> for crate::DateTime {
+    fn eq(&self, other: &chrono::DateTime<Utc>) -> bool {
+        *self == Self::from_chrono(*other)
+    }
+}
+
+/// Compares at millisecond granularity, as described on the [`PartialEq`] implementation.
+#[cfg(feature = "chrono-0_4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono-0_4")))]
+impl PartialOrd<chrono::DateTime<Utc>> for crate::DateTime {
+    fn partial_cmp(&self, other: &chrono::DateTime<Utc>) -> Option<std::cmp::Ordering> {
+        self.partial_cmp(&Self::from_chrono(*other))
+    }
+}
+
+#[cfg(feature = "chrono-0_4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono-0_4")))]
+impl PartialEq<crate::DateTime> for chrono::DateTime<Utc> {
+    fn eq(&self, other: &crate::DateTime) -> bool {
+        other == self
+    }
+}
+
+#[cfg(feature = "chrono-0_4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono-0_4")))]
+impl PartialOrd<crate::DateTime> for chrono::DateTime<Utc> {
+    fn partial_cmp(&self, other: &crate::DateTime) -> Option<std::cmp::Ordering> {
+        other.partial_cmp(self).map(std::cmp::Ordering::reverse)
+    }
+}
+
 #[cfg(all(feature = "chrono-0_4", feature = "serde_with"))]
 #[cfg_attr(docsrs, doc(cfg(all(feature = "chrono-0_4", feature = "serde_with"))))]
 impl<'de> DeserializeAs<'de, chrono::DateTime<Utc>> for crate::DateTime {
@@ -552,6 +719,42 @@ impl From<time::OffsetDateTime> for crate::DateTime {
     }
 }
 
+/// Compares at millisecond granularity: `other` is truncated to milliseconds as by
+/// [`DateTime::from_time_0_3`] before comparing, so values that differ only in sub-millisecond
+/// precision compare as equal.
+#[cfg(feature = "time-0_3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time-0_3")))]
+impl PartialEq<time::OffsetDateTime> for crate::DateTime {
+    fn eq(&self, other: &time::OffsetDateTime) -> bool {
+        *self == Self::from_time_0_3(*other)
+    }
+}
+
+/// Compares at millisecond granularity, as described on the [`PartialEq`] implementation.
+#[cfg(feature = "time-0_3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time-0_3")))]
+impl PartialOrd<time::OffsetDateTime> for crate::DateTime {
+    fn partial_cmp(&self, other: &time::OffsetDateTime) -> Option<std::cmp::Ordering> {
+        self.partial_cmp(&Self::from_time_0_3(*other))
+    }
+}
+
+#[cfg(feature = "time-0_3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time-0_3")))]
+impl PartialEq<crate::DateTime> for time::OffsetDateTime {
+    fn eq(&self, other: &crate::DateTime) -> bool {
+        other == self
+    }
+}
+
+#[cfg(feature = "time-0_3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time-0_3")))]
+impl PartialOrd<crate::DateTime> for time::OffsetDateTime {
+    fn partial_cmp(&self, other: &crate::DateTime) -> Option<std::cmp::Ordering> {
+        other.partial_cmp(self).map(std::cmp::Ordering::reverse)
+    }
+}
+
 #[cfg(all(feature = "time-0_3", feature = "serde_with"))]
 #[cfg_attr(docsrs, doc(cfg(all(feature = "time-0_3", feature = "serde_with"))))]
 impl<'de> DeserializeAs<'de, time::OffsetDateTime> for crate::DateTime {
@@ -606,6 +809,21 @@ impl serde_with_3::SerializeAs<time::OffsetDateTime> for crate::DateTime {
     }
 }
 
+/// The number of fractional-second digits to emit when formatting a [`DateTime`] with
+/// [`DateTime::to_rfc3339_string_with_precision`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TimePrecision {
+    /// Whole seconds, with no fractional part.
+    Seconds,
+    /// Three fractional digits.
+    Millis,
+    /// Six fractional digits.
+    Micros,
+    /// Nine fractional digits.
+    Nanos,
+}
+
 /// Errors that can occur during [`DateTime`] construction and generation.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -35,6 +35,11 @@ pub enum Error {
     DeserializationError {
         /// A message describing the error.
         message: String,
+
+        /// The offset in the input bytes at which the error was encountered, if known. This is
+        /// only populated when deserializing directly from BSON bytes (e.g. via
+        /// [`from_slice`](crate::from_slice)).
+        byte_offset: Option<usize>,
     },
 
     #[cfg(feature = "serde_path_to_error")]
@@ -53,7 +58,40 @@ impl Error {
     pub(crate) fn deserialization(msg: impl ToString) -> Self {
         Self::DeserializationError {
             message: msg.to_string(),
+            byte_offset: None,
+        }
+    }
+
+    /// The offset in the input bytes at which this error was encountered, if known.
+    ///
+    /// ```
+    /// use bson::{doc, Document};
+    ///
+    /// let mut bytes = bson::to_vec(&doc! { "a": 1, "b": "two" }).unwrap();
+    /// // Corrupt the length prefix of the string value for "b".
+    /// bytes[14] = 0xFF;
+    /// let err = bson::from_slice::<Document>(&bytes).unwrap_err();
+    /// // The offset points at the start of the malformed element.
+    /// assert_eq!(err.byte_offset(), Some(11));
+    /// ```
+    pub fn byte_offset(&self) -> Option<usize> {
+        match self {
+            Self::DeserializationError { byte_offset, .. } => *byte_offset,
+            #[cfg(feature = "serde_path_to_error")]
+            Self::WithPath { source, .. } => source.byte_offset(),
+            _ => None,
+        }
+    }
+
+    /// Records `offset` as the location of this error, unless a more precise offset has already
+    /// been recorded.
+    pub(crate) fn with_byte_offset(mut self, offset: Option<usize>) -> Self {
+        if let Self::DeserializationError { byte_offset, .. } = &mut self {
+            if byte_offset.is_none() {
+                *byte_offset = offset;
+            }
         }
+        self
     }
 
     #[cfg(feature = "serde_path_to_error")]
@@ -93,7 +131,11 @@ impl fmt::Display for Error {
                 key, element_type
             ),
             Error::EndOfStream => fmt.write_str("end of stream"),
-            Error::DeserializationError { message } => message.fmt(fmt),
+            Error::DeserializationError {
+                message,
+                byte_offset: Some(offset),
+            } => write!(fmt, "{} (at byte offset {})", message, offset),
+            Error::DeserializationError { message, .. } => message.fmt(fmt),
             #[cfg(feature = "serde_path_to_error")]
             Error::WithPath { path, source } => write!(fmt, "error at {}: {}", path, source),
         }
@@ -112,9 +154,7 @@ impl error::Error for Error {
 
 impl de::Error for Error {
     fn custom<T: Display>(msg: T) -> Error {
-        Error::DeserializationError {
-            message: msg.to_string(),
-        }
+        Error::deserialization(msg)
     }
 }
 
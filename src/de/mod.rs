@@ -21,19 +21,36 @@
 
 //! Deserializer
 
+/// Implements each of the given integer `deserialize_*` methods by calling the deserializer's
+/// `deserialize_integer` method.
+macro_rules! deserialize_integers {
+    ($($name:ident)*) => {
+        $(
+            #[inline]
+            fn $name<V>(self, visitor: V) -> ::std::result::Result<V::Value, Self::Error>
+            where
+                V: ::serde::de::Visitor<'de>,
+            {
+                self.deserialize_integer(visitor)
+            }
+        )*
+    };
+}
+
 mod error;
 mod raw;
 mod serde;
 
 pub use self::{
     error::{Error, Result},
-    serde::{Deserializer, DeserializerOptions},
+    serde::{Deserializer, DeserializerOptions, DuplicateKeyPolicy},
 };
 
-use std::io::Read;
+use std::{convert::TryFrom, io::Read};
 
 use crate::{
     bson::{Bson, Document, Timestamp},
+    raw::{RawBsonRef, RawDocument},
     ser::write_i32,
     spec::BinarySubtype,
 };
@@ -43,7 +60,12 @@ use ::serde::{
     Deserialize,
 };
 
-pub(crate) use self::serde::{convert_unsigned_to_signed_raw, BsonVisitor};
+pub(crate) use self::serde::{
+    convert_unsigned_to_signed_raw,
+    visit_integral_f64,
+    BsonVisitor,
+    ObjectIdVisitor,
+};
 
 pub(crate) use self::raw::Deserializer as RawDeserializer;
 
@@ -162,7 +184,25 @@ where
     Deserialize::deserialize(de)
 }
 
-pub(crate) fn reader_to_vec<R: Read>(mut reader: R) -> Result<Vec<u8>> {
+pub(crate) fn reader_to_vec<R: Read>(reader: R) -> Result<Vec<u8>> {
+    reader_to_vec_capped(reader, None)
+}
+
+/// Read a single length-prefixed document from `reader`, rejecting it before any buffer is
+/// allocated if its declared length exceeds `max_size`.
+pub(crate) fn reader_to_vec_capped<R: Read>(reader: R, max_size: Option<usize>) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    read_document_into(reader, max_size, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Read a single length-prefixed document from `reader` into `bytes`, replacing its previous
+/// contents but reusing its allocation.
+pub(crate) fn read_document_into<R: Read>(
+    mut reader: R,
+    max_size: Option<usize>,
+    bytes: &mut Vec<u8>,
+) -> Result<()> {
     let mut buf = [0; 4];
     reader.read_exact(&mut buf)?;
     let length = i32::from_le_bytes(buf);
@@ -171,11 +211,62 @@ pub(crate) fn reader_to_vec<R: Read>(mut reader: R) -> Result<Vec<u8>> {
         return Err(Error::custom("document size too small"));
     }
 
-    let mut bytes = Vec::with_capacity(length as usize);
-    write_i32(&mut bytes, length).map_err(Error::custom)?;
+    if let Some(max_size) = max_size {
+        if length as usize > max_size {
+            return Err(Error::custom(format!(
+                "document size {} exceeds maximum of {}",
+                length, max_size
+            )));
+        }
+    }
+
+    bytes.clear();
+    bytes.reserve(length as usize);
+    write_i32(bytes, length).map_err(Error::custom)?;
 
-    reader.take(length as u64 - 4).read_to_end(&mut bytes)?;
-    Ok(bytes)
+    reader.take(length as u64 - 4).read_to_end(bytes)?;
+    Ok(())
+}
+
+/// Convert `raw` into a [`Document`], resolving any repeated keys in it or in its nested documents
+/// according to `policy`.
+pub(crate) fn document_from_raw(raw: &RawDocument, policy: DuplicateKeyPolicy) -> Result<Document> {
+    let mut doc = Document::new();
+    for elem in raw {
+        let (key, value) = elem?;
+        if doc.contains_key(key) {
+            match policy {
+                DuplicateKeyPolicy::KeepFirst => continue,
+                DuplicateKeyPolicy::Error => {
+                    return Err(Error::custom(format!(
+                        "duplicate key \"{}\" in document",
+                        key
+                    )))
+                }
+                _ => {}
+            }
+        }
+        doc.insert(key, bson_from_raw(value, policy)?);
+    }
+    Ok(doc)
+}
+
+fn bson_from_raw(value: RawBsonRef, policy: DuplicateKeyPolicy) -> Result<Bson> {
+    Ok(match value {
+        RawBsonRef::Document(d) => Bson::Document(document_from_raw(d, policy)?),
+        RawBsonRef::Array(a) => Bson::Array(
+            a.into_iter()
+                .map(|v| bson_from_raw(v?, policy))
+                .collect::<Result<_>>()?,
+        ),
+        RawBsonRef::JavaScriptCodeWithScope(c) => {
+            Bson::JavaScriptCodeWithScope(crate::JavaScriptCodeWithScope {
+                code: c.code.to_string(),
+                scope: document_from_raw(c.scope, policy)?,
+            })
+        }
+        other => Bson::try_from(other)?,
+    })
 }
 
 /// Deserialize an instance of type `T` from an I/O stream of BSON.
@@ -203,6 +294,86 @@ where
     from_slice_utf8_lossy(bytes.as_slice())
 }
 
+/// An iterator that reads a sequence of length-prefixed BSON [`Document`]s from a reader, such as
+/// the output of [`write_documents`](crate::write_documents).
+///
+/// The iterator returns [`None`] once the reader is exhausted at a document boundary. Reaching the
+/// end of input partway through a document is reported as an error, after which the iterator
+/// yields no further items.
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> std::result::Result<(), Box<dyn Error>> {
+/// use bson::{de::DocumentReader, doc};
+///
+/// let docs = vec![doc! { "x": 1 }, doc! { "y": 2 }];
+/// let mut buf: Vec<u8> = Vec::new();
+/// bson::write_documents(&mut buf, docs.clone())?;
+///
+/// let read = DocumentReader::new(buf.as_slice()).collect::<bson::de::Result<Vec<_>>>()?;
+/// assert_eq!(read, docs);
+/// # Ok(())
+/// # }
+/// ```
+pub struct DocumentReader<R> {
+    reader: R,
+    done: bool,
+}
+
+impl<R: Read> DocumentReader<R> {
+    /// Construct a new [`DocumentReader`] that reads documents from the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            done: false,
+        }
+    }
+
+    /// Consume this [`DocumentReader`], returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read the 4-byte length prefix of the next document, returning [`None`] if the reader was
+    /// already exhausted.
+    fn read_length(&mut self) -> Result<Option<[u8; 4]>> {
+        let mut buf = [0; 4];
+        let mut read = 0;
+        while read < buf.len() {
+            match self.reader.read(&mut buf[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => read += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(Some(buf))
+    }
+}
+
+impl<R: Read> Iterator for DocumentReader<R> {
+    type Item = Result<Document>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = match self.read_length() {
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Ok(Some(length)) => Document::from_reader(length.as_slice().chain(&mut self.reader)),
+            Err(e) => Err(e),
+        };
+        if result.is_err() {
+            self.done = true;
+        }
+        Some(result)
+    }
+}
+
 /// Deserialize an instance of type `T` from a slice of BSON bytes.
 pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T>
 where
@@ -33,7 +33,7 @@ use crate::{
     Timestamp,
 };
 
-use super::{DeserializerHint, Error, Result};
+use super::{visit_integral_f64, DeserializerHint, Error, Result};
 use crate::de::serde::MapDeserializer;
 
 /// Deserializer mapping from raw bson to serde's data model.
@@ -46,19 +46,57 @@ pub(crate) struct Deserializer<'de> {
 struct DeserializerOptions {
     utf8_lossy: bool,
     human_readable: bool,
+    /// The address of the start of the input buffer. Every document visited during
+    /// deserialization borrows from that buffer, so this is used to translate positions within a
+    /// document into offsets within the input for error reporting.
+    buffer_start: usize,
+}
+
+impl DeserializerOptions {
+    fn byte_offset(&self, (doc, offset): (&RawDocument, usize)) -> Option<usize> {
+        let doc_start = (doc.as_bytes().as_ptr() as usize).checked_sub(self.buffer_start)?;
+        doc_start.checked_add(offset)
+    }
 }
 
 impl<'de> Deserializer<'de> {
     pub(crate) fn new(buf: &'de [u8], utf8_lossy: bool) -> Result<Self> {
         Ok(Self {
-            element: RawElement::toplevel(buf)?,
+            element: RawElement::toplevel(buf)
+                .map_err(|e| Error::from(e).with_byte_offset(Some(0)))?,
             options: DeserializerOptions {
                 utf8_lossy,
                 human_readable: false,
+                buffer_start: buf.as_ptr() as usize,
             },
         })
     }
 
+    /// Constructs a deserializer for a single element within a document, such as an array
+    /// entry. Byte offsets in errors are reported relative to the start of that document.
+    pub(crate) fn from_element(element: RawElement<'de>) -> Self {
+        let buffer_start = element.value_position().0.as_bytes().as_ptr() as usize;
+        Self {
+            element,
+            options: DeserializerOptions {
+                utf8_lossy: false,
+                human_readable: false,
+                buffer_start,
+            },
+        }
+    }
+
+    /// Deserializes an integer, additionally accepting doubles with no fractional part.
+    fn deserialize_integer<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.value()? {
+            RawBsonRef::Double(d) => visit_integral_f64(d, visitor),
+            _ => self.deserialize_hint(visitor, DeserializerHint::None),
+        }
+    }
+
     fn value(&self) -> Result<RawBsonRef<'de>> {
         Ok(self.element.value()?)
     }
@@ -72,7 +110,8 @@ impl<'de> Deserializer<'de> {
         if self.options.utf8_lossy {
             if let Some(lossy) = self.element.value_utf8_lossy()? {
                 return match lossy {
-                    Utf8LossyBson::String(s) => visitor.visit_string(s),
+                    Utf8LossyBson::String(Cow::Borrowed(s)) => visitor.visit_borrowed_str(s),
+                    Utf8LossyBson::String(Cow::Owned(s)) => visitor.visit_string(s),
                     Utf8LossyBson::RegularExpression(re) => {
                         visitor.visit_map(RegexAccess::new(BsonCow::Owned(re)))
                     }
@@ -188,7 +227,7 @@ impl<'de> Deserializer<'de> {
                     ))
                 }
             };
-            Ok(Cow::Owned(s))
+            Ok(s)
         } else {
             match self.value()? {
                 RawBsonRef::String(s) => Ok(Cow::Borrowed(s)),
@@ -310,10 +349,15 @@ impl<'de> serde::de::Deserializer<'de> for Deserializer<'de> {
         self.options.human_readable
     }
 
+    deserialize_integers! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+    }
+
     forward_to_deserialize_any! {
         bool char str byte_buf unit unit_struct string
         identifier seq tuple tuple_struct struct
-        map ignored_any i8 i16 i32 i64 u8 u16 u32 u64 f32 f64
+        map ignored_any f32 f64
     }
 }
 
@@ -333,10 +377,28 @@ impl<'de> DocumentAccess<'de> {
     }
 
     fn advance(&mut self) -> Result<()> {
-        self.elem = self.iter.next().transpose()?;
+        let position = self.iter.position();
+        self.elem = self
+            .iter
+            .next()
+            .transpose()
+            .map_err(|e| Error::from(e).with_byte_offset(self.options.byte_offset(position)))?;
         Ok(())
     }
 
+    /// Deserializes the current element with `seed`, recording the element's location on any
+    /// error that does not already have a more precise one.
+    fn deserialize_elem<T>(&self, elem: &RawElement<'de>, seed: T) -> Result<T::Value>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(Deserializer {
+            element: elem.clone(),
+            options: self.options.clone(),
+        })
+        .map_err(|e| e.with_byte_offset(self.options.byte_offset(elem.value_position())))
+    }
+
     fn deserializer(self) -> Result<Deserializer<'de>> {
         let elem = match self.elem {
             Some(e) => e,
@@ -375,10 +437,7 @@ impl<'de> serde::de::MapAccess<'de> for DocumentAccess<'de> {
     {
         match &self.elem {
             None => Err(Error::deserialization("too many values requested")),
-            Some(elem) => seed.deserialize(Deserializer {
-                element: elem.clone(),
-                options: self.options.clone(),
-            }),
+            Some(elem) => self.deserialize_elem(elem, seed),
         }
     }
 }
@@ -396,12 +455,7 @@ impl<'de> serde::de::SeqAccess<'de> for DocumentAccess<'de> {
         self.advance()?;
         match &self.elem {
             None => Ok(None),
-            Some(elem) => seed
-                .deserialize(Deserializer {
-                    element: elem.clone(),
-                    options: self.options.clone(),
-                })
-                .map(Some),
+            Some(elem) => self.deserialize_elem(elem, seed).map(Some),
         }
     }
 }
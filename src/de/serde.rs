@@ -37,7 +37,7 @@ use super::{raw::Decimal128Access, DeserializerHint};
 
 pub(crate) struct BsonVisitor;
 
-struct ObjectIdVisitor;
+pub(crate) struct ObjectIdVisitor;
 
 impl<'de> Visitor<'de> for ObjectIdVisitor {
     type Value = ObjectId;
@@ -574,6 +574,26 @@ where
     }
 }
 
+/// Visits a double with no fractional part as an integer, so that e.g. `3.0` can be deserialized
+/// into an integer field. Range checks for the target type are left to the visitor; fractional
+/// and out-of-range values are visited as doubles, which integer visitors reject.
+pub(crate) fn visit_integral_f64<'de, V, E>(value: f64, visitor: V) -> Result<V::Value, E>
+where
+    V: Visitor<'de>,
+    E: Error,
+{
+    if value.fract() == 0.0 {
+        // `i64::MAX as f64` and `u64::MAX as f64` round up to 2^63 and 2^64 respectively.
+        if value >= i64::MIN as f64 && value < i64::MAX as f64 {
+            return visitor.visit_i64(value as i64);
+        }
+        if value >= 0.0 && value < u64::MAX as f64 {
+            return visitor.visit_u64(value as u64);
+        }
+    }
+    visitor.visit_f64(value)
+}
+
 /// Serde Deserializer
 pub struct Deserializer {
     value: Option<Bson>,
@@ -589,6 +609,28 @@ pub struct DeserializerOptions {
     /// The default is true.
     #[deprecated = "use bson::serde_helpers::HumanReadable"]
     pub human_readable: Option<bool>,
+
+    /// How a document containing the same key more than once should be decoded. This applies to
+    /// [`Document::from_reader_with_options`](crate::Document::from_reader_with_options).
+    /// The default value is [`DuplicateKeyPolicy::KeepLast`].
+    pub on_duplicate_key: Option<DuplicateKeyPolicy>,
+}
+
+/// The ways in which a repeated key in a BSON document can be handled when decoding it into a
+/// [`Document`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum DuplicateKeyPolicy {
+    /// Keep the value of the last occurrence of the key, at the position of the first occurrence.
+    /// This is the behavior of [`Document::from_reader`] and [`crate::from_slice`].
+    #[default]
+    KeepLast,
+
+    /// Keep the value of the first occurrence of the key and ignore any later ones.
+    KeepFirst,
+
+    /// Return an error if any key occurs more than once.
+    Error,
 }
 
 impl DeserializerOptions {
@@ -614,6 +656,12 @@ impl DeserializerOptionsBuilder {
         self
     }
 
+    /// Set the value for [`DeserializerOptions::on_duplicate_key`].
+    pub fn on_duplicate_key(mut self, val: impl Into<Option<DuplicateKeyPolicy>>) -> Self {
+        self.options.on_duplicate_key = val.into();
+        self
+    }
+
     /// Consume this builder and produce a [`DeserializerOptions`].
     pub fn build(self) -> DeserializerOptions {
         self.options
@@ -634,6 +682,17 @@ impl Deserializer {
         }
     }
 
+    /// Deserializes an integer, additionally accepting doubles with no fractional part.
+    fn deserialize_integer<'de, V>(self, visitor: V) -> Result<V::Value, crate::de::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.value {
+            Some(Bson::Double(d)) => visit_integral_f64(d, visitor),
+            _ => self.deserialize_next(visitor, DeserializerHint::None),
+        }
+    }
+
     fn deserialize_next<'de, V>(
         mut self,
         visitor: V,
@@ -870,16 +929,13 @@ impl<'de> de::Deserializer<'de> for Deserializer {
         }
     }
 
+    deserialize_integers! {
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+    }
+
     forward_to_deserialize! {
         deserialize_bool();
-        deserialize_u8();
-        deserialize_u16();
-        deserialize_u32();
-        deserialize_u64();
-        deserialize_i8();
-        deserialize_i16();
-        deserialize_i32();
-        deserialize_i64();
         deserialize_f32();
         deserialize_f64();
         deserialize_char();
@@ -1,6 +1,9 @@
 //! [BSON Decimal128](https://github.com/mongodb/specifications/blob/master/source/bson-decimal128/decimal128.rst) data type representation
 
-use std::{convert::TryInto, fmt};
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+};
 
 use bitvec::prelude::*;
 
@@ -28,6 +31,31 @@ pub struct Decimal128 {
 }
 
 impl Decimal128 {
+    /// The value `0`, with an exponent of zero.
+    pub const ZERO: Self = Self {
+        bytes: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x40, 0x30],
+    };
+
+    /// The value `1`, with an exponent of zero.
+    pub const ONE: Self = Self {
+        bytes: [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x40, 0x30],
+    };
+
+    /// A quiet, positive NaN.
+    pub const NAN: Self = Self {
+        bytes: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x7C],
+    };
+
+    /// Positive infinity.
+    pub const POSITIVE_INFINITY: Self = Self {
+        bytes: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x78],
+    };
+
+    /// Negative infinity.
+    pub const NEGATIVE_INFINITY: Self = Self {
+        bytes: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xF8],
+    };
+
     /// Constructs a new `Decimal128` from the provided raw byte representation.
     pub fn from_bytes(bytes: [u8; 128 / 8]) -> Self {
         Self { bytes }
@@ -38,6 +66,162 @@ impl Decimal128 {
         self.bytes
     }
 
+    /// The most significant byte, which holds the sign bit and the start of the combination field.
+    fn high_byte(&self) -> u8 {
+        // BSON stores decimal128 values in little-endian order.
+        self.bytes[15]
+    }
+
+    /// Returns `true` if this `Decimal128` is NaN, either quiet or signalling.
+    ///
+    /// ```rust
+    /// # use bson::Decimal128;
+    /// assert!(Decimal128::NAN.is_nan());
+    /// assert!(!Decimal128::POSITIVE_INFINITY.is_nan());
+    /// ```
+    pub fn is_nan(&self) -> bool {
+        self.high_byte() & 0x7C == 0x7C
+    }
+
+    /// Returns `true` if this `Decimal128` is positive or negative infinity.
+    ///
+    /// ```rust
+    /// # use bson::Decimal128;
+    /// assert!(Decimal128::NEGATIVE_INFINITY.is_infinite());
+    /// assert!(!Decimal128::NAN.is_infinite());
+    /// ```
+    pub fn is_infinite(&self) -> bool {
+        self.high_byte() & 0x7C == 0x78
+    }
+
+    /// Returns `true` if this `Decimal128` is neither infinite nor NaN.
+    ///
+    /// ```rust
+    /// # use bson::Decimal128;
+    /// assert!(Decimal128::ONE.is_finite());
+    /// assert!(!Decimal128::POSITIVE_INFINITY.is_finite());
+    /// ```
+    pub fn is_finite(&self) -> bool {
+        self.high_byte() & 0x78 != 0x78
+    }
+
+    /// Returns `true` if this `Decimal128` is zero, with any sign and exponent. As required by the
+    /// decimal128 specification, values whose coefficient exceeds the maximum of 34 digits are
+    /// treated as zero.
+    ///
+    /// ```rust
+    /// # use bson::Decimal128;
+    /// assert!(Decimal128::ZERO.is_zero());
+    /// assert!("-0.000".parse::<Decimal128>().unwrap().is_zero());
+    /// assert!(!Decimal128::ONE.is_zero());
+    /// ```
+    pub fn is_zero(&self) -> bool {
+        match ParsedDecimal128::new(self).kind {
+            Decimal128Kind::Finite { coefficient, .. } => coefficient.value() == 0,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the sign bit of this `Decimal128` is set. This includes negative zero and
+    /// negative infinity, as well as NaNs with the sign bit set.
+    ///
+    /// ```rust
+    /// # use bson::Decimal128;
+    /// assert!("-1.5".parse::<Decimal128>().unwrap().is_negative());
+    /// assert!("-0".parse::<Decimal128>().unwrap().is_negative());
+    /// assert!(Decimal128::NEGATIVE_INFINITY.is_negative());
+    /// assert!(!Decimal128::ONE.is_negative());
+    /// ```
+    pub fn is_negative(&self) -> bool {
+        self.high_byte() & 0x80 != 0
+    }
+
+    /// Constructs a `Decimal128` holding the shortest decimal representation that round-trips to
+    /// `value`. Infinities map to [`Decimal128::POSITIVE_INFINITY`] and
+    /// [`Decimal128::NEGATIVE_INFINITY`], and any NaN maps to [`Decimal128::NAN`].
+    ///
+    /// ```rust
+    /// # use bson::Decimal128;
+    /// assert_eq!(Decimal128::from_f64(0.1).to_string(), "0.1");
+    /// assert_eq!(Decimal128::from_f64(f64::INFINITY), Decimal128::POSITIVE_INFINITY);
+    /// ```
+    pub fn from_f64(value: f64) -> Self {
+        if value.is_nan() {
+            return Self::NAN;
+        }
+        // Every finite double has at most 17 significant digits and a decimal exponent well within
+        // the range of Decimal128, so this conversion is always exact.
+        format!("{:e}", value)
+            .parse()
+            .expect("formatted f64 should always be a valid Decimal128")
+    }
+
+    /// Returns the `f64` nearest to the value of this `Decimal128`. This conversion is lossy:
+    /// `Decimal128` has more precision and a far wider exponent range than `f64`, so values may be
+    /// rounded, and values outside the range of `f64` become infinite or zero.
+    ///
+    /// ```rust
+    /// # use bson::Decimal128;
+    /// let value: Decimal128 = "1.05E+3".parse().unwrap();
+    /// assert_eq!(value.to_f64(), 1050.0);
+    /// assert!(Decimal128::NAN.to_f64().is_nan());
+    /// ```
+    pub fn to_f64(&self) -> f64 {
+        // The standard library parser is correctly rounded and accepts the `NaN` and `Infinity`
+        // spellings produced by the `Display` impl.
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Constructs a `Decimal128` with an exponent of zero holding exactly `value`, or returns
+    /// `None` if `value` has more than 34 significant digits.
+    pub(crate) fn from_i128_exact(value: i128) -> Option<Self> {
+        let magnitude = value.unsigned_abs();
+        if magnitude > Coefficient::MAX_VALUE {
+            return None;
+        }
+        let parsed = ParsedDecimal128 {
+            sign: value < 0,
+            kind: Decimal128Kind::Finite {
+                exponent: Exponent::from_native(0),
+                coefficient: Coefficient::from_native(magnitude),
+            },
+        };
+        Some(parsed.pack())
+    }
+
+    /// Returns the value of this `Decimal128` as an `i128` if it is an integer in the range of
+    /// `i128`, regardless of its exponent.
+    pub(crate) fn to_i128_exact(self) -> Option<i128> {
+        let parsed = ParsedDecimal128::new(&self);
+        let (exponent, coefficient) = match parsed.kind {
+            Decimal128Kind::Finite {
+                exponent,
+                coefficient,
+            } => (exponent.value(), coefficient.value()),
+            _ => return None,
+        };
+        let magnitude = if coefficient == 0 {
+            0
+        } else if exponent >= 0 {
+            10u128
+                .checked_pow(exponent as u32)
+                .and_then(|scale| coefficient.checked_mul(scale))?
+        } else {
+            // A nonzero coefficient has at most 34 digits, so any larger negative exponent leaves
+            // a fractional part.
+            let scale = 10u128.checked_pow(u32::from(exponent.unsigned_abs()))?;
+            if coefficient % scale != 0 {
+                return None;
+            }
+            coefficient / scale
+        };
+        if parsed.sign {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
     pub(crate) fn deserialize_from_slice<E: serde::de::Error>(
         bytes: &[u8],
     ) -> std::result::Result<Self, E> {
@@ -14,9 +14,9 @@ use ahash::RandomState;
 use indexmap::IndexMap;
 
 use crate::{
-    bson::{Array, Bson, Timestamp},
+    bson::{Array, Bson, PathSegment, Timestamp},
     oid::ObjectId,
-    spec::BinarySubtype,
+    spec::{BinarySubtype, ElementType},
     Binary,
     Decimal128,
 };
@@ -30,6 +30,8 @@ pub enum ValueAccessError {
     NotPresent,
     /// Found a Bson value with the specified key, but not with the expected type
     UnexpectedType,
+    /// Found the specified key, but its value is [`Bson::Null`] rather than the expected type
+    UnexpectedNull,
 }
 
 /// Result of accessing Bson value
@@ -42,6 +44,7 @@ impl Debug for ValueAccessError {
             ValueAccessError::UnexpectedType => {
                 write!(f, "ValueAccessError: field does not have the expected type")
             }
+            ValueAccessError::UnexpectedNull => write!(f, "ValueAccessError: field is null"),
         }
     }
 }
@@ -51,12 +54,55 @@ impl Display for ValueAccessError {
         match *self {
             ValueAccessError::NotPresent => write!(f, "field is not present"),
             ValueAccessError::UnexpectedType => write!(f, "field does not have the expected type"),
+            ValueAccessError::UnexpectedNull => write!(f, "field is null"),
         }
     }
 }
 
 impl error::Error for ValueAccessError {}
 
+/// Error returned by [`Document::rename_key`] when the new key is already present in the
+/// document.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct KeyCollisionError {
+    /// The key that already exists.
+    pub key: String,
+}
+
+impl Display for KeyCollisionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "key \"{}\" already exists in the document", self.key)
+    }
+}
+
+impl error::Error for KeyCollisionError {}
+
+/// Error returned by the [`Bson`] methods that modify a value in place, such as
+/// [`Bson::array_push`], when the value is not of the type the method requires. The value is left
+/// unchanged.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct UnexpectedTypeError {
+    /// The type the method requires.
+    pub expected: ElementType,
+
+    /// The actual type of the value.
+    pub actual: ElementType,
+}
+
+impl Display for UnexpectedTypeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected a value of type {}, found {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl error::Error for UnexpectedTypeError {}
+
 /// A BSON document represented as an associative HashMap with insertion ordering.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "hashable", derive(Eq))]
@@ -70,6 +116,7 @@ impl Default for Document {
     }
 }
 
+/// Like [`PartialEq`] for [`Document`], hashing ignores the order of keys.
 #[cfg(feature = "hashable")]
 impl Hash for Document {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -171,6 +218,11 @@ pub struct Values<'a> {
     inner: indexmap::map::Values<'a, String, Bson>,
 }
 
+/// An iterator over a [`Document`]'s mutable values.
+pub struct ValuesMut<'a> {
+    inner: indexmap::map::ValuesMut<'a, String, Bson>,
+}
+
 /// An iterator over a [`Document`]'s keys and mutable values.
 pub struct IterMut<'a> {
     inner: indexmap::map::IterMut<'a, String, Bson>,
@@ -192,6 +244,14 @@ impl<'a> Iterator for Values<'a> {
     }
 }
 
+impl<'a> Iterator for ValuesMut<'a> {
+    type Item = &'a mut Bson;
+
+    fn next(&mut self) -> Option<&'a mut Bson> {
+        self.inner.next()
+    }
+}
+
 impl IntoIterator for Document {
     type Item = (String, Bson);
     type IntoIter = IntoIter;
@@ -256,6 +316,35 @@ impl Document {
         }
     }
 
+    /// Returns a [`DocumentBuilder`] for constructing a document one field at a time, optionally
+    /// skipping fields whose values are absent.
+    pub fn builder() -> DocumentBuilder {
+        DocumentBuilder::new()
+    }
+
+    /// Creates a new empty Document with space for at least `capacity` elements without
+    /// reallocating.
+    pub fn with_capacity(capacity: usize) -> Document {
+        Document {
+            inner: IndexMap::with_capacity_and_hasher(capacity, RandomState::default()),
+        }
+    }
+
+    /// Returns the number of elements the document can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more elements to be inserted.
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    /// Shrinks the capacity of the document as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit()
+    }
+
     /// Gets an iterator over the entries of the map.
     pub fn iter(&self) -> Iter {
         self.into_iter()
@@ -288,6 +377,7 @@ impl Document {
     pub fn get_f64(&self, key: impl AsRef<str>) -> ValueAccessResult<f64> {
         match self.get(key) {
             Some(&Bson::Double(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -298,6 +388,7 @@ impl Document {
     pub fn get_f64_mut(&mut self, key: impl AsRef<str>) -> ValueAccessResult<&mut f64> {
         match self.get_mut(key) {
             Some(&mut Bson::Double(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -307,6 +398,7 @@ impl Document {
     pub fn get_decimal128(&self, key: impl AsRef<str>) -> ValueAccessResult<&Decimal128> {
         match self.get(key) {
             Some(Bson::Decimal128(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -319,6 +411,7 @@ impl Document {
     ) -> ValueAccessResult<&mut Decimal128> {
         match self.get_mut(key) {
             Some(&mut Bson::Decimal128(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -328,6 +421,7 @@ impl Document {
     pub fn get_str(&self, key: impl AsRef<str>) -> ValueAccessResult<&str> {
         match self.get(key) {
             Some(Bson::String(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -337,6 +431,7 @@ impl Document {
     pub fn get_str_mut(&mut self, key: impl AsRef<str>) -> ValueAccessResult<&mut str> {
         match self.get_mut(key) {
             Some(&mut Bson::String(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -347,6 +442,7 @@ impl Document {
     pub fn get_array(&self, key: impl AsRef<str>) -> ValueAccessResult<&Array> {
         match self.get(key) {
             Some(Bson::Array(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -357,6 +453,7 @@ impl Document {
     pub fn get_array_mut(&mut self, key: impl AsRef<str>) -> ValueAccessResult<&mut Array> {
         match self.get_mut(key) {
             Some(&mut Bson::Array(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -367,6 +464,7 @@ impl Document {
     pub fn get_document(&self, key: impl AsRef<str>) -> ValueAccessResult<&Document> {
         match self.get(key) {
             Some(Bson::Document(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -377,6 +475,7 @@ impl Document {
     pub fn get_document_mut(&mut self, key: impl AsRef<str>) -> ValueAccessResult<&mut Document> {
         match self.get_mut(key) {
             Some(&mut Bson::Document(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -386,6 +485,7 @@ impl Document {
     pub fn get_bool(&self, key: impl AsRef<str>) -> ValueAccessResult<bool> {
         match self.get(key) {
             Some(&Bson::Boolean(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -395,6 +495,35 @@ impl Document {
     pub fn get_bool_mut(&mut self, key: impl AsRef<str>) -> ValueAccessResult<&mut bool> {
         match self.get_mut(key) {
             Some(&mut Bson::Boolean(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
+            Some(_) => Err(ValueAccessError::UnexpectedType),
+            None => Err(ValueAccessError::NotPresent),
+        }
+    }
+
+    /// Get a bool value for this key if it exists and can be interpreted as a bool. In addition to
+    /// [`Bson::Boolean`], this accepts [`Bson::Int32`] and [`Bson::Int64`] values of `0` or `1`,
+    /// and the strings `"true"` and `"false"` compared case-insensitively. Any other value
+    /// returns [`ValueAccessError::UnexpectedType`]; see [`Document::get_bool`] for a strict
+    /// version.
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let doc = doc! { "a": true, "b": 0, "c": "TRUE", "d": 2 };
+    /// assert_eq!(doc.get_bool_lenient("a"), Ok(true));
+    /// assert_eq!(doc.get_bool_lenient("b"), Ok(false));
+    /// assert_eq!(doc.get_bool_lenient("c"), Ok(true));
+    /// assert!(doc.get_bool_lenient("d").is_err());
+    /// ```
+    pub fn get_bool_lenient(&self, key: impl AsRef<str>) -> ValueAccessResult<bool> {
+        match self.get(key) {
+            Some(&Bson::Boolean(v)) => Ok(v),
+            Some(Bson::Int32(0)) | Some(Bson::Int64(0)) => Ok(false),
+            Some(Bson::Int32(1)) | Some(Bson::Int64(1)) => Ok(true),
+            Some(Bson::String(s)) if s.eq_ignore_ascii_case("true") => Ok(true),
+            Some(Bson::String(s)) if s.eq_ignore_ascii_case("false") => Ok(false),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -409,6 +538,7 @@ impl Document {
     pub fn get_i32(&self, key: impl AsRef<str>) -> ValueAccessResult<i32> {
         match self.get(key) {
             Some(&Bson::Int32(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -418,6 +548,7 @@ impl Document {
     pub fn get_i32_mut(&mut self, key: impl AsRef<str>) -> ValueAccessResult<&mut i32> {
         match self.get_mut(key) {
             Some(&mut Bson::Int32(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -427,6 +558,7 @@ impl Document {
     pub fn get_i64(&self, key: impl AsRef<str>) -> ValueAccessResult<i64> {
         match self.get(key) {
             Some(&Bson::Int64(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -436,6 +568,7 @@ impl Document {
     pub fn get_i64_mut(&mut self, key: impl AsRef<str>) -> ValueAccessResult<&mut i64> {
         match self.get_mut(key) {
             Some(&mut Bson::Int64(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -445,6 +578,7 @@ impl Document {
     pub fn get_timestamp(&self, key: impl AsRef<str>) -> ValueAccessResult<Timestamp> {
         match self.get(key) {
             Some(&Bson::Timestamp(timestamp)) => Ok(timestamp),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -455,19 +589,25 @@ impl Document {
     pub fn get_timestamp_mut(&mut self, key: impl AsRef<str>) -> ValueAccessResult<&mut Timestamp> {
         match self.get_mut(key) {
             Some(&mut Bson::Timestamp(ref mut timestamp)) => Ok(timestamp),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
     }
 
     /// Get a reference to a generic binary value for this key if it exists and has the correct
-    /// type.
+    /// type. Binary values with any subtype other than [`BinarySubtype::Generic`] will return
+    /// [`ValueAccessError::UnexpectedType`].
+    ///
+    /// The returned `&Vec<u8>` dereferences to `&[u8]`, so it can be passed directly to anything
+    /// expecting a byte slice.
     pub fn get_binary_generic(&self, key: impl AsRef<str>) -> ValueAccessResult<&Vec<u8>> {
         match self.get(key) {
             Some(&Bson::Binary(Binary {
                 subtype: BinarySubtype::Generic,
                 ref bytes,
             })) => Ok(bytes),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -484,6 +624,7 @@ impl Document {
                 subtype: BinarySubtype::Generic,
                 ref mut bytes,
             })) => Ok(bytes),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -493,6 +634,7 @@ impl Document {
     pub fn get_object_id(&self, key: impl AsRef<str>) -> ValueAccessResult<ObjectId> {
         match self.get(key) {
             Some(&Bson::ObjectId(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -503,6 +645,7 @@ impl Document {
     pub fn get_object_id_mut(&mut self, key: impl AsRef<str>) -> ValueAccessResult<&mut ObjectId> {
         match self.get_mut(key) {
             Some(&mut Bson::ObjectId(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -512,6 +655,7 @@ impl Document {
     pub fn get_datetime(&self, key: impl AsRef<str>) -> ValueAccessResult<&crate::DateTime> {
         match self.get(key) {
             Some(Bson::DateTime(v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -525,6 +669,7 @@ impl Document {
     ) -> ValueAccessResult<&mut crate::DateTime> {
         match self.get_mut(key) {
             Some(&mut Bson::DateTime(ref mut v)) => Ok(v),
+            Some(Bson::Null) => Err(ValueAccessError::UnexpectedNull),
             Some(_) => Err(ValueAccessError::UnexpectedType),
             None => Err(ValueAccessError::NotPresent),
         }
@@ -549,12 +694,59 @@ impl Document {
         }
     }
 
+    /// Gets a mutable iterator over all values in the document, in insertion order.
+    pub fn values_mut(&mut self) -> ValuesMut<'_> {
+        ValuesMut {
+            inner: self.inner.values_mut(),
+        }
+    }
+
+    /// Returns true if the document contains a value of a deprecated BSON type (see
+    /// [`Bson::is_deprecated_type`]), including within any nested documents, arrays, or
+    /// code-with-scope scopes.
+    ///
+    /// ```
+    /// use bson::{doc, Bson};
+    ///
+    /// let doc = doc! { "a": 1, "b": { "c": [Bson::Undefined] } };
+    /// assert!(doc.contains_deprecated_types());
+    /// assert!(!doc! { "a": 1 }.contains_deprecated_types());
+    /// ```
+    pub fn contains_deprecated_types(&self) -> bool {
+        self.values().any(Bson::contains_deprecated_types)
+    }
+
+    /// Returns the total number of values in the document, including every value nested within
+    /// it. The document itself is not counted. See [`Bson::node_count`].
+    pub fn node_count(&self) -> usize {
+        self.values().map(Bson::node_count).sum()
+    }
+
+    /// Returns the maximum nesting depth of the document, where the document itself has a depth
+    /// of 1. See [`Bson::max_depth`].
+    pub fn max_depth(&self) -> usize {
+        1 + self.values().map(Bson::max_depth).max().unwrap_or(0)
+    }
+
+    /// Visits every value in the document, including those nested within embedded documents and
+    /// arrays, in depth-first order. See [`Bson::walk`] for details; unlike that method, the
+    /// document itself is not passed to `visitor`.
+    pub fn walk<'a, F>(&'a self, mut visitor: F)
+    where
+        F: FnMut(&[PathSegment<'a>], &'a Bson),
+    {
+        crate::bson::walk_document(self, &mut Vec::new(), &mut visitor);
+    }
+
     /// Returns the number of elements in the document.
     pub fn len(&self) -> usize {
         self.inner.len()
     }
 
-    /// Returns true if the document contains no elements
+    /// Returns true if the document contains no elements.
+    ///
+    /// This can be used with `#[serde(skip_serializing_if = "Document::is_empty")]` to omit empty
+    /// documents; see [`Bson::is_empty_container`] for fields of type [`Bson`].
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
     }
@@ -566,12 +758,279 @@ impl Document {
         self.inner.insert(key.into(), val.into())
     }
 
+    /// Inserts the given key and value into the document and returns it, allowing documents to be
+    /// built by chaining calls. As with [`Document::insert`], an existing value for the key is
+    /// replaced in place.
+    ///
+    /// ```
+    /// use bson::{doc, Document};
+    ///
+    /// let doc = Document::new().append("a", 1).append("b", "two");
+    /// assert_eq!(doc, doc! { "a": 1, "b": "two" });
+    /// ```
+    pub fn append(mut self, key: impl Into<String>, value: impl Into<Bson>) -> Self {
+        self.insert(key, value);
+        self
+    }
+
     /// Takes the value of the entry out of the document, and returns it.
     /// Computes in **O(n)** time (average).
     pub fn remove(&mut self, key: impl AsRef<str>) -> Option<Bson> {
         self.inner.shift_remove(key.as_ref())
     }
 
+    /// Consumes the document, splitting its entries into two documents: the first contains the
+    /// entries for which `f` returns `true`, and the second contains the rest. The relative order
+    /// of entries is preserved within each document.
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let doc = doc! { "name": "x", "extra": true, "age": 3, "other": "y" };
+    /// let (known, rest) = doc.partition(|key, _| key == "name" || key == "age");
+    /// assert_eq!(known, doc! { "name": "x", "age": 3 });
+    /// assert_eq!(rest, doc! { "extra": true, "other": "y" });
+    /// ```
+    pub fn partition<F: FnMut(&str, &Bson) -> bool>(self, mut f: F) -> (Document, Document) {
+        let mut matched = Document::new();
+        let mut rest = Document::new();
+        for (key, value) in self {
+            if f(&key, &value) {
+                matched.inner.insert(key, value);
+            } else {
+                rest.inner.insert(key, value);
+            }
+        }
+        (matched, rest)
+    }
+
+    /// Renames the entry with key `from` to `to`, keeping its value and its position in the
+    /// document. Returns `Ok(false)` if `from` is not present.
+    ///
+    /// If a different entry with key `to` already exists, the document is left unchanged and a
+    /// [`KeyCollisionError`] is returned. Computes in **O(n)** time (average).
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let mut doc = doc! { "a": 1, "b": 2, "c": 3 };
+    /// assert_eq!(doc.rename_key("b", "z"), Ok(true));
+    /// assert_eq!(doc, doc! { "a": 1, "z": 2, "c": 3 });
+    /// assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["a", "z", "c"]);
+    ///
+    /// assert_eq!(doc.rename_key("missing", "y"), Ok(false));
+    /// assert!(doc.rename_key("a", "c").is_err());
+    /// ```
+    pub fn rename_key(
+        &mut self,
+        from: impl AsRef<str>,
+        to: impl Into<String>,
+    ) -> Result<bool, KeyCollisionError> {
+        let from = from.as_ref();
+        let to = to.into();
+        if !self.inner.contains_key(from) {
+            return Ok(false);
+        }
+        if from == to {
+            return Ok(true);
+        }
+        if self.inner.contains_key(to.as_str()) {
+            return Err(KeyCollisionError { key: to });
+        }
+
+        if let Some((index, _, value)) = self.inner.shift_remove_full(from) {
+            let (new_index, _) = self.inner.insert_full(to, value);
+            self.inner.move_index(new_index, index);
+        }
+        Ok(true)
+    }
+
+    /// Sorts the entries of the document by key in lexicographic order. Nested documents are left
+    /// unchanged; see [`Document::sort_keys_recursive`] to sort those as well.
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let mut doc = doc! { "b": 1, "a": { "d": 2, "c": 3 } };
+    /// doc.sort_keys();
+    /// assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+    /// ```
+    pub fn sort_keys(&mut self) {
+        self.inner.sort_keys();
+    }
+
+    /// Sorts the entries of the document by key in lexicographic order, along with the entries of
+    /// every document nested within it, including documents contained in arrays and the scopes of
+    /// JavaScript code with scope values.
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let mut doc = doc! { "b": [{ "y": 1, "x": 2 }], "a": { "d": 2, "c": 3 } };
+    /// doc.sort_keys_recursive();
+    /// assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+    /// assert_eq!(doc.get_document("a")?.keys().collect::<Vec<_>>(), vec!["c", "d"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn sort_keys_recursive(&mut self) {
+        fn sort_value(value: &mut Bson) {
+            match value {
+                Bson::Document(doc) => doc.sort_keys_recursive(),
+                Bson::Array(arr) => arr.iter_mut().for_each(sort_value),
+                Bson::JavaScriptCodeWithScope(c) => c.scope.sort_keys_recursive(),
+                _ => {}
+            }
+        }
+
+        self.sort_keys();
+        self.inner.values_mut().for_each(sort_value);
+    }
+
+    /// Replaces the value of every entry in this document whose key matches `should_redact` with
+    /// `Bson::String("***")`, searching recursively through nested documents and arrays. See
+    /// [`Bson::redact`] for more details.
+    pub fn redact<F: Fn(&str) -> bool>(&mut self, should_redact: F) {
+        self.redact_with(&should_redact)
+    }
+
+    pub(crate) fn redact_with(&mut self, should_redact: &dyn Fn(&str) -> bool) {
+        for (key, value) in self.iter_mut() {
+            if should_redact(key) {
+                *value = Bson::String("***".to_string());
+            } else {
+                value.redact_with(should_redact);
+            }
+        }
+    }
+
+    /// Removes every entry whose value is [`Bson::Null`], searching recursively through nested
+    /// documents and arrays. See [`Bson::remove_nulls`] for more details.
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let mut doc = doc! { "a": 1, "b": null, "c": { "d": null } };
+    /// doc.remove_nulls();
+    /// assert_eq!(doc, doc! { "a": 1, "c": {} });
+    /// ```
+    pub fn remove_nulls(&mut self) {
+        self.remove_nulls_with(false)
+    }
+
+    /// Like [`Document::remove_nulls`], but also removes entries whose values are empty documents
+    /// or arrays. See [`Bson::remove_nulls_and_empty`] for more details.
+    pub fn remove_nulls_and_empty(&mut self) {
+        self.remove_nulls_with(true)
+    }
+
+    pub(crate) fn remove_nulls_with(&mut self, remove_empty: bool) {
+        self.inner.retain(|_, value| {
+            value.remove_nulls_with(remove_empty);
+            match value {
+                Bson::Null => false,
+                Bson::Document(doc) if remove_empty => !doc.is_empty(),
+                Bson::Array(arr) if remove_empty => !arr.is_empty(),
+                _ => true,
+            }
+        });
+    }
+
+    /// Returns a copy of this document with each key replaced by the result of calling `f` on
+    /// it. Keys of nested documents are left unchanged; see
+    /// [`Document::transform_keys_recursive`] to rewrite those as well.
+    ///
+    /// If `f` maps two keys to the same value, the later entry's value is kept at the position of
+    /// the earlier one.
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let doc = doc! { "a": 1, "b": { "c": 2 } };
+    /// assert_eq!(
+    ///     doc.transform_keys(|k| k.to_uppercase()),
+    ///     doc! { "A": 1, "B": { "c": 2 } }
+    /// );
+    /// ```
+    pub fn transform_keys<F: FnMut(&str) -> String>(&self, mut f: F) -> Document {
+        self.iter().map(|(k, v)| (f(k), v.clone())).collect()
+    }
+
+    /// Returns a copy of this document with each key replaced by the result of calling `f` on
+    /// it, including the keys of every document nested within it (and within arrays).
+    ///
+    /// As with [`Document::transform_keys`], if `f` maps two keys of the same document to the
+    /// same value, only the later entry's value is kept, at the position of the earlier one.
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let doc = doc! { "a": 1, "b": [{ "c": 2 }] };
+    /// assert_eq!(
+    ///     doc.transform_keys_recursive(|k| k.to_uppercase()),
+    ///     doc! { "A": 1, "B": [{ "C": 2 }] }
+    /// );
+    /// ```
+    pub fn transform_keys_recursive<F: FnMut(&str) -> String>(&self, mut f: F) -> Document {
+        fn transform_doc(doc: &Document, f: &mut dyn FnMut(&str) -> String) -> Document {
+            doc.iter()
+                .map(|(k, v)| (f(k), transform_value(v, f)))
+                .collect()
+        }
+
+        fn transform_value(value: &Bson, f: &mut dyn FnMut(&str) -> String) -> Bson {
+            match value {
+                Bson::Document(doc) => Bson::Document(transform_doc(doc, f)),
+                Bson::Array(arr) => {
+                    Bson::Array(arr.iter().map(|v| transform_value(v, f)).collect())
+                }
+                other => other.clone(),
+            }
+        }
+
+        transform_doc(self, &mut f)
+    }
+
+    /// Returns a copy of this document with all keys, including those of nested documents,
+    /// converted from `camelCase` or `PascalCase` to `snake_case`. Runs of capitals are treated
+    /// as a single word, so `"userID"` becomes `"user_id"` and `"HTTPServer"` becomes
+    /// `"http_server"`. Keys that are already `snake_case` are left unchanged, so a document that
+    /// contains both `"firstName"` and `"first_name"` keeps only the later entry's value, as
+    /// described in [`Document::transform_keys_recursive`].
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let doc = doc! { "_id": 1, "firstName": "a", "address": { "zipCode": "b" } };
+    /// assert_eq!(
+    ///     doc.keys_to_snake_case(),
+    ///     doc! { "_id": 1, "first_name": "a", "address": { "zip_code": "b" } }
+    /// );
+    /// ```
+    pub fn keys_to_snake_case(&self) -> Document {
+        self.transform_keys_recursive(to_snake_case)
+    }
+
+    /// Returns a copy of this document with all keys, including those of nested documents,
+    /// converted from `snake_case` to `camelCase`. Only a single underscore between two other
+    /// characters separates words; leading, trailing, and repeated underscores are preserved, so
+    /// `"_id"`, `"trailing_"`, and `"user__id"` are left unchanged. Keys that are already
+    /// `camelCase` are also left unchanged, so a document that contains both `"first_name"` and
+    /// `"firstName"` keeps only the later entry's value, as described in
+    /// [`Document::transform_keys_recursive`].
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let doc = doc! { "_id": 1, "first_name": "a", "address": { "zip_code": "b" } };
+    /// assert_eq!(
+    ///     doc.keys_to_camel_case(),
+    ///     doc! { "_id": 1, "firstName": "a", "address": { "zipCode": "b" } }
+    /// );
+    /// ```
+    pub fn keys_to_camel_case(&self) -> Document {
+        self.transform_keys_recursive(to_camel_case)
+    }
+
     pub fn entry(&mut self, k: String) -> Entry {
         match self.inner.entry(k) {
             indexmap::map::Entry::Occupied(o) => Entry::Occupied(OccupiedEntry { inner: o }),
@@ -601,8 +1060,84 @@ impl Document {
         Ok(())
     }
 
-    fn decode<R: Read + ?Sized>(reader: &mut R, utf_lossy: bool) -> crate::de::Result<Document> {
-        let buf = crate::de::reader_to_vec(reader)?;
+    /// Returns the number of bytes this document occupies when encoded as BSON, e.g. by
+    /// [`Document::to_writer`] or [`crate::to_vec`]. This uses the same measuring pass that
+    /// [`crate::to_writer`] runs to compute document lengths, which discards the encoded bytes as
+    /// it goes rather than collecting them, so it is a cheap way to check a document against a size
+    /// limit such as MongoDB's 16MiB maximum before sending it.
+    ///
+    /// Returns an error if the document cannot be encoded, e.g. if a key contains a null byte. The
+    /// maximum size itself is not enforced.
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let doc = doc! { "x": 1, "y": ["a", { "z": true }] };
+    /// assert_eq!(doc.byte_size()?, bson::to_vec(&doc)?.len());
+    /// # Ok::<(), bson::ser::Error>(())
+    /// ```
+    pub fn byte_size(&self) -> crate::ser::Result<usize> {
+        crate::ser::serialized_len(self)
+    }
+
+    /// Serializes the [`Document`] into a deterministic byte representation, suitable for hashing
+    /// or signing (e.g. with HMAC).
+    ///
+    /// The entries of this document and of every document nested within it, including documents
+    /// contained in arrays and code with scope scopes, are written in lexicographic byte order of
+    /// their keys, as with [`Document::sort_keys_recursive`]. Two documents that differ only in
+    /// the insertion order of their keys therefore produce identical bytes. Array element order is
+    /// preserved, and no other normalization (e.g. of numeric types) is performed.
+    ///
+    /// ```
+    /// use bson::doc;
+    ///
+    /// let a = doc! { "b": 1, "a": { "y": [{ "q": 1, "p": 2 }], "x": true } };
+    /// let b = doc! { "a": { "x": true, "y": [{ "p": 2, "q": 1 }] }, "b": 1 };
+    /// assert_eq!(a.to_canonical_vec()?, b.to_canonical_vec()?);
+    /// # Ok::<(), bson::ser::Error>(())
+    /// ```
+    pub fn to_canonical_vec(&self) -> crate::ser::Result<Vec<u8>> {
+        let mut sorted = self.clone();
+        sorted.sort_keys_recursive();
+        crate::to_vec(&sorted)
+    }
+
+    /// Wraps the document in a [`Bson::Document`].
+    ///
+    /// ```
+    /// use bson::{bson, doc};
+    ///
+    /// assert_eq!(doc! { "x": 1 }.into_bson(), bson!({ "x": 1 }));
+    /// ```
+    pub fn into_bson(self) -> Bson {
+        Bson::Document(self)
+    }
+
+    /// Converts the [`Document`] into a [`RawDocumentBuf`](crate::RawDocumentBuf).
+    ///
+    /// This re-encodes the entire document into a newly allocated buffer each time it is called,
+    /// so callers that need the raw form repeatedly should convert once and keep the result.
+    ///
+    /// ```
+    /// # use bson::raw::Error;
+    /// use bson::{doc, rawdoc};
+    ///
+    /// let doc = doc! { "x": 1, "y": { "z": "text" } };
+    /// let raw = doc.to_raw_document_buf()?;
+    /// assert_eq!(raw, rawdoc! { "x": 1, "y": { "z": "text" } });
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn to_raw_document_buf(&self) -> crate::raw::Result<crate::RawDocumentBuf> {
+        crate::RawDocumentBuf::from_document_bytes(self)
+    }
+
+    fn decode<R: Read + ?Sized>(
+        reader: &mut R,
+        utf_lossy: bool,
+        max_size: Option<usize>,
+    ) -> crate::de::Result<Document> {
+        let buf = crate::de::reader_to_vec_capped(reader, max_size)?;
         crate::de::from_raw(crate::de::RawDeserializer::new(&buf, utf_lossy)?)
     }
 
@@ -635,7 +1170,145 @@ impl Document {
     /// # }
     /// ```
     pub fn from_reader<R: Read>(mut reader: R) -> crate::de::Result<Document> {
-        Self::decode(&mut reader, false)
+        Self::decode(&mut reader, false, None)
+    }
+
+    /// Attempts to deserialize a [`Document`] from a byte stream using the provided options.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> std::result::Result<(), Box<dyn Error>> {
+    /// use bson::{de::DuplicateKeyPolicy, rawdoc, DeserializerOptions, Document};
+    ///
+    /// let raw = rawdoc! { "a": 1, "a": 2 };
+    ///
+    /// let options = DeserializerOptions::builder()
+    ///     .on_duplicate_key(DuplicateKeyPolicy::KeepFirst)
+    ///     .build();
+    /// let doc = Document::from_reader_with_options(raw.as_bytes(), options)?;
+    /// assert_eq!(doc.get_i32("a")?, 1);
+    ///
+    /// let options = DeserializerOptions::builder()
+    ///     .on_duplicate_key(DuplicateKeyPolicy::Error)
+    ///     .build();
+    /// assert!(Document::from_reader_with_options(raw.as_bytes(), options).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_reader_with_options<R: Read>(
+        mut reader: R,
+        options: crate::DeserializerOptions,
+    ) -> crate::de::Result<Document> {
+        match options.on_duplicate_key.unwrap_or_default() {
+            crate::de::DuplicateKeyPolicy::KeepLast => Self::decode(&mut reader, false, None),
+            policy => {
+                let buf = crate::de::reader_to_vec(reader)?;
+                crate::de::document_from_raw(crate::RawDocument::from_bytes(&buf)?, policy)
+            }
+        }
+    }
+
+    /// Attempts to deserialize a [`Document`] from a byte stream, returning an error if the
+    /// document's declared length exceeds `max_size` bytes.
+    ///
+    /// The length prefix is checked before any buffer is allocated or any further bytes are read,
+    /// so this is suitable for reading untrusted input where a malicious length could otherwise be
+    /// used to exhaust memory.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> std::result::Result<(), Box<dyn Error>> {
+    /// use bson::{doc, Document};
+    ///
+    /// let bytes = bson::to_vec(&doc! { "x": "a".repeat(100) })?;
+    ///
+    /// assert!(Document::from_reader_capped(bytes.as_slice(), 1024).is_ok());
+    /// assert!(Document::from_reader_capped(bytes.as_slice(), 64).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_reader_capped<R: Read>(
+        mut reader: R,
+        max_size: usize,
+    ) -> crate::de::Result<Document> {
+        Self::decode(&mut reader, false, Some(max_size))
+    }
+
+    /// Attempts to deserialize a [`Document`] from a byte stream, using `scratch` to hold the
+    /// document's bytes while it is decoded.
+    ///
+    /// `scratch` is cleared before each read, but its allocation is kept, so reusing the same
+    /// buffer across calls avoids allocating a new one for every document read from a stream.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> std::result::Result<(), Box<dyn Error>> {
+    /// use bson::{doc, Document};
+    ///
+    /// let mut bytes = bson::to_vec(&doc! { "x": 1 })?;
+    /// bytes.extend(bson::to_vec(&doc! { "y": 2 })?);
+    ///
+    /// let mut reader = bytes.as_slice();
+    /// let mut scratch = Vec::new();
+    /// let first = Document::from_reader_buffered(&mut reader, &mut scratch)?;
+    /// let second = Document::from_reader_buffered(&mut reader, &mut scratch)?;
+    /// assert_eq!(first, doc! { "x": 1 });
+    /// assert_eq!(second, doc! { "y": 2 });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_reader_buffered<R: Read>(
+        reader: R,
+        scratch: &mut Vec<u8>,
+    ) -> crate::de::Result<Document> {
+        crate::de::read_document_into(reader, None, scratch)?;
+        crate::de::from_raw(crate::de::RawDeserializer::new(scratch, false)?)
+    }
+
+    /// Attempts to deserialize a [`Document`] from the start of `bytes`, returning it along with
+    /// whatever bytes follow the end of the document.
+    ///
+    /// Unlike [`crate::from_slice`], `bytes` does not need to be trimmed to the exact length of
+    /// the document, which makes this useful for reading a sequence of concatenated documents from
+    /// a single buffer.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> std::result::Result<(), Box<dyn Error>> {
+    /// use bson::{doc, Document};
+    ///
+    /// let mut bytes = bson::to_vec(&doc! { "x": 1 })?;
+    /// bytes.extend(bson::to_vec(&doc! { "y": 2 })?);
+    ///
+    /// let (first, rest) = Document::from_slice_prefix(&bytes)?;
+    /// let (second, rest) = Document::from_slice_prefix(rest)?;
+    /// assert_eq!(first, doc! { "x": 1 });
+    /// assert_eq!(second, doc! { "y": 2 });
+    /// assert!(rest.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_slice_prefix(bytes: &[u8]) -> crate::de::Result<(Document, &[u8])> {
+        use serde::de::Error as _;
+
+        let length = match bytes.get(..4) {
+            Some(prefix) => i32::from_le_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]),
+            None => return Err(crate::de::Error::custom("document size too small")),
+        };
+        if length < crate::de::MIN_BSON_DOCUMENT_SIZE {
+            return Err(crate::de::Error::custom("document size too small"));
+        }
+        let length = length as usize;
+        if length > bytes.len() {
+            return Err(crate::de::Error::custom(format!(
+                "document size {} exceeds remaining input of {} bytes",
+                length,
+                bytes.len()
+            )));
+        }
+
+        let (doc, rest) = bytes.split_at(length);
+        Ok((crate::from_slice(doc)?, rest))
     }
 
     /// Attempt to deserialize a [`Document`] that may contain invalid UTF-8 strings from a byte
@@ -646,10 +1319,57 @@ impl Document {
     /// For most use cases, `Document::from_reader` can be used instead.
     #[deprecated = "use bson::serde_helpers::Utf8LossyDeserialization"]
     pub fn from_reader_utf8_lossy<R: Read>(mut reader: R) -> crate::de::Result<Document> {
-        Self::decode(&mut reader, true)
+        Self::decode(&mut reader, true, None)
     }
 }
 
+fn to_snake_case(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    let mut out = String::with_capacity(key.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let starts_word = match prev {
+                Some(p) if p.is_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_uppercase() => next.is_some_and(|n| n.is_lowercase()),
+                _ => false,
+            };
+            if starts_word {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn to_camel_case(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    let mut out = String::with_capacity(key.len());
+    let mut upper_next = false;
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let separates_words = prev.is_some_and(|p| p != '_') && next.is_some_and(|&n| n != '_');
+            if separates_words {
+                upper_next = true;
+                continue;
+            }
+        }
+        if upper_next {
+            out.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
 /// A view into a single entry in a map, which may either be vacant or occupied.
 ///
 /// This enum is constructed from the entry method on HashMap.
@@ -723,3 +1443,73 @@ impl Extend<(String, Bson)> for Document {
         }
     }
 }
+
+impl<'a> Extend<(&'a str, Bson)> for Document {
+    fn extend<T: IntoIterator<Item = (&'a str, Bson)>>(&mut self, iter: T) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+/// A builder for constructing a [`Document`] incrementally, which is convenient when some fields
+/// should only be included conditionally.
+///
+/// Fields are added in the order the methods are called. As with [`Document::insert`], adding a
+/// key that is already present replaces its value but keeps its original position.
+///
+/// ```
+/// use bson::{doc, Document};
+///
+/// let nickname: Option<&str> = None;
+/// let age = Some(42);
+///
+/// let doc = Document::builder()
+///     .field("name", "Alice")
+///     .field_if_some("nickname", nickname)
+///     .field_if_some("age", age)
+///     .field_if("admin", true, age.is_some_and(|a| a > 40))
+///     .build();
+/// assert_eq!(doc, doc! { "name": "Alice", "age": 42, "admin": true });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DocumentBuilder {
+    doc: Document,
+}
+
+impl DocumentBuilder {
+    /// Creates a new builder for an empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a field with the given key and value.
+    pub fn field(mut self, key: impl Into<String>, value: impl Into<Bson>) -> Self {
+        self.doc.insert(key, value);
+        self
+    }
+
+    /// Adds a field with the given key and the contents of `value` if it is [`Some`], and does
+    /// nothing if it is [`None`].
+    pub fn field_if_some<T: Into<Bson>>(self, key: impl Into<String>, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.field(key, value),
+            None => self,
+        }
+    }
+
+    /// Adds a field with the given key and value if `condition` is true, and does nothing
+    /// otherwise.
+    pub fn field_if(self, key: impl Into<String>, value: impl Into<Bson>, condition: bool) -> Self {
+        if condition {
+            self.field(key, value)
+        } else {
+            self
+        }
+    }
+
+    /// Returns the constructed document.
+    pub fn build(self) -> Document {
+        self.doc
+    }
+}
@@ -21,7 +21,10 @@
 //! Bson::try_from(invalid_ext_json).expect_err("5 should be a string");
 //! ```
 
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    str::FromStr,
+};
 
 use serde::de::{Error as _, Unexpected};
 
@@ -208,6 +211,26 @@ impl TryFrom<serde_json::Value> for Bson {
     }
 }
 
+/// This parses the input string as [MongoDB Extended JSON v2](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/).
+///
+/// ```
+/// # use bson::{oid::ObjectId, Bson};
+/// let bson: Bson = r#"{ "$oid": "507f1f77bcf86cd799439011" }"#.parse()?;
+/// assert_eq!(bson, Bson::ObjectId(ObjectId::parse_str("507f1f77bcf86cd799439011")?));
+///
+/// assert!("{ not json".parse::<Bson>().is_err());
+/// assert!(r#"{ "$numberLong": 5 }"#.parse::<Bson>().is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+impl FromStr for Bson {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(s)?;
+        value.try_into()
+    }
+}
+
 /// This converts from the input JSON as if it were [MongoDB Extended JSON v2](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/).
 impl TryFrom<serde_json::Map<String, serde_json::Value>> for Document {
     type Error = Error;
@@ -90,3 +90,4 @@
 
 pub mod de;
 pub(crate) mod models;
+pub(crate) mod ser;
@@ -0,0 +1,182 @@
+//! Serialization of [`Bson`] values to extended JSON. This backs both
+//! [`Bson::into_relaxed_extjson`] and [`Bson::into_canonical_extjson`], which collect the output
+//! into a [`serde_json::Value`], and the `to_*_extjson_string` methods, which write text directly.
+
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use crate::{Binary, Bson, DbPointer, Document, JavaScriptCodeWithScope, Regex, Timestamp};
+
+/// A borrowed [`Bson`] value that serializes as extended JSON.
+pub(crate) struct ExtJson<'a> {
+    pub(crate) value: &'a Bson,
+    pub(crate) canonical: bool,
+}
+
+impl<'a> ExtJson<'a> {
+    fn nested(&self, value: &'a Bson) -> Self {
+        Self {
+            value,
+            canonical: self.canonical,
+        }
+    }
+}
+
+/// A borrowed [`Document`] that serializes as an extended JSON object.
+struct ExtJsonDocument<'a> {
+    doc: &'a Document,
+    canonical: bool,
+}
+
+impl Serialize for ExtJsonDocument<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.doc.iter().map(|(k, value)| {
+            let value = ExtJson {
+                value,
+                canonical: self.canonical,
+            };
+            (k, value)
+        }))
+    }
+}
+
+/// A JSON object with a single entry.
+struct Object1<A>(&'static str, A);
+
+impl<A: Serialize> Serialize for Object1<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.0, &self.1)?;
+        map.end()
+    }
+}
+
+/// A JSON object with two entries.
+struct Object2<A, B>(&'static str, A, &'static str, B);
+
+impl<A: Serialize, B: Serialize> Serialize for Object2<A, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry(self.0, &self.1)?;
+        map.serialize_entry(self.2, &self.3)?;
+        map.end()
+    }
+}
+
+impl Serialize for ExtJson<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.canonical {
+            match self.value {
+                Bson::Int32(i) => {
+                    return Object1("$numberInt", i.to_string()).serialize(serializer)
+                }
+                Bson::Int64(i) => {
+                    return Object1("$numberLong", i.to_string()).serialize(serializer)
+                }
+                Bson::Double(f) if f.is_normal() => {
+                    let mut s = f.to_string();
+                    if f.fract() == 0.0 {
+                        s.push_str(".0");
+                    }
+                    return Object1("$numberDouble", s).serialize(serializer);
+                }
+                Bson::Double(f) if *f == 0.0 => {
+                    let s = if f.is_sign_negative() { "-0.0" } else { "0.0" };
+                    return Object1("$numberDouble", s).serialize(serializer);
+                }
+                Bson::DateTime(date) => {
+                    return Object1(
+                        "$date",
+                        Object1("$numberLong", date.timestamp_millis().to_string()),
+                    )
+                    .serialize(serializer)
+                }
+                Bson::JavaScriptCodeWithScope(JavaScriptCodeWithScope { code, scope }) => {
+                    let scope = ExtJsonDocument {
+                        doc: scope,
+                        canonical: true,
+                    };
+                    return Object2("$code", code, "$scope", scope).serialize(serializer);
+                }
+                _ => {}
+            }
+        }
+
+        match self.value {
+            Bson::Double(v) if v.is_nan() => {
+                let s = if v.is_sign_negative() { "-NaN" } else { "NaN" };
+                Object1("$numberDouble", s).serialize(serializer)
+            }
+            Bson::Double(v) if v.is_infinite() => {
+                let s = if v.is_sign_negative() {
+                    "-Infinity"
+                } else {
+                    "Infinity"
+                };
+                Object1("$numberDouble", s).serialize(serializer)
+            }
+            Bson::Double(v) => serializer.serialize_f64(*v),
+            Bson::String(v) => serializer.serialize_str(v),
+            Bson::Array(v) => serializer.collect_seq(v.iter().map(|v| self.nested(v))),
+            Bson::Document(doc) => ExtJsonDocument {
+                doc,
+                canonical: self.canonical,
+            }
+            .serialize(serializer),
+            Bson::Boolean(v) => serializer.serialize_bool(*v),
+            Bson::Null => serializer.serialize_unit(),
+            Bson::RegularExpression(Regex { pattern, options }) => {
+                let mut chars: Vec<_> = options.chars().collect();
+                chars.sort_unstable();
+                let options: String = chars.into_iter().collect();
+
+                Object1(
+                    "$regularExpression",
+                    Object2("pattern", pattern, "options", options),
+                )
+                .serialize(serializer)
+            }
+            Bson::JavaScriptCode(code) => Object1("$code", code).serialize(serializer),
+            Bson::JavaScriptCodeWithScope(JavaScriptCodeWithScope { code, scope }) => {
+                Object2("$code", code, "$scope", scope).serialize(serializer)
+            }
+            Bson::Int32(v) => serializer.serialize_i32(*v),
+            Bson::Int64(v) => serializer.serialize_i64(*v),
+            Bson::Timestamp(Timestamp { time, increment }) => {
+                Object1("$timestamp", Object2("t", time, "i", increment)).serialize(serializer)
+            }
+            Bson::Binary(Binary { subtype, bytes }) => {
+                let tval: u8 = From::from(*subtype);
+                Object1(
+                    "$binary",
+                    Object2(
+                        "base64",
+                        base64::encode(bytes),
+                        "subType",
+                        hex::encode([tval]),
+                    ),
+                )
+                .serialize(serializer)
+            }
+            Bson::ObjectId(v) => Object1("$oid", v.to_hex()).serialize(serializer),
+            Bson::DateTime(v) if v.timestamp_millis() >= 0 && v.to_time_0_3().year() <= 9999 => {
+                // Unwrap safety: timestamps in the guarded range can always be formatted.
+                Object1("$date", v.try_to_rfc3339_string().unwrap()).serialize(serializer)
+            }
+            Bson::DateTime(v) => Object1(
+                "$date",
+                Object1("$numberLong", v.timestamp_millis().to_string()),
+            )
+            .serialize(serializer),
+            Bson::Symbol(v) => Object1("$symbol", v).serialize(serializer),
+            Bson::Decimal128(v) => Object1("$numberDecimal", v.to_string()).serialize(serializer),
+            Bson::Undefined => Object1("$undefined", true).serialize(serializer),
+            Bson::MinKey => Object1("$minKey", 1).serialize(serializer),
+            Bson::MaxKey => Object1("$maxKey", 1).serialize(serializer),
+            Bson::DbPointer(DbPointer { namespace, id }) => Object1(
+                "$dbPointer",
+                Object2("$ref", namespace, "$id", Object1("$oid", id.to_hex())),
+            )
+            .serialize(serializer),
+        }
+    }
+}
@@ -273,7 +273,9 @@
 //! ## WASM support
 //!
 //! This crate compiles to the `wasm32-unknown-unknown` target; when doing so, the `js-sys` crate is
-//! used for the current timestamp component of `ObjectId` generation.
+//! used for the current timestamp component of `ObjectId` generation. See
+//! [`ObjectId::new_with_timestamp`](oid::ObjectId::new_with_timestamp) for generating ids from a
+//! time supplied by the host instead.
 //!
 //! ## Minimum supported Rust version (MSRV)
 //!
@@ -287,7 +289,16 @@
 #[doc(inline)]
 pub use self::{
     binary::Binary,
-    bson::{Array, Bson, DbPointer, Document, JavaScriptCodeWithScope, Regex, Timestamp},
+    bson::{
+        Array,
+        Bson,
+        DbPointer,
+        Document,
+        JavaScriptCodeWithScope,
+        PathSegment,
+        Regex,
+        Timestamp,
+    },
     datetime::DateTime,
     de::{
         from_bson,
@@ -297,7 +308,9 @@ pub use self::{
         from_reader,
         from_slice,
         Deserializer,
+        DocumentReader,
         DeserializerOptions,
+        DuplicateKeyPolicy,
     },
     decimal128::Decimal128,
     raw::{
@@ -320,6 +333,11 @@ pub use self::{
         to_document_with_options,
         to_raw_document_buf,
         to_vec,
+        to_vec_with_buffer,
+        to_vec_with_options,
+        to_writer,
+        write_documents,
+        EnumRepresentation,
         Serializer,
         SerializerOptions,
     },
@@ -213,6 +213,259 @@ macro_rules! doc {
     }};
 }
 
+/// Construct a bson::BSON value from a literal, where values may be any type that implements
+/// `TryInto<Bson>`.
+///
+/// This accepts the same syntax as [`bson!`], but rather than requiring values to implement
+/// `Into<Bson>`, each value is converted with [`TryInto`](std::convert::TryInto) and the whole
+/// expression evaluates to a `Result<Bson, Box<dyn std::error::Error + Send + Sync>>` holding
+/// either the constructed value or the first conversion error encountered.
+///
+/// ```rust
+/// # use bson::{try_bson, Bson};
+/// #
+/// # fn main() {
+/// let value = try_bson!({
+///     "parsed": serde_json::json!({ "$oid": "507f1f77bcf86cd799439011" }),
+///     "plain": 1,
+/// });
+/// assert!(value.is_ok());
+///
+/// let error = try_bson!([serde_json::json!({ "$oid": "not an oid" })]);
+/// assert!(error.is_err());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! try_bson {
+    //////////////////////////////////////////////////////////////////////////
+    // TT muncher for parsing the inside of an array [...]. Produces a vec![...]
+    // of the elements.
+    //
+    // Must be invoked as: try_bson!(@array [] $($tt)*)
+    //////////////////////////////////////////////////////////////////////////
+
+    // Finished with trailing comma.
+    (@array [$($elems:expr,)*]) => {
+        vec![$($elems,)*]
+    };
+
+    // Finished without trailing comma.
+    (@array [$($elems:expr),*]) => {
+        vec![$($elems),*]
+    };
+
+    // Next element is `null`.
+    (@array [$($elems:expr,)*] null $($rest:tt)*) => {
+        $crate::try_bson!(@array [$($elems,)* $crate::try_bson!(@value null)] $($rest)*)
+    };
+
+    // Next element is an array.
+    (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
+        $crate::try_bson!(@array [$($elems,)* $crate::try_bson!(@value [$($array)*])] $($rest)*)
+    };
+
+    // Next element is a map.
+    (@array [$($elems:expr,)*] {$($map:tt)*} $($rest:tt)*) => {
+        $crate::try_bson!(@array [$($elems,)* $crate::try_bson!(@value {$($map)*})] $($rest)*)
+    };
+
+    // Next element is an expression followed by comma.
+    (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
+        $crate::try_bson!(@array [$($elems,)* $crate::try_bson!(@value $next),] $($rest)*)
+    };
+
+    // Last element is an expression with no trailing comma.
+    (@array [$($elems:expr,)*] $last:expr) => {
+        $crate::try_bson!(@array [$($elems,)* $crate::try_bson!(@value $last)])
+    };
+
+    // Comma after the most recent element.
+    (@array [$($elems:expr),*] , $($rest:tt)*) => {
+        $crate::try_bson!(@array [$($elems,)*] $($rest)*)
+    };
+
+    //////////////////////////////////////////////////////////////////////////
+    // TT muncher for parsing the inside of an object {...}. Each entry is
+    // inserted into the given map variable.
+    //
+    // Must be invoked as: try_bson!(@object $map () ($($tt)*) ($($tt)*))
+    //
+    // We require two copies of the input tokens so that we can match on one
+    // copy and trigger errors on the other copy.
+    //////////////////////////////////////////////////////////////////////////
+
+    // Finished.
+    (@object $object:ident () () ()) => {};
+
+    // Insert the current entry followed by trailing comma.
+    (@object $object:ident [$($key:tt)+] ($value:expr) , $($rest:tt)*) => {
+        $object.insert::<_, $crate::Bson>(($($key)+), $value);
+        $crate::try_bson!(@object $object () ($($rest)*) ($($rest)*));
+    };
+
+    // Insert the last entry without trailing comma.
+    (@object $object:ident [$($key:tt)+] ($value:expr)) => {
+        $object.insert::<_, $crate::Bson>(($($key)+), $value);
+    };
+
+    // Next value is `null`.
+    (@object $object:ident ($($key:tt)+) (: null $($rest:tt)*) $copy:tt) => {
+        $crate::try_bson!(@object $object [$($key)+] ($crate::try_bson!(@value null)) $($rest)*);
+    };
+
+    // Next value is an array.
+    (@object $object:ident ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
+        $crate::try_bson!(@object $object [$($key)+] ($crate::try_bson!(@value [$($array)*])) $($rest)*);
+    };
+
+    // Next value is a map.
+    (@object $object:ident ($($key:tt)+) (: {$($map:tt)*} $($rest:tt)*) $copy:tt) => {
+        $crate::try_bson!(@object $object [$($key)+] ($crate::try_bson!(@value {$($map)*})) $($rest)*);
+    };
+
+    // Next value is an expression followed by comma.
+    (@object $object:ident ($($key:tt)+) (: $value:expr , $($rest:tt)*) $copy:tt) => {
+        $crate::try_bson!(@object $object [$($key)+] ($crate::try_bson!(@value $value)) , $($rest)*);
+    };
+
+    // Last value is an expression with no trailing comma.
+    (@object $object:ident ($($key:tt)+) (: $value:expr) $copy:tt) => {
+        $crate::try_bson!(@object $object [$($key)+] ($crate::try_bson!(@value $value)));
+    };
+
+    // Missing value for last entry. Trigger a reasonable error message.
+    (@object $object:ident ($($key:tt)+) (:) $copy:tt) => {
+        // "unexpected end of macro invocation"
+        $crate::try_bson!();
+    };
+
+    // Missing key-value separator and value for last entry.
+    // Trigger a reasonable error message.
+    (@object $object:ident ($($key:tt)+) () $copy:tt) => {
+        // "unexpected end of macro invocation"
+        $crate::try_bson!();
+    };
+
+    // Misplaced key-value separator. Trigger a reasonable error message.
+    (@object $object:ident () (: $($rest:tt)*) ($kv_separator:tt $($copy:tt)*)) => {
+        // Takes no arguments so "no rules expected the token `:`".
+        unimplemented!($kv_separator);
+    };
+
+    // Found a comma inside a key. Trigger a reasonable error message.
+    (@object $object:ident ($($key:tt)*) (, $($rest:tt)*) ($comma:tt $($copy:tt)*)) => {
+        // Takes no arguments so "no rules expected the token `,`".
+        unimplemented!($comma);
+    };
+
+    // Key is fully parenthesized. This avoids clippy double_parens false
+    // positives because the parenthesization may be necessary here.
+    (@object $object:ident () (($key:expr) : $($rest:tt)*) $copy:tt) => {
+        $crate::try_bson!(@object $object ($key) (: $($rest)*) (: $($rest)*));
+    };
+
+    // Munch a token into the current key.
+    (@object $object:ident ($($key:tt)*) ($tt:tt $($rest:tt)*) $copy:tt) => {
+        $crate::try_bson!(@object $object ($($key)* $tt) ($($rest)*) ($($rest)*));
+    };
+
+    //////////////////////////////////////////////////////////////////////////
+    // Produces a Bson value, propagating conversion errors with `?`. Must be
+    // expanded inside the closure created by the main implementation.
+    //
+    // Must be invoked as: try_bson!(@value $($bson)+)
+    //////////////////////////////////////////////////////////////////////////
+
+    (@value null) => {
+        $crate::Bson::Null
+    };
+
+    (@value []) => {
+        $crate::Bson::Array(vec![])
+    };
+
+    (@value [ $($tt:tt)+ ]) => {
+        $crate::Bson::Array($crate::try_bson!(@array [] $($tt)+))
+    };
+
+    (@value {}) => {
+        $crate::Bson::Document($crate::Document::new())
+    };
+
+    (@value {$($tt:tt)+}) => {{
+        let mut object = $crate::Document::new();
+        $crate::try_bson!(@object object () ($($tt)+) ($($tt)+));
+        $crate::Bson::Document(object)
+    }};
+
+    // Any TryInto<Bson> type.
+    // Must be below every other @value rule.
+    (@value $other:expr) => {
+        ::std::convert::TryInto::<$crate::Bson>::try_into($other).map_err(
+            |e| -> ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync> {
+                e.into()
+            },
+        )?
+    };
+
+    //////////////////////////////////////////////////////////////////////////
+    // The main implementation.
+    //
+    // Must be invoked as: try_bson!($($bson)+)
+    //////////////////////////////////////////////////////////////////////////
+
+    ($($tt:tt)+) => {{
+        #[allow(clippy::redundant_closure_call)]
+        let result = (|| -> ::std::result::Result<
+            $crate::Bson,
+            ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+        > {
+            ::std::result::Result::Ok($crate::try_bson!(@value $($tt)+))
+        })();
+        result
+    }};
+}
+
+/// Construct a bson::Document value, where values may be any type that implements
+/// `TryInto<Bson>`.
+///
+/// This accepts the same syntax as [`doc!`], and evaluates to a
+/// `Result<Document, Box<dyn std::error::Error + Send + Sync>>` holding either the constructed
+/// document or the first conversion error encountered. See [`try_bson!`] for more details.
+///
+/// ```rust
+/// # use bson::{try_doc, RawBson};
+/// #
+/// # fn main() {
+/// let doc = try_doc! {
+///     "a": RawBson::Int32(1),
+///     "b": { "c": RawBson::String("d".to_string()) },
+/// };
+/// assert_eq!(doc.unwrap(), bson::doc! { "a": 1, "b": { "c": "d" } });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! try_doc {
+    () => {{
+        ::std::result::Result::<
+            $crate::Document,
+            ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+        >::Ok($crate::Document::new())
+    }};
+    ( $($tt:tt)+ ) => {{
+        #[allow(clippy::redundant_closure_call)]
+        let result = (|| -> ::std::result::Result<
+            $crate::Document,
+            ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+        > {
+            let mut object = $crate::Document::new();
+            $crate::try_bson!(@object object () ($($tt)+) ($($tt)+));
+            ::std::result::Result::Ok(object)
+        })();
+        result
+    }};
+}
+
 /// Construct a [`crate::RawBson`] value from a literal.
 ///
 /// ```rust
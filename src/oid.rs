@@ -2,6 +2,7 @@
 //! For more information, see the documentation for the [`ObjectId`] type.
 
 use std::{
+    cmp,
     error,
     fmt,
     result,
@@ -74,6 +75,19 @@ impl error::Error for Error {}
 
 /// A wrapper around a raw 12-byte ObjectId.
 ///
+/// ## Ordering
+/// [`ObjectId`]s are ordered by comparing their raw bytes, which is also the order of their hex
+/// string representations. Because the bytes are laid out as a big-endian timestamp (in seconds),
+/// then a per-process random value, then a big-endian counter, ids compare first by the second in
+/// which they were created.
+///
+/// Within the same second, ids generated by [`ObjectId::new`] in a single process compare by
+/// their counter, which is incremented for every id generated. The counter starts at a random
+/// value and wraps around after 2<sup>24</sup> ids, so ids created later in the same second sort
+/// after earlier ones unless the counter wrapped in between. Ids generated by different
+/// processes in the same second are ordered by their random process values, which have no
+/// relation to creation order.
+///
 /// ## `serde` integration
 /// When serialized to BSON via `serde`, this type produces a BSON ObjectId. In non-BSON formats, it
 /// will serialize to and deserialize from that format's equivalent of the [extended JSON representation](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/) of a BSON ObjectId.
@@ -137,11 +151,25 @@ impl error::Error for Error {}
 /// bson: { "oid": ObjectId("63ceeffd37518221cdc6cda2"), "oid_as_hex": "63ceeffd37518221cdc6cda3" }
 /// json: {"oid":{"$oid":"63ceeffd37518221cdc6cda2"},"oid_as_hex":"63ceeffd37518221cdc6cda3"}
 /// ```
-#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ObjectId {
     id: [u8; 12],
 }
 
+impl PartialOrd for ObjectId {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares the raw bytes of the ids. See the [type-level documentation](ObjectId#ordering) for
+/// how this relates to creation order.
+impl Ord for ObjectId {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
 impl Default for ObjectId {
     fn default() -> Self {
         Self::new()
@@ -162,16 +190,104 @@ impl From<[u8; 12]> for ObjectId {
     }
 }
 
+/// A source of newly generated [`ObjectId`]s, for use with [`ObjectId::new_with`].
+///
+/// Implementing this trait allows the generation of ids to be controlled, e.g. to use a fixed
+/// clock or a seeded random number generator in tests.
+///
+/// ```
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use bson::oid::{ObjectId, ObjectIdGenerator};
+///
+/// struct Sequential(AtomicU32);
+///
+/// impl ObjectIdGenerator for Sequential {
+///     fn generate(&self) -> ObjectId {
+///         let count = self.0.fetch_add(1, Ordering::SeqCst);
+///         let [_, a, b, c] = count.to_be_bytes();
+///         ObjectId::from_parts(1_700_000_000, [0; 5], [a, b, c])
+///     }
+/// }
+///
+/// let generator = Sequential(AtomicU32::new(0));
+/// assert_eq!(ObjectId::new_with(&generator).to_hex(), "6553f1000000000000000000");
+/// assert_eq!(ObjectId::new_with(&generator).counter(), 1);
+/// ```
+pub trait ObjectIdGenerator {
+    /// Generates a new [`ObjectId`].
+    fn generate(&self) -> ObjectId;
+}
+
+/// The [`ObjectIdGenerator`] used by [`ObjectId::new`]. Ids are composed of the current time, a
+/// random value generated once per process, and a process-wide counter that starts at a random
+/// value and is incremented for each id.
+///
+/// ```
+/// use bson::oid::{DefaultObjectIdGenerator, ObjectId};
+///
+/// let id = ObjectId::new_with(&DefaultObjectIdGenerator);
+/// assert_ne!(id, ObjectId::new_with(&DefaultObjectIdGenerator));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultObjectIdGenerator;
+
+impl ObjectIdGenerator for DefaultObjectIdGenerator {
+    fn generate(&self) -> ObjectId {
+        let timestamp = ObjectId::gen_timestamp();
+        let process_id = ObjectId::gen_process_id();
+        let counter = ObjectId::gen_count();
+
+        ObjectId::from_parts(timestamp, process_id, counter)
+    }
+}
+
 impl ObjectId {
     /// Generates a new [`ObjectId`], represented in bytes.
     /// See the [docs](http://www.mongodb.com/docs/manual/reference/object-id/)
     /// for more information.
     pub fn new() -> Self {
-        let timestamp = Self::gen_timestamp();
-        let process_id = Self::gen_process_id();
-        let counter = Self::gen_count();
+        DefaultObjectIdGenerator.generate()
+    }
 
-        Self::from_parts(timestamp, process_id, counter)
+    /// Generates a new [`ObjectId`] whose timestamp is `dt`, truncated to whole seconds, rather
+    /// than the current time. The process and counter components are generated as in
+    /// [`ObjectId::new`], so ids created with the same timestamp are still unique.
+    ///
+    /// Unlike [`ObjectId::new`], this does not read the current time: it neither reads the system
+    /// clock nor, on `wasm32-unknown-unknown`, calls the JavaScript `Date` API through `js-sys`.
+    /// This makes it usable when the time has to be supplied by the host, e.g. on WebAssembly
+    /// targets where no clock is available to Rust.
+    ///
+    /// It does not remove the other platform requirements of [`ObjectId::new`]. The process and
+    /// counter components are still seeded from the operating system's random number source via
+    /// `getrandom`, which on `wasm32-unknown-unknown` needs a JavaScript environment or a custom
+    /// `getrandom` backend, and on WASI is provided by the host. `js-sys` also remains a
+    /// dependency of this crate on `wasm32-unknown-unknown` whether or not this method is used. To
+    /// generate ids without either, implement [`ObjectIdGenerator`] or use
+    /// [`ObjectId::from_parts`].
+    ///
+    /// Times before the Unix epoch or after the maximum 4-byte timestamp (in the year 2106) are
+    /// clamped to that range.
+    ///
+    /// ```
+    /// use bson::{oid::ObjectId, DateTime};
+    ///
+    /// let dt = DateTime::from_millis(1_700_000_000_999);
+    /// let id = ObjectId::new_with_timestamp(dt);
+    /// assert_eq!(id.timestamp(), DateTime::from_millis(1_700_000_000_000));
+    /// assert_ne!(id, ObjectId::new_with_timestamp(dt));
+    /// ```
+    pub fn new_with_timestamp(dt: crate::DateTime) -> Self {
+        let seconds = dt
+            .timestamp_millis()
+            .div_euclid(1000)
+            .clamp(0, u32::MAX as i64) as u32;
+        Self::from_parts(seconds, Self::gen_process_id(), Self::gen_count())
+    }
+
+    /// Generates a new [`ObjectId`] using the given [`ObjectIdGenerator`].
+    pub fn new_with(generator: &(impl ObjectIdGenerator + ?Sized)) -> Self {
+        generator.generate()
     }
 
     /// Constructs a new ObjectId wrapper around the raw byte representation.
@@ -233,6 +349,40 @@ impl ObjectId {
         crate::DateTime::from_millis(seconds_since_epoch as i64 * 1000)
     }
 
+    /// Returns the 4-byte big-endian timestamp component of this [`ObjectId`], which holds the
+    /// number of seconds since the Unix epoch at which it was generated.
+    pub fn timestamp_bytes(&self) -> [u8; 4] {
+        let mut buf = [0; TIMESTAMP_SIZE];
+        buf.copy_from_slice(&self.id[TIMESTAMP_OFFSET..(TIMESTAMP_OFFSET + TIMESTAMP_SIZE)]);
+        buf
+    }
+
+    /// Returns the 5-byte random value component of this [`ObjectId`]. This is generated once
+    /// per process, so all ids generated by the same process via [`ObjectId::new`] share it.
+    pub fn process_bytes(&self) -> [u8; 5] {
+        let mut buf = [0; PROCESS_ID_SIZE];
+        buf.copy_from_slice(&self.id[PROCESS_ID_OFFSET..(PROCESS_ID_OFFSET + PROCESS_ID_SIZE)]);
+        buf
+    }
+
+    /// Returns the 3-byte counter component of this [`ObjectId`] as an integer. Ids generated by
+    /// the same process via [`ObjectId::new`] have successive counter values, wrapping around
+    /// after `0xFFFFFF`.
+    ///
+    /// ```
+    /// use bson::oid::ObjectId;
+    ///
+    /// let first = ObjectId::new();
+    /// let second = ObjectId::new();
+    /// assert_eq!(first.process_bytes(), second.process_bytes());
+    /// assert_eq!(second.counter(), (first.counter() + 1) & 0xFF_FFFF);
+    /// ```
+    pub fn counter(&self) -> u32 {
+        let mut buf = [0; 4];
+        buf[1..].copy_from_slice(&self.id[COUNTER_OFFSET..(COUNTER_OFFSET + COUNTER_SIZE)]);
+        u32::from_be_bytes(buf)
+    }
+
     /// Returns the raw byte representation of an ObjectId.
     pub const fn bytes(&self) -> [u8; 12] {
         self.id
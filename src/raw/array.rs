@@ -1,6 +1,6 @@
 use std::{borrow::Cow, convert::TryFrom};
 
-use serde::{ser::SerializeSeq, Deserialize, Serialize};
+use serde::{de::DeserializeOwned, ser::SerializeSeq, Deserialize, Serialize};
 
 use super::{
     error::{ValueAccessError, ValueAccessErrorKind, ValueAccessResult},
@@ -103,6 +103,43 @@ impl RawArray {
         RawArrayBuf::from_raw_document_buf(self.doc.to_raw_document_buf())
     }
 
+    /// Deserializes each element of this array into a `T`, collecting the results into a
+    /// [`Vec`]. This is equivalent to deserializing the array as a `Vec<T>` field, but without
+    /// needing an enclosing document or converting to [`Bson`] first.
+    ///
+    /// ```
+    /// use bson::rawdoc;
+    ///
+    /// let doc = rawdoc! { "xs": [1, 2, 3] };
+    /// let xs: Vec<i64> = doc.get_array("xs")?.to_vec()?;
+    /// assert_eq!(xs, vec![1, 2, 3]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_vec<T: DeserializeOwned>(&self) -> crate::de::Result<Vec<T>> {
+        self.iter_as().collect()
+    }
+
+    /// Returns an iterator that lazily deserializes each element of this array into a `T`.
+    /// Deserialized values may borrow from the array.
+    ///
+    /// ```
+    /// use bson::rawdoc;
+    ///
+    /// let doc = rawdoc! { "names": ["a", "b", 3] };
+    /// let mut names = doc.get_array("names")?.iter_as::<&str>();
+    /// assert_eq!(names.next().transpose()?, Some("a"));
+    /// assert_eq!(names.next().transpose()?, Some("b"));
+    /// assert!(names.next().unwrap().is_err());
+    /// assert!(names.next().is_none());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn iter_as<'a, T: Deserialize<'a>>(
+        &'a self,
+    ) -> impl Iterator<Item = crate::de::Result<T>> + 'a {
+        RawIter::new(&self.doc)
+            .map(|element| crate::de::from_raw(crate::de::RawDeserializer::from_element(element?)))
+    }
+
     /// Gets a reference to the value at the given index.
     pub fn get(&self, index: usize) -> Result<Option<RawBsonRef<'_>>> {
         self.into_iter().nth(index).transpose()
@@ -126,6 +163,10 @@ impl RawArray {
             })?;
         match f(bson) {
             Some(t) => Ok(t),
+            None if bson.element_type() == ElementType::Null => Err(ValueAccessError {
+                key: index.to_string(),
+                kind: ValueAccessErrorKind::UnexpectedNull,
+            }),
             None => Err(ValueAccessError {
                 key: index.to_string(),
                 kind: ValueAccessErrorKind::UnexpectedType {
@@ -304,7 +345,7 @@ impl<'de: 'a, 'a> Deserialize<'de> for &'a RawArray {
     }
 }
 
-impl Serialize for &RawArray {
+impl Serialize for RawArray {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
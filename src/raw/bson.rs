@@ -245,6 +245,15 @@ impl RawBson {
         }
     }
 
+    /// Gets the wrapped [`crate::Decimal128`] value or returns [`None`] if the wrapped value isn't
+    /// a BSON Decimal128.
+    pub fn as_decimal128(&self) -> Option<Decimal128> {
+        match self {
+            RawBson::Decimal128(d) => Some(*d),
+            _ => None,
+        }
+    }
+
     /// Returns `Some(())` if this value is null, otherwise returns [`None`].
     pub fn as_null(&self) -> Option<()> {
         match self {
@@ -121,6 +121,10 @@ impl<'a> RawBsonRef<'a> {
 
     /// Gets the [`RawArray`] that's referenced or returns [`None`] if the referenced value
     /// isn't a BSON array.
+    ///
+    /// This borrows the array's bytes without decoding them; its elements are only parsed when
+    /// accessed. To decode the whole array up front, convert it with
+    /// [`Bson::try_from`](crate::Bson::try_from) or [`RawArray::to_vec`].
     pub fn as_array(self) -> Option<&'a RawArray> {
         match self {
             RawBsonRef::Array(v) => Some(v),
@@ -130,6 +134,8 @@ impl<'a> RawBsonRef<'a> {
 
     /// Gets the [`RawDocument`] that's referenced or returns [`None`] if the referenced value
     /// isn't a BSON document.
+    ///
+    /// As with [`RawBsonRef::as_array`], the document is borrowed without being decoded.
     pub fn as_document(self) -> Option<&'a RawDocument> {
         match self {
             RawBsonRef::Document(v) => Some(v),
@@ -218,6 +224,15 @@ impl<'a> RawBsonRef<'a> {
         }
     }
 
+    /// Gets the [`crate::Decimal128`] that's referenced or returns [`None`] if the referenced
+    /// value isn't a BSON Decimal128.
+    pub fn as_decimal128(self) -> Option<Decimal128> {
+        match self {
+            RawBsonRef::Decimal128(d) => Some(d),
+            _ => None,
+        }
+    }
+
     /// Gets the null value that's referenced or returns [`None`] if the referenced value isn't a
     /// BSON null.
     pub fn as_null(self) -> Option<()> {
@@ -254,6 +269,50 @@ impl<'a> RawBsonRef<'a> {
         }
     }
 
+    /// Gets the referenced value as an `i64`, accepting a BSON Int32, Int64, or a Double with no
+    /// fractional part that lies within the range of `i64`. Returns [`None`] for any other value.
+    ///
+    /// ```
+    /// use bson::RawBsonRef;
+    ///
+    /// assert_eq!(RawBsonRef::Int32(5).as_i64_lenient(), Some(5));
+    /// assert_eq!(RawBsonRef::Double(5.0).as_i64_lenient(), Some(5));
+    /// assert_eq!(RawBsonRef::Double(5.5).as_i64_lenient(), None);
+    /// ```
+    pub fn as_i64_lenient(self) -> Option<i64> {
+        match self {
+            RawBsonRef::Int32(i) => Some(i as i64),
+            RawBsonRef::Int64(i) => Some(i),
+            RawBsonRef::Double(d)
+                if d.fract() == 0.0 && d >= i64::MIN as f64 && d < i64::MAX as f64 =>
+            {
+                Some(d as i64)
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the referenced value as an `f64`, accepting a BSON Double, Int32, or Int64. Returns
+    /// [`None`] for any other value.
+    ///
+    /// Int64 values with a magnitude greater than 2<sup>53</sup> may lose precision in the
+    /// conversion.
+    ///
+    /// ```
+    /// use bson::RawBsonRef;
+    ///
+    /// assert_eq!(RawBsonRef::Int64(5).as_f64_lenient(), Some(5.0));
+    /// assert_eq!(RawBsonRef::String("5").as_f64_lenient(), None);
+    /// ```
+    pub fn as_f64_lenient(self) -> Option<f64> {
+        match self {
+            RawBsonRef::Double(d) => Some(d),
+            RawBsonRef::Int32(i) => Some(i as f64),
+            RawBsonRef::Int64(i) => Some(i as f64),
+            _ => None,
+        }
+    }
+
     /// Convert this [`RawBsonRef`] to the equivalent [`RawBson`].
     pub fn to_raw_bson(self) -> RawBson {
         match self {
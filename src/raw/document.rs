@@ -9,13 +9,14 @@ use crate::{
     de::MIN_BSON_DOCUMENT_SIZE,
     raw::{error::ErrorKind, serde::OwnedOrBorrowedRawDocument, RAW_DOCUMENT_NEWTYPE},
     DateTime,
+    Decimal128,
     Timestamp,
 };
 
 use super::{
     error::{ValueAccessError, ValueAccessErrorKind, ValueAccessResult},
-    i32_from_slice,
     iter::Iter,
+    read_i32_le,
     try_to_str,
     Error,
     RawArray,
@@ -25,6 +26,7 @@ use super::{
     RawIter,
     RawRegexRef,
     Result,
+    ValidationOptions,
 };
 use crate::{oid::ObjectId, spec::ElementType, Document};
 
@@ -103,7 +105,7 @@ impl RawDocument {
             });
         }
 
-        let length = i32_from_slice(data)?;
+        let length = read_i32_le(data)?;
 
         if data.len() as i32 != length {
             return Err(Error {
@@ -181,6 +183,35 @@ impl RawDocument {
         Ok(None)
     }
 
+    /// Gets the encoded bytes of the value for the given key, excluding the element type byte and
+    /// the key name, or returns `None` if the key is not found. Together with the value's
+    /// [`ElementType`](crate::spec::ElementType), these bytes can be spliced into another document
+    /// without decoding the value.
+    ///
+    /// ```
+    /// # use bson::raw::Error;
+    /// use bson::rawdoc;
+    ///
+    /// let doc = rawdoc! {
+    ///     "i32": 5,
+    ///     "string": "hello",
+    /// };
+    ///
+    /// assert_eq!(doc.value_bytes("i32")?, Some(&5i32.to_le_bytes()[..]));
+    /// assert_eq!(doc.value_bytes("string")?, Some(&b"\x06\0\0\0hello\0"[..]));
+    /// assert!(doc.value_bytes("unknown")?.is_none());
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn value_bytes(&self, key: impl AsRef<str>) -> Result<Option<&[u8]>> {
+        for elem in RawIter::new(self) {
+            let elem = elem?;
+            if key.as_ref() == elem.key() {
+                return Ok(Some(elem.slice()));
+            }
+        }
+        Ok(None)
+    }
+
     /// Gets an iterator over the elements in the [`RawDocument`] that yields
     /// `Result<(&str, RawBson<'_>)>`.
     pub fn iter(&self) -> Iter<'_> {
@@ -201,6 +232,72 @@ impl RawDocument {
         RawIter::new(self)
     }
 
+    /// Gets an iterator over the keys in the [`RawDocument`].
+    ///
+    /// Values are skipped over using their encoded lengths without being decoded or validated,
+    /// which makes this cheaper than iterating over the full elements. As with
+    /// [`RawDocument::iter`], an error is yielded if the document is malformed, after which the
+    /// iterator ends.
+    ///
+    /// ```
+    /// # use bson::raw::Error;
+    /// use bson::rawdoc;
+    ///
+    /// let doc = rawdoc! { "a": 1, "b": { "c": true } };
+    /// let keys = doc.keys().collect::<Result<Vec<_>, Error>>()?;
+    /// assert_eq!(keys, vec!["a", "b"]);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn keys(&self) -> impl Iterator<Item = Result<&str>> + '_ {
+        self.iter_elements().map(|elem| elem.map(|elem| elem.key()))
+    }
+
+    /// Gets an iterator over the elements in the [`RawDocument`] whose keys start with `prefix`.
+    ///
+    /// Only the values of matching elements are decoded; other elements are skipped in the same
+    /// way as [`RawDocument::keys`].
+    ///
+    /// ```
+    /// # use bson::raw::Error;
+    /// use bson::rawdoc;
+    ///
+    /// let doc = rawdoc! { "attr_a": 1, "id": 2, "attr_b": 3 };
+    /// let attrs = doc
+    ///     .iter_with_prefix("attr_")
+    ///     .map(|elem| elem.map(|(key, value)| (key, value.as_i32())))
+    ///     .collect::<Result<Vec<_>, Error>>()?;
+    /// assert_eq!(attrs, vec![("attr_a", Some(1)), ("attr_b", Some(3))]);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn iter_with_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = Result<(&'a str, RawBsonRef<'a>)>> {
+        self.iter_elements().filter_map(move |elem| match elem {
+            Ok(elem) if elem.key().starts_with(prefix) => {
+                Some(elem.value().map(|value| (elem.key(), value)))
+            }
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+    }
+
+    /// Returns the number of elements in the [`RawDocument`], skipping over values in the same way
+    /// as [`RawDocument::keys`]. Returns an error if the document is malformed.
+    ///
+    /// ```
+    /// # use bson::raw::Error;
+    /// use bson::rawdoc;
+    ///
+    /// let doc = rawdoc! { "a": 1, "b": [1, 2, 3] };
+    /// assert_eq!(doc.len()?, 2);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn len(&self) -> Result<usize> {
+        self.iter_elements()
+            .try_fold(0, |count, elem| elem.map(|_| count + 1))
+    }
+
     fn get_with<'a, T>(
         &'a self,
         key: impl AsRef<str>,
@@ -221,6 +318,10 @@ impl RawDocument {
             })?;
         match f(bson) {
             Some(t) => Ok(t),
+            None if bson.element_type() == ElementType::Null => Err(ValueAccessError {
+                key: key.to_string(),
+                kind: ValueAccessErrorKind::UnexpectedNull,
+            }),
             None => Err(ValueAccessError {
                 key: key.to_string(),
                 kind: ValueAccessErrorKind::UnexpectedType {
@@ -231,6 +332,22 @@ impl RawDocument {
         }
     }
 
+    fn get_opt_with<'a, T>(
+        &'a self,
+        key: impl AsRef<str>,
+        expected_type: ElementType,
+        f: impl FnOnce(RawBsonRef<'a>) -> Option<T>,
+    ) -> ValueAccessResult<Option<T>> {
+        match self.get_with(key, expected_type, f) {
+            Ok(t) => Ok(Some(t)),
+            Err(ValueAccessError {
+                kind: ValueAccessErrorKind::NotPresent,
+                ..
+            }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Gets a reference to the BSON double value corresponding to a given key or returns an error
     /// if the key corresponds to a value which isn't a double.
     ///
@@ -455,6 +572,27 @@ impl RawDocument {
         self.get_with(key, ElementType::Timestamp, RawBsonRef::as_timestamp)
     }
 
+    /// Gets the BSON Decimal128 value corresponding to a given key or returns an error if the key
+    /// corresponds to a value which isn't a Decimal128.
+    ///
+    /// ```
+    /// use bson::{rawdoc, raw::ValueAccessErrorKind, Decimal128};
+    ///
+    /// let price: Decimal128 = "19.99".parse()?;
+    /// let doc = rawdoc! {
+    ///     "bool": true,
+    ///     "price": price,
+    /// };
+    ///
+    /// assert_eq!(doc.get_decimal128("price")?, price);
+    /// assert!(matches!(doc.get_decimal128("bool").unwrap_err().kind, ValueAccessErrorKind::UnexpectedType { .. }));
+    /// assert!(matches!(doc.get_decimal128("unknown").unwrap_err().kind, ValueAccessErrorKind::NotPresent));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_decimal128(&self, key: impl AsRef<str>) -> ValueAccessResult<Decimal128> {
+        self.get_with(key, ElementType::Decimal128, RawBsonRef::as_decimal128)
+    }
+
     /// Gets a reference to the BSON int32 value corresponding to a given key or returns an error if
     /// the key corresponds to a value which isn't a 32-bit integer.
     ///
@@ -497,6 +635,99 @@ impl RawDocument {
         self.get_with(key, ElementType::Int64, RawBsonRef::as_i64)
     }
 
+    /// Like [`RawDocument::get_f64`], but returns `Ok(None)` if the key is not present.
+    pub fn get_f64_opt(&self, key: impl AsRef<str>) -> ValueAccessResult<Option<f64>> {
+        self.get_opt_with(key, ElementType::Double, RawBsonRef::as_f64)
+    }
+
+    /// Like [`RawDocument::get_str`], but returns `Ok(None)` if the key is not present.
+    pub fn get_str_opt(&self, key: impl AsRef<str>) -> ValueAccessResult<Option<&'_ str>> {
+        self.get_opt_with(key, ElementType::String, RawBsonRef::as_str)
+    }
+
+    /// Like [`RawDocument::get_document`], but returns `Ok(None)` if the key is not present
+    /// rather than an error. An error is still returned if the key corresponds to a value which
+    /// isn't a document.
+    ///
+    /// ```
+    /// use bson::{rawdoc, raw::ValueAccessErrorKind};
+    ///
+    /// let doc = rawdoc! {
+    ///     "doc": { "key": "value" },
+    ///     "bool": true,
+    /// };
+    ///
+    /// assert_eq!(doc.get_document_opt("doc")?.unwrap().get_str("key")?, "value");
+    /// assert!(doc.get_document_opt("unknown")?.is_none());
+    /// assert!(matches!(doc.get_document_opt("bool").unwrap_err().kind, ValueAccessErrorKind::UnexpectedType { .. }));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_document_opt(
+        &self,
+        key: impl AsRef<str>,
+    ) -> ValueAccessResult<Option<&'_ RawDocument>> {
+        self.get_opt_with(key, ElementType::EmbeddedDocument, RawBsonRef::as_document)
+    }
+
+    /// Like [`RawDocument::get_array`], but returns `Ok(None)` if the key is not present.
+    pub fn get_array_opt(&self, key: impl AsRef<str>) -> ValueAccessResult<Option<&'_ RawArray>> {
+        self.get_opt_with(key, ElementType::Array, RawBsonRef::as_array)
+    }
+
+    /// Like [`RawDocument::get_binary`], but returns `Ok(None)` if the key is not present.
+    pub fn get_binary_opt(
+        &self,
+        key: impl AsRef<str>,
+    ) -> ValueAccessResult<Option<RawBinaryRef<'_>>> {
+        self.get_opt_with(key, ElementType::Binary, RawBsonRef::as_binary)
+    }
+
+    /// Like [`RawDocument::get_object_id`], but returns `Ok(None)` if the key is not present.
+    pub fn get_object_id_opt(&self, key: impl AsRef<str>) -> ValueAccessResult<Option<ObjectId>> {
+        self.get_opt_with(key, ElementType::ObjectId, RawBsonRef::as_object_id)
+    }
+
+    /// Like [`RawDocument::get_bool`], but returns `Ok(None)` if the key is not present.
+    pub fn get_bool_opt(&self, key: impl AsRef<str>) -> ValueAccessResult<Option<bool>> {
+        self.get_opt_with(key, ElementType::Boolean, RawBsonRef::as_bool)
+    }
+
+    /// Like [`RawDocument::get_datetime`], but returns `Ok(None)` if the key is not present.
+    pub fn get_datetime_opt(&self, key: impl AsRef<str>) -> ValueAccessResult<Option<DateTime>> {
+        self.get_opt_with(key, ElementType::DateTime, RawBsonRef::as_datetime)
+    }
+
+    /// Like [`RawDocument::get_regex`], but returns `Ok(None)` if the key is not present.
+    pub fn get_regex_opt(
+        &self,
+        key: impl AsRef<str>,
+    ) -> ValueAccessResult<Option<RawRegexRef<'_>>> {
+        self.get_opt_with(key, ElementType::RegularExpression, RawBsonRef::as_regex)
+    }
+
+    /// Like [`RawDocument::get_timestamp`], but returns `Ok(None)` if the key is not present.
+    pub fn get_timestamp_opt(&self, key: impl AsRef<str>) -> ValueAccessResult<Option<Timestamp>> {
+        self.get_opt_with(key, ElementType::Timestamp, RawBsonRef::as_timestamp)
+    }
+
+    /// Like [`RawDocument::get_decimal128`], but returns `Ok(None)` if the key is not present.
+    pub fn get_decimal128_opt(
+        &self,
+        key: impl AsRef<str>,
+    ) -> ValueAccessResult<Option<Decimal128>> {
+        self.get_opt_with(key, ElementType::Decimal128, RawBsonRef::as_decimal128)
+    }
+
+    /// Like [`RawDocument::get_i32`], but returns `Ok(None)` if the key is not present.
+    pub fn get_i32_opt(&self, key: impl AsRef<str>) -> ValueAccessResult<Option<i32>> {
+        self.get_opt_with(key, ElementType::Int32, RawBsonRef::as_i32)
+    }
+
+    /// Like [`RawDocument::get_i64`], but returns `Ok(None)` if the key is not present.
+    pub fn get_i64_opt(&self, key: impl AsRef<str>) -> ValueAccessResult<Option<i64>> {
+        self.get_opt_with(key, ElementType::Int64, RawBsonRef::as_i64)
+    }
+
     /// Return a reference to the contained data as a `&[u8]`
     ///
     /// ```
@@ -515,6 +746,29 @@ impl RawDocument {
         self.as_bytes().len() == MIN_BSON_DOCUMENT_SIZE as usize
     }
 
+    /// Fully validates the contents of this document, returning an error if any malformed BSON is
+    /// encountered or if any of the limits in the provided [`ValidationOptions`] are exceeded.
+    ///
+    /// Unlike [`RawDocument::from_bytes`], which only checks the outer length and null terminator,
+    /// this walks every element (including those in nested documents and arrays) and verifies all
+    /// length prefixes, null terminators, UTF-8 strings, and binary subtype encodings. This is
+    /// useful for checking untrusted input once up front, after which accessing elements will not
+    /// encounter malformed data.
+    ///
+    /// ```
+    /// use bson::{rawdoc, raw::ValidationOptions};
+    ///
+    /// let doc = rawdoc! { "a": { "b": { "c": 1 } } };
+    /// doc.validate(ValidationOptions::default())?;
+    ///
+    /// let options = ValidationOptions::builder().max_depth(2).build();
+    /// assert!(doc.validate(options).is_err());
+    /// # Ok::<(), bson::raw::Error>(())
+    /// ```
+    pub fn validate(&self, options: ValidationOptions) -> Result<()> {
+        super::validation::validate(self, &options)
+    }
+
     pub(crate) fn cstring_bytes_at(&self, start_at: usize) -> Result<&[u8]> {
         let buf = &self.as_bytes()[start_at..];
 
@@ -548,7 +802,7 @@ impl<'de: 'a, 'a> Deserialize<'de> for &'a RawDocument {
     }
 }
 
-impl Serialize for &RawDocument {
+impl Serialize for RawDocument {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
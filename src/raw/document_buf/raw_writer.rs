@@ -18,7 +18,19 @@ impl<'a> RawWriter<'a> {
         self.data[original_len - 1] = value.element_type() as u8;
 
         write_cstring(self.data, key)?;
+        self.write_value(value)?;
 
+        // append trailing null byte
+        self.data.push(0);
+        // update length
+        let new_len = (self.data.len() as i32).to_le_bytes();
+        self.data[0..4].copy_from_slice(&new_len);
+
+        Ok(())
+    }
+
+    /// Write the encoded form of `value` to the end of the buffer, without an element type or key.
+    pub(super) fn write_value(&mut self, value: RawBsonRef) -> crate::ser::Result<()> {
         match value {
             RawBsonRef::Int32(i) => {
                 self.data.extend(i.to_le_bytes());
@@ -84,13 +96,6 @@ impl<'a> RawWriter<'a> {
             }
             RawBsonRef::Null | RawBsonRef::Undefined | RawBsonRef::MinKey | RawBsonRef::MaxKey => {}
         }
-
-        // append trailing null byte
-        self.data.push(0);
-        // update length
-        let new_len = (self.data.len() as i32).to_le_bytes();
-        self.data[0..4].copy_from_slice(&new_len);
-
         Ok(())
     }
 }
@@ -45,6 +45,9 @@ pub enum ErrorKind {
 
     /// Improper UTF-8 bytes were found when proper UTF-8 was expected.
     Utf8EncodingError(Utf8Error),
+
+    /// A key was encountered that already exists in the document.
+    DuplicateKey,
 }
 
 impl std::fmt::Display for Error {
@@ -61,6 +64,7 @@ impl std::fmt::Display for Error {
                 write!(f, "{}malformed value: {:?}", prefix, message)
             }
             ErrorKind::Utf8EncodingError(e) => write!(f, "{}utf-8 encoding error: {}", prefix, e),
+            ErrorKind::DuplicateKey => write!(f, "{}duplicate key", prefix),
         }
     }
 }
@@ -115,6 +119,9 @@ pub enum ValueAccessErrorKind {
 
     /// An error was encountered attempting to decode the document.
     InvalidBson(super::Error),
+
+    /// Found the specified key, but its value is null rather than the expected type.
+    UnexpectedNull,
 }
 
 impl std::fmt::Display for ValueAccessError {
@@ -131,6 +138,7 @@ impl std::fmt::Display for ValueAccessError {
                 write!(f, "{}: {}", prefix, error)
             }
             ValueAccessErrorKind::NotPresent => write!(f, "{}value not present", prefix),
+            ValueAccessErrorKind::UnexpectedNull => write!(f, "{}value is null", prefix),
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::{borrow::Cow, convert::TryInto};
 
 use crate::{
     de::{MIN_BSON_DOCUMENT_SIZE, MIN_CODE_WITH_SCOPE_SIZE},
@@ -21,9 +21,9 @@ use super::{
     bool_from_slice,
     checked_add,
     error::try_with_key,
-    f64_from_slice,
-    i32_from_slice,
-    i64_from_slice,
+    read_f64_le,
+    read_i32_le,
+    read_i64_le,
     read_len,
     read_lenencode,
     read_lenencode_bytes,
@@ -79,6 +79,11 @@ impl<'a> RawIter<'a> {
         }
     }
 
+    /// The document being iterated and the offset within it of the next element.
+    pub(crate) fn position(&self) -> (&'a RawDocument, usize) {
+        (self.doc, self.offset)
+    }
+
     fn verify_enough_bytes(&self, start: usize, num_bytes: usize) -> Result<()> {
         let end = checked_add(start, num_bytes)?;
         if self.doc.as_bytes().get(start..end).is_none() {
@@ -93,7 +98,7 @@ impl<'a> RawIter<'a> {
 
     fn next_document_len(&self, starting_at: usize) -> Result<usize> {
         self.verify_enough_bytes(starting_at, MIN_BSON_DOCUMENT_SIZE as usize)?;
-        let size = i32_from_slice(&self.doc.as_bytes()[starting_at..])? as usize;
+        let size = read_i32_le(&self.doc.as_bytes()[starting_at..])? as usize;
 
         if size < MIN_BSON_DOCUMENT_SIZE as usize {
             return Err(Error::malformed(format!(
@@ -168,6 +173,15 @@ impl<'a> RawElement<'a> {
         self.kind
     }
 
+    /// Returns the complete encoded bytes of this element: the element type byte, the
+    /// null-terminated key, and the value. These can be appended directly to another document's
+    /// element list without re-encoding.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        // element type byte + key + null terminator
+        let header_len = 1 + self.key.len() + 1;
+        self.slice_bounds(self.start_at - header_len, header_len + self.size)
+    }
+
     pub fn value(&self) -> Result<RawBsonRef<'a>> {
         Ok(match self.kind {
             ElementType::Null => RawBsonRef::Null,
@@ -175,9 +189,9 @@ impl<'a> RawElement<'a> {
             ElementType::MinKey => RawBsonRef::MinKey,
             ElementType::MaxKey => RawBsonRef::MaxKey,
             ElementType::ObjectId => RawBsonRef::ObjectId(self.get_oid_at(self.start_at)?),
-            ElementType::Int32 => RawBsonRef::Int32(i32_from_slice(self.slice())?),
-            ElementType::Int64 => RawBsonRef::Int64(i64_from_slice(self.slice())?),
-            ElementType::Double => RawBsonRef::Double(f64_from_slice(self.slice())?),
+            ElementType::Int32 => RawBsonRef::Int32(read_i32_le(self.slice())?),
+            ElementType::Int64 => RawBsonRef::Int64(read_i64_le(self.slice())?),
+            ElementType::Double => RawBsonRef::Double(read_f64_le(self.slice())?),
             ElementType::String => RawBsonRef::String(self.read_str()?),
             ElementType::EmbeddedDocument => {
                 RawBsonRef::Document(RawDocument::from_bytes(self.slice())?)
@@ -189,7 +203,7 @@ impl<'a> RawElement<'a> {
                 bool_from_slice(self.slice()).map_err(|e| self.malformed_error(e))?,
             ),
             ElementType::DateTime => {
-                RawBsonRef::DateTime(DateTime::from_millis(i64_from_slice(self.slice())?))
+                RawBsonRef::DateTime(DateTime::from_millis(read_i64_le(self.slice())?))
             }
             ElementType::Decimal128 => RawBsonRef::Decimal128(Decimal128::from_bytes(
                 self.slice()
@@ -235,8 +249,7 @@ impl<'a> RawElement<'a> {
                                 "old binary subtype has no inner declared length",
                             ));
                         }
-                        let oldlength =
-                            i32_from_slice(&self.doc.as_bytes()[data_start..])? as usize;
+                        let oldlength = read_i32_le(&self.doc.as_bytes()[data_start..])? as usize;
                         if checked_add(oldlength, 4)? != len {
                             return Err(self.malformed_error(
                                 "old binary subtype has wrong inner declared length",
@@ -268,7 +281,7 @@ impl<'a> RawElement<'a> {
 
     pub(crate) fn value_utf8_lossy(&self) -> Result<Option<Utf8LossyBson<'a>>> {
         Ok(Some(match self.kind {
-            ElementType::String => Utf8LossyBson::String(self.read_utf8_lossy()),
+            ElementType::String => Utf8LossyBson::String(String::from_utf8_lossy(self.str_bytes())),
             ElementType::JavaScriptCode => Utf8LossyBson::JavaScriptCode(self.read_utf8_lossy()),
             ElementType::JavaScriptCodeWithScope => {
                 if self.size < MIN_CODE_WITH_SCOPE_SIZE as usize {
@@ -311,6 +324,11 @@ impl<'a> RawElement<'a> {
         Error::malformed(e).with_key(self.key)
     }
 
+    /// The document containing this element and the offset within it of the element's value.
+    pub(crate) fn value_position(&self) -> (&'a RawDocument, usize) {
+        (self.doc, self.start_at)
+    }
+
     pub(crate) fn slice(&self) -> &'a [u8] {
         self.slice_bounds(self.start_at, self.size)
     }
@@ -342,7 +360,7 @@ impl<'a> RawElement<'a> {
 
 impl RawIter<'_> {
     fn get_next_length_at(&self, start_at: usize) -> Result<usize> {
-        let len = i32_from_slice(&self.doc.as_bytes()[start_at..])?;
+        let len = read_i32_le(&self.doc.as_bytes()[start_at..])?;
         if len < 0 {
             Err(Error::malformed("lengths can't be negative"))
         } else {
@@ -443,7 +461,8 @@ impl<'a> Iterator for RawIter<'a> {
 }
 
 pub(crate) enum Utf8LossyBson<'a> {
-    String(String),
+    /// Borrowed when the string is already valid UTF-8.
+    String(Cow<'a, str>),
     JavaScriptCode(String),
     JavaScriptCodeWithScope(Utf8LossyJavaScriptCodeWithScope<'a>),
     Symbol(String),
@@ -80,6 +80,25 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 //!
+//! ### Scanning concatenated documents
+//!
+//! [`RawDocument::from_bytes`] never copies its input, so a [`RawDocumentSequence`] can be used
+//! to lazily iterate over a buffer of concatenated documents (e.g. a memory-mapped `mongodump`
+//! `.bson` file), yielding each one as a `&RawDocument` that borrows from the buffer.
+//!
+//! ```rust
+//! use bson::{doc, raw::RawDocumentSequence};
+//!
+//! let mut bytes = bson::to_vec(&doc! { "name": "a" }).unwrap();
+//! bytes.extend(bson::to_vec(&doc! { "name": "b" }).unwrap());
+//!
+//! let names = RawDocumentSequence::new(&bytes)
+//!     .map(|doc| Ok(doc?.get_str("name")?))
+//!     .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+//! assert_eq!(names, ["a", "b"]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
 //! ### Iteration
 //!
 //! [`RawDocument`] implements [`IntoIterator`], which can also be
@@ -120,9 +139,11 @@ mod document;
 mod document_buf;
 mod error;
 mod iter;
+mod sequence;
 pub(crate) mod serde;
 #[cfg(test)]
 mod test;
+mod validation;
 
 use std::convert::{TryFrom, TryInto};
 
@@ -143,6 +164,8 @@ pub use self::{
     document_buf::RawDocumentBuf,
     error::{Error, ErrorKind, Result, ValueAccessError, ValueAccessErrorKind, ValueAccessResult},
     iter::{RawElement, RawIter},
+    sequence::RawDocumentSequence,
+    validation::{ValidationOptions, ValidationOptionsBuilder},
 };
 
 pub(crate) use self::iter::{Utf8LossyBson, Utf8LossyJavaScriptCodeWithScope};
@@ -156,9 +179,17 @@ pub(crate) const RAW_ARRAY_NEWTYPE: &str = "$__private__bson_RawArray";
 /// Special newtype name indicating that the type being (de)serialized is a raw BSON value.
 pub(crate) const RAW_BSON_NEWTYPE: &str = "$__private__bson_RawBson";
 
-/// Given a u8 slice, return an i32 calculated from the first four bytes in
-/// little endian order.
-fn f64_from_slice(val: &[u8]) -> Result<f64> {
+/// Reads an `f64` from the first eight bytes of `val` in little endian order, as BSON doubles are
+/// encoded. Returns an error if `val` contains fewer than eight bytes; any further bytes are
+/// ignored.
+///
+/// ```
+/// # use bson::raw::Error;
+/// assert_eq!(bson::raw::read_f64_le(&1.5f64.to_le_bytes())?, 1.5);
+/// assert!(bson::raw::read_f64_le(&[0; 7]).is_err());
+/// # Ok::<(), Error>(())
+/// ```
+pub fn read_f64_le(val: &[u8]) -> Result<f64> {
     let arr = val
         .get(0..8)
         .and_then(|s| s.try_into().ok())
@@ -171,9 +202,17 @@ fn f64_from_slice(val: &[u8]) -> Result<f64> {
     Ok(f64::from_le_bytes(arr))
 }
 
-/// Given a u8 slice, return an i32 calculated from the first four bytes in
-/// little endian order.
-fn i32_from_slice(val: &[u8]) -> Result<i32> {
+/// Reads an `i32` from the first four bytes of `val` in little endian order, as BSON lengths and
+/// 32-bit integers are encoded. Returns an error if `val` contains fewer than four bytes; any
+/// further bytes are ignored.
+///
+/// ```
+/// # use bson::raw::Error;
+/// assert_eq!(bson::raw::read_i32_le(&[0x10, 0, 0, 0, 0xFF])?, 16);
+/// assert!(bson::raw::read_i32_le(&[1, 2]).is_err());
+/// # Ok::<(), Error>(())
+/// ```
+pub fn read_i32_le(val: &[u8]) -> Result<i32> {
     let arr: [u8; 4] = val
         .get(0..4)
         .and_then(|s| s.try_into().ok())
@@ -186,9 +225,17 @@ fn i32_from_slice(val: &[u8]) -> Result<i32> {
     Ok(i32::from_le_bytes(arr))
 }
 
-/// Given an u8 slice, return an i64 calculated from the first 8 bytes in
-/// little endian order.
-fn i64_from_slice(val: &[u8]) -> Result<i64> {
+/// Reads an `i64` from the first eight bytes of `val` in little endian order, as BSON 64-bit
+/// integers and datetimes are encoded. Returns an error if `val` contains fewer than eight bytes;
+/// any further bytes are ignored.
+///
+/// ```
+/// # use bson::raw::Error;
+/// assert_eq!(bson::raw::read_i64_le(&(-2i64).to_le_bytes())?, -2);
+/// assert!(bson::raw::read_i64_le(&[0; 4]).is_err());
+/// # Ok::<(), Error>(())
+/// ```
+pub fn read_i64_le(val: &[u8]) -> Result<i64> {
     let arr = val
         .get(0..8)
         .and_then(|s| s.try_into().ok())
@@ -234,7 +281,7 @@ fn read_len(buf: &[u8]) -> Result<usize> {
         )));
     }
 
-    let length = i32_from_slice(&buf[..4])?;
+    let length = read_i32_le(&buf[..4])?;
     let end = checked_add(usize_try_from_i32(length)?, 4)?;
 
     if end < MIN_BSON_STRING_SIZE as usize {
@@ -0,0 +1,84 @@
+use std::convert::TryFrom;
+
+use super::{read_i32_le, Error, RawDocument, Result};
+
+/// A lazy, zero-copy iterator over a sequence of concatenated BSON documents, such as the
+/// contents of a `mongodump` `.bson` file.
+///
+/// Each document yielded borrows directly from the underlying bytes, so a [`RawDocumentSequence`]
+/// can be used to scan a memory-mapped file without copying or decoding documents that are not
+/// inspected. As with [`RawDocument::from_bytes`], only the length prefix and null terminator of
+/// each document are checked up front; the elements themselves are validated when accessed.
+///
+/// If a document's length prefix is malformed, the iterator yields an error and then ends, since
+/// the position of any subsequent documents cannot be determined.
+///
+/// ```
+/// # use bson::raw::Error;
+/// use bson::{doc, raw::RawDocumentSequence};
+///
+/// // In practice, these bytes might come from a memory-mapped file, e.g. via the `memmap2`
+/// // crate: `let bytes: &[u8] = &unsafe { memmap2::Mmap::map(&file)? };`
+/// let mut bytes = bson::to_vec(&doc! { "x": 1 }).unwrap();
+/// bytes.extend(bson::to_vec(&doc! { "x": 2 }).unwrap());
+///
+/// let mut total = 0;
+/// for doc in RawDocumentSequence::new(&bytes) {
+///     total += doc?.get_i32("x")?;
+/// }
+/// assert_eq!(total, 3);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct RawDocumentSequence<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> RawDocumentSequence<'a> {
+    /// Constructs a new [`RawDocumentSequence`] over the given bytes. No validation is performed
+    /// until the sequence is iterated.
+    pub fn new<D: AsRef<[u8]> + ?Sized>(bytes: &'a D) -> Self {
+        Self {
+            bytes: bytes.as_ref(),
+        }
+    }
+
+    /// Returns the bytes that have not yet been yielded by this iterator.
+    pub fn remaining_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    fn next_document(&mut self) -> Result<&'a RawDocument> {
+        if self.bytes.len() < 4 {
+            return Err(Error::malformed("document length truncated"));
+        }
+        let length = read_i32_le(self.bytes)?;
+        let length = usize::try_from(length)
+            .ok()
+            .filter(|len| *len <= self.bytes.len())
+            .ok_or_else(|| Error::malformed(format!("invalid document length: {}", length)))?;
+
+        let (doc, rest) = self.bytes.split_at(length);
+        let doc = RawDocument::from_bytes(doc)?;
+        self.bytes = rest;
+        Ok(doc)
+    }
+}
+
+impl<'a> Iterator for RawDocumentSequence<'a> {
+    type Item = Result<&'a RawDocument>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let result = self.next_document();
+        if result.is_err() {
+            self.bytes = &[];
+        }
+        Some(result)
+    }
+}
+
+impl std::iter::FusedIterator for RawDocumentSequence<'_> {}
@@ -435,3 +435,36 @@ fn array_buf() {
 
     assert_eq!(arr_buf.as_ref(), arr.as_array().unwrap());
 }
+
+#[test]
+fn extend_from_raw_document() {
+    let expected = doc! {
+        "a": 1_i32,
+        "b": "two",
+        "c": { "d": true },
+    };
+    append_test(expected, |doc| {
+        doc.append("a", 1_i32);
+        doc.extend_from_raw_document(&RawDocumentBuf::new());
+        doc.extend_from_raw_document(&rawdoc! { "b": "two", "c": { "d": true } });
+    });
+
+    let mut doc = RawDocumentBuf::new();
+    doc.extend_from_raw_document(&rawdoc! { "a": 1_i32 });
+    doc.append("b", 2_i32);
+    assert_eq!(doc, rawdoc! { "a": 1_i32, "b": 2_i32 });
+}
+
+#[test]
+fn extend_from_raw_document_unique() {
+    let mut doc = rawdoc! { "a": 1_i32 };
+    doc.extend_from_raw_document_unique(&rawdoc! { "b": 2_i32 })
+        .unwrap();
+
+    let err = doc
+        .extend_from_raw_document_unique(&rawdoc! { "c": 3_i32, "b": 4_i32 })
+        .unwrap_err();
+    assert_eq!(err.kind, crate::raw::ErrorKind::DuplicateKey);
+    assert_eq!(err.key(), Some("b"));
+    assert_eq!(doc, rawdoc! { "a": 1_i32, "b": 2_i32 });
+}
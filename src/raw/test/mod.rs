@@ -6,7 +6,7 @@ use crate::{
     doc,
     oid::ObjectId,
     raw::error::ValueAccessErrorKind,
-    spec::BinarySubtype,
+    spec::{BinarySubtype, ElementType},
     Binary,
     Bson,
     DateTime,
@@ -381,6 +381,428 @@ fn int64() {
         .expect("was not int64");
     assert_eq!(int64, 46i64);
 }
+
+#[test]
+fn decimal128() {
+    let decimal: Decimal128 = "1.25".parse().unwrap();
+    let rawdoc = rawdoc! { "d": decimal, "i": 1 };
+    assert_eq!(rawdoc.get_decimal128("d").unwrap(), decimal);
+    assert_eq!(rawdoc.get_decimal128_opt("missing").unwrap(), None);
+    assert!(matches!(
+        rawdoc.get_decimal128("i").unwrap_err().kind,
+        ValueAccessErrorKind::UnexpectedType {
+            expected: ElementType::Decimal128,
+            actual: ElementType::Int32,
+            ..
+        }
+    ));
+    assert_eq!(RawBson::Decimal128(decimal).as_decimal128(), Some(decimal));
+    assert_eq!(RawBson::Int32(1).as_decimal128(), None);
+}
+
+#[test]
+fn iter_with_prefix() {
+    let rawdoc = rawdoc! {
+        "attr_a": 1,
+        "id": 2,
+        "attr_b": { "x": true },
+        "attr": 4,
+    };
+    let matched = rawdoc
+        .iter_with_prefix("attr_")
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        matched,
+        vec![
+            ("attr_a", RawBsonRef::Int32(1)),
+            ("attr_b", RawBsonRef::Document(&rawdoc! { "x": true })),
+        ]
+    );
+    assert_eq!(rawdoc.iter_with_prefix("").count(), 4);
+    assert_eq!(rawdoc.iter_with_prefix("missing").count(), 0);
+
+    // values of non-matching elements are not decoded
+    let mut bytes = rawdoc! { "s": "x", "attr_a": 1 }.into_bytes();
+    bytes[11] = 0xff;
+    let doc = RawDocument::from_bytes(&bytes).unwrap();
+    assert!(doc.get("s").is_err());
+    assert_eq!(
+        doc.iter_with_prefix("attr_")
+            .collect::<Result<Vec<_>>>()
+            .unwrap(),
+        vec![("attr_a", RawBsonRef::Int32(1))]
+    );
+    assert!(doc.iter_with_prefix("s").next().unwrap().is_err());
+}
+
+#[test]
+fn serialize_borrowed() {
+    use ::serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Wrapper<'a> {
+        doc: &'a RawDocument,
+        arr: &'a RawArray,
+    }
+
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Vec<u8> {
+        crate::to_vec(value).unwrap()
+    }
+
+    let rawdoc = rawdoc! { "a": 1, "b": [true, "x"] };
+    assert_eq!(encode::<RawDocument>(&rawdoc), rawdoc.as_bytes());
+
+    let wrapper = Wrapper {
+        doc: &rawdoc,
+        arr: rawdoc.get_array("b").unwrap(),
+    };
+    let expected = rawdoc! { "doc": rawdoc.clone(), "arr": [true, "x"] };
+    assert_eq!(encode(&wrapper), expected.as_bytes());
+    assert_eq!(
+        crate::to_document(&wrapper).unwrap(),
+        doc! { "doc": { "a": 1, "b": [true, "x"] }, "arr": [true, "x"] }
+    );
+}
+
+#[test]
+fn from_document_bytes() {
+    let document = doc! {
+        "double": 1.5,
+        "string": "hello",
+        "document": { "nested": { "deeper": [1, { "x": null }] } },
+        "array": [1_i64, "two", [3.0], { "four": 4 }],
+        "binary": Binary { subtype: BinarySubtype::BinaryOld, bytes: vec![1, 2, 3] },
+        "oid": ObjectId::new(),
+        "bool": true,
+        "datetime": DateTime::from_millis(1_000),
+        "null": null,
+        "regex": Regex::new("^a", "i"),
+        "code": Bson::JavaScriptCode("f()".to_string()),
+        "code_w_scope": crate::JavaScriptCodeWithScope::new("g()", doc! { "y": 1 }),
+        "int32": 32,
+        "timestamp": Timestamp { time: 1, increment: 2 },
+        "int64": 64_i64,
+        "undefined": Bson::Undefined,
+        "symbol": Bson::Symbol("sym".to_string()),
+        "decimal": "1.23".parse::<Decimal128>().unwrap(),
+        "max": Bson::MaxKey,
+        "min": Bson::MinKey,
+    };
+
+    let direct = RawDocumentBuf::from_document_bytes(&document).unwrap();
+    assert_eq!(direct, RawDocumentBuf::from_document(&document).unwrap());
+    assert_eq!(direct.to_document().unwrap(), document);
+    assert_eq!(
+        RawDocumentBuf::from_document_bytes(&doc! {}).unwrap(),
+        RawDocumentBuf::new()
+    );
+
+    let err = RawDocumentBuf::from_document_bytes(&doc! { "a": { "b\0": 1 } }).unwrap_err();
+    assert_eq!(err.key(), Some("b\0"));
+}
+
+#[test]
+fn null_value_access() {
+    let doc = rawdoc! { "null": null, "int": 1, "array": [null] };
+
+    let err = doc.get_str("null").unwrap_err();
+    assert_eq!(err.kind, ValueAccessErrorKind::UnexpectedNull);
+    assert_eq!(err.key(), "null");
+    assert!(matches!(
+        doc.get_str("int").unwrap_err().kind,
+        ValueAccessErrorKind::UnexpectedType { .. }
+    ));
+    assert_eq!(
+        doc.get_i32_opt("null").unwrap_err().kind,
+        ValueAccessErrorKind::UnexpectedNull
+    );
+    assert_eq!(
+        doc.get_array("array").unwrap().get_i32(0).unwrap_err().kind,
+        ValueAccessErrorKind::UnexpectedNull
+    );
+}
+
+#[test]
+fn value_bytes() {
+    let source = rawdoc! {
+        "a": 1,
+        "b": { "c": [1, "two", 3.0] },
+        "d": "text",
+    };
+
+    // Splice each value into a new document using its element type and value bytes.
+    let mut bytes = Vec::new();
+    for key in ["b", "d"] {
+        let element_type = source.get(key).unwrap().unwrap().element_type();
+        bytes.push(element_type as u8);
+        bytes.extend_from_slice(key.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(source.value_bytes(key).unwrap().unwrap());
+    }
+    bytes.push(0);
+    let len = (bytes.len() + 4) as i32;
+    let mut spliced = len.to_le_bytes().to_vec();
+    spliced.extend(bytes);
+
+    let spliced = RawDocumentBuf::from_bytes(spliced).unwrap();
+    assert_eq!(
+        spliced,
+        rawdoc! {
+            "b": { "c": [1, "two", 3.0] },
+            "d": "text",
+        }
+    );
+    assert_eq!(
+        source.value_bytes("a").unwrap(),
+        Some(&1i32.to_le_bytes()[..])
+    );
+    assert_eq!(source.value_bytes("missing").unwrap(), None);
+}
+
+#[test]
+fn document_sequence() {
+    let first = rawdoc! { "x": 1 };
+    let second = rawdoc! { "y": { "z": "two" } };
+    let mut bytes = first.as_bytes().to_vec();
+    bytes.extend_from_slice(second.as_bytes());
+
+    let docs = RawDocumentSequence::new(&bytes)
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(docs, vec![first.as_ref(), second.as_ref()]);
+    // The yielded documents borrow from the input rather than copying it.
+    assert_eq!(docs[0].as_bytes().as_ptr(), bytes.as_ptr());
+    assert!(RawDocumentSequence::new(&[]).next().is_none());
+
+    // A truncated trailing document yields an error, after which iteration stops.
+    let mut seq = RawDocumentSequence::new(&bytes[..bytes.len() - 1]);
+    assert_eq!(seq.next().unwrap().unwrap(), first.as_ref());
+    assert!(seq.next().unwrap().is_err());
+    assert!(seq.next().is_none());
+
+    let mut seq = RawDocumentSequence::new(&[1, 2]);
+    assert!(seq.next().unwrap().is_err());
+    assert!(seq.next().is_none());
+}
+
+#[test]
+fn array_to_vec_and_iter_as() {
+    #[derive(::serde::Deserialize, Debug, PartialEq)]
+    struct Item {
+        name: String,
+        qty: i32,
+    }
+
+    let doc = rawdoc! {
+        "items": [{ "name": "a", "qty": 1 }, { "name": "b", "qty": 2 }],
+        "ids": [ObjectId::from_bytes([1; 12]), ObjectId::from_bytes([2; 12])],
+        "mixed": [1, "two", 3],
+        "empty": [],
+    };
+
+    let items: Vec<Item> = doc.get_array("items").unwrap().to_vec().unwrap();
+    assert_eq!(
+        items,
+        vec![
+            Item {
+                name: "a".to_string(),
+                qty: 1
+            },
+            Item {
+                name: "b".to_string(),
+                qty: 2
+            },
+        ]
+    );
+
+    let ids: Vec<ObjectId> = doc.get_array("ids").unwrap().to_vec().unwrap();
+    assert_eq!(
+        ids,
+        vec![ObjectId::from_bytes([1; 12]), ObjectId::from_bytes([2; 12])]
+    );
+
+    let empty: Vec<Bson> = doc.get_array("empty").unwrap().to_vec().unwrap();
+    assert!(empty.is_empty());
+
+    let mixed = doc.get_array("mixed").unwrap();
+    assert!(mixed.to_vec::<i32>().is_err());
+    let results: Vec<_> = mixed.iter_as::<i32>().collect();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), &1);
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap(), &3);
+
+    let names = rawdoc! { "names": ["x", "y"] };
+    let borrowed: Vec<&str> = names
+        .get_array("names")
+        .unwrap()
+        .iter_as()
+        .collect::<crate::de::Result<_>>()
+        .unwrap();
+    assert_eq!(borrowed, vec!["x", "y"]);
+}
+
+#[test]
+fn document_buf_clear_retains_capacity() {
+    let mut doc = rawdoc! { "a": 1, "b": "a longer string value", "c": { "d": [1, 2, 3] } };
+    let len = doc.as_bytes().len();
+
+    doc.clear();
+    assert_eq!(doc.as_bytes(), RawDocumentBuf::new().as_bytes());
+    assert!(doc.is_empty());
+    assert!(doc.into_bytes().capacity() >= len);
+
+    let mut doc = rawdoc! { "a": 1 };
+    let ptr = doc.as_bytes().as_ptr();
+    doc.clear();
+    doc.append("b", 2);
+    assert_eq!(doc, rawdoc! { "b": 2 });
+    assert_eq!(doc.as_bytes().as_ptr(), ptr);
+
+    let mut empty = RawDocumentBuf::new();
+    empty.clear();
+    assert_eq!(empty, RawDocumentBuf::new());
+}
+
+#[test]
+fn nested_array_is_not_decoded_until_accessed() {
+    // { "a": [<element with invalid type 0x99>] }
+    let bytes = [
+        20, 0, 0, 0, // document length
+        0x04, b'a', 0, // array "a"
+        12, 0, 0, 0, // array length
+        0x99, b'0', 0, 1, 2, 3, 4, // malformed element
+        0, // array terminator
+        0, // document terminator
+    ];
+    let doc = RawDocument::from_bytes(&bytes).unwrap();
+
+    let value = doc.get("a").unwrap().unwrap();
+    let arr = value.as_array().unwrap();
+    assert_eq!(arr.as_bytes(), &bytes[7..19]);
+    assert!(doc.get_array("a").is_ok());
+
+    // decoding happens only when the elements are accessed
+    assert!(arr.into_iter().next().unwrap().is_err());
+    assert!(Bson::try_from(value).is_err());
+    assert!(value.to_raw_bson().as_array().is_some());
+}
+
+#[test]
+fn optional_getters() {
+    let rawdoc = rawdoc! {
+        "doc": { "x": 1 },
+        "str": "hello",
+        "i64": 5_i64,
+        "bool": true,
+    };
+
+    assert_eq!(
+        rawdoc.get_document_opt("doc").unwrap().unwrap(),
+        rawdoc! { "x": 1 }.as_ref()
+    );
+    assert_eq!(rawdoc.get_str_opt("str").unwrap(), Some("hello"));
+    assert_eq!(rawdoc.get_i64_opt("i64").unwrap(), Some(5));
+    assert_eq!(rawdoc.get_bool_opt("bool").unwrap(), Some(true));
+
+    assert_eq!(rawdoc.get_document_opt("missing").unwrap(), None);
+    assert_eq!(rawdoc.get_str_opt("missing").unwrap(), None);
+    assert_eq!(rawdoc.get_i64_opt("missing").unwrap(), None);
+
+    let err = rawdoc.get_i64_opt("str").unwrap_err();
+    assert_eq!(err.key(), "str");
+    assert!(matches!(
+        err.kind,
+        ValueAccessErrorKind::UnexpectedType {
+            expected: ElementType::Int64,
+            actual: ElementType::String,
+            ..
+        }
+    ));
+    assert!(rawdoc.get_document_opt("bool").is_err());
+}
+
+#[test]
+fn keys_and_len() {
+    let rawdoc = rawdoc! {
+        "a": 1,
+        "b": { "c": "d" },
+        "e": [1, 2, 3],
+        "f": RawBson::Binary(Binary { subtype: BinarySubtype::Generic, bytes: vec![1, 2] }),
+    };
+    let keys = rawdoc.keys().collect::<Result<Vec<_>>>().unwrap();
+    assert_eq!(keys, vec!["a", "b", "e", "f"]);
+    assert_eq!(rawdoc.len().unwrap(), 4);
+
+    let empty = rawdoc! {};
+    assert_eq!(empty.keys().count(), 0);
+    assert_eq!(empty.len().unwrap(), 0);
+
+    // a value that would fail to decode doesn't affect key iteration
+    let mut bytes = rawdoc! { "s": "x" }.into_bytes();
+    bytes[11] = 0xff;
+    let doc = RawDocument::from_bytes(&bytes).unwrap();
+    assert!(doc.get("s").is_err());
+    assert_eq!(doc.keys().collect::<Result<Vec<_>>>().unwrap(), vec!["s"]);
+    assert_eq!(doc.len().unwrap(), 1);
+
+    // truncated length prefix on a nested document
+    let mut bytes = rawdoc! { "d": { "x": 1 } }.into_bytes();
+    bytes[7] = 0xff;
+    let doc = RawDocument::from_bytes(&bytes).unwrap();
+    assert!(doc.len().is_err());
+    let keys: Vec<_> = doc.keys().collect();
+    assert_eq!(keys.len(), 1);
+    assert!(keys[0].is_err());
+}
+
+#[test]
+fn element_as_bytes() {
+    let rawdoc = rawdoc! {
+        "a": 1,
+        "bb": { "c": "d" },
+        "e": [true],
+    };
+    let mut elements = Vec::new();
+    for elem in rawdoc.iter_elements() {
+        elements.extend_from_slice(elem.unwrap().as_bytes());
+    }
+    // the document's elements are everything between the length prefix and the final null byte
+    let bytes = rawdoc.as_bytes();
+    assert_eq!(elements, &bytes[4..bytes.len() - 1]);
+
+    let elem = rawdoc.iter_elements().next().unwrap().unwrap();
+    assert_eq!(elem.as_bytes(), &[0x10, b'a', 0, 1, 0, 0, 0]);
+}
+
+#[test]
+fn numeric_lenient() {
+    let rawdoc = rawdoc! {
+        "int32": 1,
+        "int64": 2i64,
+        "double": 3.0,
+        "fractional": 3.5,
+        "huge": 1e19,
+        "nan": f64::NAN,
+        "string": "4",
+    };
+    let get = |key: &str| rawdoc.get(key).unwrap().unwrap();
+
+    assert_eq!(get("int32").as_i64_lenient(), Some(1));
+    assert_eq!(get("int64").as_i64_lenient(), Some(2));
+    assert_eq!(get("double").as_i64_lenient(), Some(3));
+    assert_eq!(get("fractional").as_i64_lenient(), None);
+    assert_eq!(get("huge").as_i64_lenient(), None);
+    assert_eq!(get("nan").as_i64_lenient(), None);
+    assert_eq!(get("string").as_i64_lenient(), None);
+
+    assert_eq!(get("int32").as_f64_lenient(), Some(1.0));
+    assert_eq!(get("int64").as_f64_lenient(), Some(2.0));
+    assert_eq!(get("fractional").as_f64_lenient(), Some(3.5));
+    assert_eq!(get("string").as_f64_lenient(), None);
+}
+
 #[test]
 fn document_iteration() {
     let rawdoc = rawdoc! {
@@ -482,6 +904,48 @@ fn fuzz_oom() {
     let _ = crate::from_slice::<crate::Document>(bytes);
 }
 
+#[test]
+fn validate() {
+    let rawdoc = rawdoc! {
+        "string": "hello",
+        "nested": { "array": [1, { "inner": true }] },
+        "code_w_scope": crate::RawJavaScriptCodeWithScope {
+            code: "x".to_string(),
+            scope: rawdoc! { "y": { "z": 1 } },
+        },
+    };
+    rawdoc.validate(Default::default()).unwrap();
+    rawdoc
+        .validate(ValidationOptions::builder().max_depth(4).build())
+        .unwrap();
+
+    let err = rawdoc
+        .validate(ValidationOptions::builder().max_depth(3).build())
+        .unwrap_err();
+    assert_eq!(err.key(), Some("1"));
+
+    let err = rawdoc
+        .validate(ValidationOptions::builder().max_depth(2).build())
+        .unwrap_err();
+    assert_eq!(err.key(), Some("array"));
+
+    let len = rawdoc.as_bytes().len();
+    rawdoc
+        .validate(ValidationOptions::builder().max_size(len).build())
+        .unwrap();
+    assert!(rawdoc
+        .validate(ValidationOptions::builder().max_size(len - 1).build())
+        .is_err());
+
+    // invalid UTF-8 in a nested string is not caught by from_bytes but is caught by validate
+    let mut bytes = rawdoc! { "a": { "b": "hello" } }.into_bytes();
+    let pos = bytes.iter().position(|b| *b == b'h').unwrap();
+    bytes[pos] = 0xff;
+    let invalid = RawDocument::from_bytes(&bytes).unwrap();
+    let err = invalid.validate(Default::default()).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::Utf8EncodingError(_)));
+}
+
 use props::arbitrary_bson;
 use proptest::prelude::*;
 use std::convert::TryInto;
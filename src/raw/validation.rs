@@ -0,0 +1,96 @@
+use super::{Error, RawBsonRef, RawDocument, RawIter, Result};
+
+/// Options used to configure [`RawDocument::validate`].
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ValidationOptions {
+    /// The maximum nesting depth permitted, where the top-level document has a depth of 1 and
+    /// each embedded document, array, or code-with-scope scope adds one level. The default is no
+    /// limit.
+    pub max_depth: Option<usize>,
+
+    /// The maximum total size in bytes of the document being validated. The default is no limit.
+    pub max_size: Option<usize>,
+}
+
+impl ValidationOptions {
+    /// Create a builder struct used to construct a [`ValidationOptions`].
+    pub fn builder() -> ValidationOptionsBuilder {
+        ValidationOptionsBuilder {
+            options: Default::default(),
+        }
+    }
+}
+
+/// Builder used to construct a [`ValidationOptions`].
+pub struct ValidationOptionsBuilder {
+    options: ValidationOptions,
+}
+
+impl ValidationOptionsBuilder {
+    /// Set the value for [`ValidationOptions::max_depth`].
+    pub fn max_depth(mut self, val: impl Into<Option<usize>>) -> Self {
+        self.options.max_depth = val.into();
+        self
+    }
+
+    /// Set the value for [`ValidationOptions::max_size`].
+    pub fn max_size(mut self, val: impl Into<Option<usize>>) -> Self {
+        self.options.max_size = val.into();
+        self
+    }
+
+    /// Consume this builder and produce a [`ValidationOptions`].
+    pub fn build(self) -> ValidationOptions {
+        self.options
+    }
+}
+
+/// Walk every element of `doc`, including the contents of any nested documents, arrays, and
+/// code-with-scope scopes, returning the first error encountered.
+///
+/// Traversal uses an explicit stack rather than recursion so that deeply nested input cannot
+/// overflow the call stack when no depth limit is configured.
+pub(crate) fn validate(doc: &RawDocument, options: &ValidationOptions) -> Result<()> {
+    if let Some(max_size) = options.max_size {
+        if doc.as_bytes().len() > max_size {
+            return Err(Error::malformed(format!(
+                "document size {} exceeds maximum of {}",
+                doc.as_bytes().len(),
+                max_size
+            )));
+        }
+    }
+
+    let mut stack: Vec<(RawIter, usize)> = vec![(RawIter::new(doc), 1)];
+    while let Some((iter, depth)) = stack.last_mut() {
+        let depth = *depth;
+        let elem = match iter.next() {
+            Some(elem) => elem?,
+            None => {
+                stack.pop();
+                continue;
+            }
+        };
+
+        let nested = match elem.value()? {
+            RawBsonRef::Document(d) => d,
+            RawBsonRef::Array(a) => a.as_doc(),
+            RawBsonRef::JavaScriptCodeWithScope(c) => c.scope,
+            _ => continue,
+        };
+
+        if let Some(max_depth) = options.max_depth {
+            if depth + 1 > max_depth {
+                return Err(Error::malformed(format!(
+                    "nesting depth exceeds maximum of {}",
+                    max_depth
+                ))
+                .with_key(elem.key()));
+            }
+        }
+        stack.push((RawIter::new(nested), depth + 1));
+    }
+
+    Ok(())
+}
@@ -27,7 +27,7 @@ mod serde;
 
 pub use self::{
     error::{Error, Result},
-    serde::{Serializer, SerializerOptions},
+    serde::{EnumRepresentation, Serializer, SerializerOptions},
 };
 
 use std::io::Write;
@@ -38,7 +38,10 @@ use crate::{
     spec::BinarySubtype,
     RawDocumentBuf,
 };
-use ::serde::{ser::Error as SerdeError, Serialize};
+use ::serde::{
+    ser::{Error as SerdeError, SerializeMap},
+    Serialize,
+};
 
 pub(crate) fn write_string(buf: &mut Vec<u8>, s: &str) {
     buf.extend(&(s.len() as i32 + 1).to_le_bytes());
@@ -199,9 +202,33 @@ where
 #[inline]
 pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
 where
-    T: Serialize,
+    T: Serialize + ?Sized,
+{
+    to_vec_with_options(value, Default::default())
+}
+
+/// Serialize the given `T` as a BSON byte vector, configuring the underlying serializer with the
+/// provided options.
+///
+/// Unlike [`to_bson_with_options`], the serializer used by this function presents itself as not
+/// human readable unless [`SerializerOptions::human_readable`] is set to true.
+/// ```
+/// # use serde::Serialize;
+/// # use bson::SerializerOptions;
+/// #[derive(Debug, Serialize)]
+/// struct MyData {
+///     a: f64,
+/// }
+///
+/// let data = MyData { a: f64::NAN };
+/// let options = SerializerOptions::builder().reject_nan(true).build();
+/// assert!(bson::to_vec_with_options(&data, options).is_err());
+/// ```
+pub fn to_vec_with_options<T>(value: &T, options: SerializerOptions) -> Result<Vec<u8>>
+where
+    T: Serialize + ?Sized,
 {
-    let mut serializer = raw::Serializer::new();
+    let mut serializer = raw::Serializer::new_with_options(options);
     #[cfg(feature = "serde_path_to_error")]
     {
         serde_path_to_error::serialize(value, &mut serializer).map_err(Error::with_path)?;
@@ -213,6 +240,130 @@ where
     Ok(serializer.into_vec())
 }
 
+/// Serialize the given `T` as BSON into `buffer`, replacing its previous contents.
+///
+/// Because document lengths are written in place as serialization proceeds, the only allocation
+/// needed is the output buffer itself. Reusing the same buffer across calls therefore avoids
+/// allocating for each value, which can be significant when encoding many small documents. If an
+/// error is returned, `buffer` is left empty but retains its capacity.
+///
+/// ```
+/// # use serde::Serialize;
+/// #[derive(Serialize)]
+/// struct Item {
+///     id: i32,
+/// }
+///
+/// let mut buffer = Vec::new();
+/// for id in 0..3 {
+///     bson::to_vec_with_buffer(&Item { id }, &mut buffer)?;
+///     assert_eq!(buffer, bson::to_vec(&Item { id })?);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn to_vec_with_buffer<T>(value: &T, buffer: &mut Vec<u8>) -> Result<()>
+where
+    T: Serialize + ?Sized,
+{
+    buffer.clear();
+    let mut serializer = raw::Serializer::with_buffer(std::mem::take(buffer));
+    #[cfg(feature = "serde_path_to_error")]
+    let result = serde_path_to_error::serialize(value, &mut serializer).map_err(Error::with_path);
+    #[cfg(not(feature = "serde_path_to_error"))]
+    let result = value.serialize(&mut serializer);
+    *buffer = serializer.into_vec();
+    if result.is_err() {
+        buffer.clear();
+    }
+    result
+}
+
+/// Serialize the given `T` as BSON, writing the bytes to `writer` as they are produced.
+///
+/// Unlike [`to_vec`], this does not hold the entire serialized value in memory. Because each BSON
+/// document begins with its length, the value is serialized twice: once to compute the length of
+/// every document in it, and again to write it out. `value` must therefore serialize identically
+/// both times; an error is returned if it does not. For values that fit comfortably in memory,
+/// serializing with [`to_vec`] and writing the result is typically faster.
+///
+/// If an error is returned, some of the value may already have been written to `writer`.
+///
+/// ```
+/// # use serde::Serialize;
+/// #[derive(Serialize)]
+/// struct Item {
+///     id: i32,
+///     tags: Vec<String>,
+/// }
+///
+/// let item = Item { id: 1, tags: vec!["a".to_string()] };
+/// let mut output = Vec::new();
+/// bson::to_writer(&mut output, &item)?;
+/// assert_eq!(output, bson::to_vec(&item)?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn to_writer<W, T>(mut writer: W, value: &T) -> Result<()>
+where
+    W: Write,
+    T: Serialize + ?Sized,
+{
+    let mut measure = raw::Serializer::measuring();
+    serialize_raw(value, &mut measure)?;
+
+    let mut stream = raw::Serializer::streaming(&mut writer, measure.into_layout());
+    serialize_raw(value, &mut stream)?;
+    stream.flush(true)
+}
+
+/// Returns the number of bytes `value` occupies when serialized as BSON. This runs the same
+/// measuring pass that [`to_writer`] uses to record document lengths, discarding the encoded bytes
+/// as they are produced.
+pub(crate) fn serialized_len<T>(value: &T) -> Result<usize>
+where
+    T: Serialize + ?Sized,
+{
+    let mut measure = raw::Serializer::measuring();
+    serialize_raw(value, &mut measure)?;
+    Ok(measure.bytes_serialized())
+}
+
+/// Returns the number of bytes `value` occupies when serialized as a BSON element value, excluding
+/// the element's type byte and key.
+pub(crate) fn serialized_value_len<T>(value: &T) -> Result<usize>
+where
+    T: Serialize + ?Sized,
+{
+    struct Element<'a, T: ?Sized>(&'a T);
+
+    impl<T: Serialize + ?Sized> Serialize for Element<'_, T> {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: ::serde::Serializer,
+        {
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry("", self.0)?;
+            map.end()
+        }
+    }
+
+    // document length + element type + empty key + document terminator
+    Ok(serialized_len(&Element(value))? - (4 + 1 + 1 + 1))
+}
+
+fn serialize_raw<T>(value: &T, serializer: &mut raw::Serializer<'_>) -> Result<()>
+where
+    T: Serialize + ?Sized,
+{
+    #[cfg(feature = "serde_path_to_error")]
+    {
+        serde_path_to_error::serialize(value, serializer).map_err(Error::with_path)
+    }
+    #[cfg(not(feature = "serde_path_to_error"))]
+    {
+        value.serialize(serializer)
+    }
+}
+
 /// Serialize the given `T` as a [`RawDocumentBuf`].
 ///
 /// ```rust
@@ -233,7 +384,34 @@ where
 #[inline]
 pub fn to_raw_document_buf<T>(value: &T) -> Result<RawDocumentBuf>
 where
-    T: Serialize,
+    T: Serialize + ?Sized,
 {
     RawDocumentBuf::from_bytes(to_vec(value)?).map_err(Error::custom)
 }
+
+/// Write each of the given [`Document`]s to the writer in sequence, with no separator between
+/// them.
+///
+/// Because every BSON document begins with its own length, the output can be read back one
+/// document at a time with [`DocumentReader`](crate::de::DocumentReader). This is the framing
+/// used by the MongoDB wire protocol and by `mongodump` output files.
+///
+/// ```
+/// # fn main() -> bson::ser::Result<()> {
+/// use bson::doc;
+///
+/// let mut buf: Vec<u8> = Vec::new();
+/// bson::write_documents(&mut buf, vec![doc! { "x": 1 }, doc! { "y": 2 }])?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_documents<W, I>(mut writer: W, docs: I) -> Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = Document>,
+{
+    for doc in docs {
+        doc.to_writer(&mut writer)?;
+    }
+    Ok(())
+}
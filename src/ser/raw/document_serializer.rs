@@ -1,42 +1,48 @@
 use serde::{ser::Impossible, Serialize};
 
 use crate::{
-    ser::{write_cstring, write_i32, Error, Result},
+    ser::{serde::tag_conflict, write_cstring, Error, Result},
     to_bson,
     Bson,
 };
 
 use super::Serializer;
 
-pub(crate) struct DocumentSerializationResult<'a> {
-    pub(crate) root_serializer: &'a mut Serializer,
+pub(crate) struct DocumentSerializationResult<'a, 'w> {
+    pub(crate) root_serializer: &'a mut Serializer<'w>,
 }
 
 /// Serializer used to serialize document or array bodies.
-pub(crate) struct DocumentSerializer<'a> {
-    root_serializer: &'a mut Serializer,
+pub(crate) struct DocumentSerializer<'a, 'w> {
+    root_serializer: &'a mut Serializer<'w>,
     num_keys_serialized: usize,
     start: usize,
+
+    /// The tag key and variant name written as the first field of this document when it holds
+    /// the contents of an internally tagged newtype variant.
+    variant_tag: Option<(String, &'static str)>,
 }
 
-impl<'a> DocumentSerializer<'a> {
-    pub(crate) fn start(rs: &'a mut Serializer) -> crate::ser::Result<Self> {
-        let start = rs.bytes.len();
-        write_i32(&mut rs.bytes, 0)?;
+impl<'a, 'w> DocumentSerializer<'a, 'w> {
+    pub(crate) fn start(rs: &'a mut Serializer<'w>) -> crate::ser::Result<Self> {
+        let start = rs.reserve_length()?;
+        let variant = rs.variant_tag;
+        let variant_tag = rs.write_variant_tag()?.zip(variant);
         Ok(Self {
             root_serializer: rs,
             num_keys_serialized: 0,
             start,
+            variant_tag,
         })
     }
 
     /// Serialize a document key using the provided closure.
-    fn serialize_doc_key_custom<F: FnOnce(&mut Serializer) -> Result<()>>(
+    fn serialize_doc_key_custom<F: FnOnce(&mut Serializer<'w>) -> Result<()>>(
         &mut self,
         f: F,
     ) -> Result<()> {
         // push a dummy element type for now, will update this once we serialize the value
-        self.root_serializer.reserve_element_type();
+        self.root_serializer.reserve_element_type()?;
         f(self.root_serializer)?;
         self.num_keys_serialized += 1;
         Ok(())
@@ -47,26 +53,33 @@ impl<'a> DocumentSerializer<'a> {
     where
         T: serde::Serialize + ?Sized,
     {
+        let variant_tag = self.variant_tag.take();
         self.serialize_doc_key_custom(|rs| {
+            let key_start = rs.bytes.len();
             key.serialize(KeySerializer {
                 root_serializer: rs,
             })?;
+            if let Some((ref tag, variant)) = variant_tag {
+                if rs.bytes[key_start..rs.bytes.len() - 1] == *tag.as_bytes() {
+                    return Err(tag_conflict(variant, tag));
+                }
+            }
             Ok(())
         })?;
+        self.variant_tag = variant_tag;
         Ok(())
     }
 
-    pub(crate) fn end_doc(self) -> crate::ser::Result<DocumentSerializationResult<'a>> {
+    pub(crate) fn end_doc(self) -> crate::ser::Result<DocumentSerializationResult<'a, 'w>> {
         self.root_serializer.bytes.push(0);
-        let length = (self.root_serializer.bytes.len() - self.start) as i32;
-        self.root_serializer.replace_i32(self.start, length);
+        self.root_serializer.update_length(self.start)?;
         Ok(DocumentSerializationResult {
             root_serializer: self.root_serializer,
         })
     }
 }
 
-impl serde::ser::SerializeSeq for DocumentSerializer<'_> {
+impl serde::ser::SerializeSeq for DocumentSerializer<'_, '_> {
     type Ok = ();
     type Error = Error;
 
@@ -91,7 +104,7 @@ impl serde::ser::SerializeSeq for DocumentSerializer<'_> {
     }
 }
 
-impl serde::ser::SerializeMap for DocumentSerializer<'_> {
+impl serde::ser::SerializeMap for DocumentSerializer<'_, '_> {
     type Ok = ();
 
     type Error = Error;
@@ -117,7 +130,7 @@ impl serde::ser::SerializeMap for DocumentSerializer<'_> {
     }
 }
 
-impl serde::ser::SerializeStruct for DocumentSerializer<'_> {
+impl serde::ser::SerializeStruct for DocumentSerializer<'_, '_> {
     type Ok = ();
 
     type Error = Error;
@@ -137,7 +150,7 @@ impl serde::ser::SerializeStruct for DocumentSerializer<'_> {
     }
 }
 
-impl serde::ser::SerializeTuple for DocumentSerializer<'_> {
+impl serde::ser::SerializeTuple for DocumentSerializer<'_, '_> {
     type Ok = ();
 
     type Error = Error;
@@ -157,7 +170,7 @@ impl serde::ser::SerializeTuple for DocumentSerializer<'_> {
     }
 }
 
-impl serde::ser::SerializeTupleStruct for DocumentSerializer<'_> {
+impl serde::ser::SerializeTupleStruct for DocumentSerializer<'_, '_> {
     type Ok = ();
 
     type Error = Error;
@@ -179,17 +192,17 @@ impl serde::ser::SerializeTupleStruct for DocumentSerializer<'_> {
 
 /// Serializer used specifically for serializing document keys.
 /// Only keys that serialize to strings will be accepted.
-struct KeySerializer<'a> {
-    root_serializer: &'a mut Serializer,
+struct KeySerializer<'a, 'w> {
+    root_serializer: &'a mut Serializer<'w>,
 }
 
-impl KeySerializer<'_> {
+impl KeySerializer<'_, '_> {
     fn invalid_key<T: Serialize>(v: T) -> Error {
         Error::InvalidDocumentKey(to_bson(&v).unwrap_or(Bson::Null))
     }
 }
 
-impl serde::Serializer for KeySerializer<'_> {
+impl serde::Serializer for KeySerializer<'_, '_> {
     type Ok = ();
 
     type Error = Error;
@@ -10,10 +10,18 @@ use serde::{
 
 use self::value_serializer::{ValueSerializer, ValueType};
 
-use super::{write_binary, write_cstring, write_f64, write_i32, write_i64, write_string};
+use super::{
+    serde::{tag_conflict, untaggable_variant},
+    write_binary,
+    write_cstring,
+    write_f64,
+    write_i32,
+    write_i64,
+    write_string,
+};
 use crate::{
     raw::{RAW_ARRAY_NEWTYPE, RAW_DOCUMENT_NEWTYPE},
-    ser::{Error, Result},
+    ser::{EnumRepresentation, Error, Result, SerializerOptions},
     serde_helpers::HUMAN_READABLE_NEWTYPE,
     spec::{BinarySubtype, ElementType},
     uuid::UUID_NEWTYPE_NAME,
@@ -21,10 +29,17 @@ use crate::{
 use document_serializer::DocumentSerializer;
 
 /// Serializer used to convert a type `T` into raw BSON bytes.
-pub(crate) struct Serializer {
+pub(crate) struct Serializer<'w> {
+    /// Serialized bytes that have not yet been flushed to the output.
     bytes: Vec<u8>,
 
-    /// The index into `bytes` where the current element type will need to be stored.
+    /// The number of bytes that were flushed from `bytes` before its current contents.
+    flushed: usize,
+
+    /// Where serialized bytes end up.
+    output: Output<'w>,
+
+    /// The position in the output where the current element type will need to be stored.
     /// This needs to be set retroactively because in BSON, the element type comes before the key,
     /// but in serde, the serializer learns of the type after serializing the key.
     type_index: usize,
@@ -33,6 +48,64 @@ pub(crate) struct Serializer {
     hint: SerializerHint,
 
     human_readable: bool,
+
+    /// Whether NaN floating-point values should be rejected.
+    reject_nan: bool,
+
+    /// How enum variants are represented.
+    enum_representation: Option<EnumRepresentation>,
+
+    /// The name of an internally tagged newtype variant whose contents are being serialized. The
+    /// tag is written as the first field of the document that the contents start.
+    variant_tag: Option<&'static str>,
+}
+
+/// The destination of the bytes produced by a [`Serializer`].
+///
+/// Document lengths and element types are only known after the bytes following them have been
+/// serialized. When serializing into a buffer, placeholders are written and later filled in. To
+/// write to an [`std::io::Write`] without holding the whole output in memory, the value is instead
+/// serialized twice: a [`Output::Measure`] pass records the lengths and types, and a
+/// [`Output::Stream`] pass writes them up front and flushes bytes to the writer as it goes.
+enum Output<'w> {
+    /// All bytes are kept in the buffer.
+    Buffer,
+
+    /// Bytes are discarded, and the document lengths and element types are recorded.
+    Measure {
+        layout: Layout,
+
+        /// The indexes in `layout.lengths` of the documents that have been started but not ended.
+        open_lengths: Vec<usize>,
+    },
+
+    /// Bytes are written to `writer`, using the document lengths and element types recorded by a
+    /// previous `Measure` pass over the same value.
+    Stream {
+        writer: &'w mut dyn Write,
+        layout: Layout,
+        next_length: usize,
+        next_type: usize,
+
+        /// The indexes in `layout.lengths` of the documents that have been started but not ended.
+        open_lengths: Vec<usize>,
+    },
+}
+
+/// The document lengths and element types of a serialized value, each stored in the order in
+/// which their placeholders appear in the output.
+#[derive(Default)]
+pub(crate) struct Layout {
+    lengths: Vec<i32>,
+    types: Vec<u8>,
+}
+
+/// The number of buffered bytes above which a serializer that is not writing to a buffer flushes
+/// them to its output.
+const FLUSH_THRESHOLD: usize = 8 * 1024;
+
+fn layout_mismatch() -> Error {
+    Error::custom("value serialized differently when written a second time")
 }
 
 /// Various bits of information that the serialized type can provide to the serializer to
@@ -57,13 +130,63 @@ impl SerializerHint {
     }
 }
 
-impl Serializer {
+impl<'w> Serializer<'w> {
     pub(crate) fn new() -> Self {
         Self {
             bytes: Vec::new(),
+            flushed: 0,
+            output: Output::Buffer,
             type_index: 0,
             hint: SerializerHint::None,
             human_readable: false,
+            reject_nan: false,
+            enum_representation: None,
+            variant_tag: None,
+        }
+    }
+
+    #[allow(deprecated)]
+    pub(crate) fn new_with_options(options: SerializerOptions) -> Self {
+        Self {
+            human_readable: options.human_readable.unwrap_or(false),
+            reject_nan: options.reject_nan.unwrap_or(false),
+            enum_representation: options.enum_representation,
+            ..Self::new()
+        }
+    }
+
+    /// Construct a serializer that appends to `bytes`, reusing its allocation.
+    pub(crate) fn with_buffer(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            ..Self::new()
+        }
+    }
+
+    /// Construct a serializer that discards its output, recording the value's [`Layout`] for use
+    /// with [`Serializer::streaming`].
+    pub(crate) fn measuring() -> Self {
+        Self {
+            output: Output::Measure {
+                layout: Layout::default(),
+                open_lengths: Vec::new(),
+            },
+            ..Self::new()
+        }
+    }
+
+    /// Construct a serializer that writes to `writer`, using the `layout` recorded by serializing
+    /// the same value with a [`Serializer::measuring`] serializer.
+    pub(crate) fn streaming(writer: &'w mut dyn Write, layout: Layout) -> Self {
+        Self {
+            output: Output::Stream {
+                writer,
+                layout,
+                next_length: 0,
+                next_type: 0,
+                open_lengths: Vec::new(),
+            },
+            ..Self::new()
         }
     }
 
@@ -72,16 +195,127 @@ impl Serializer {
         self.bytes
     }
 
+    /// Convert a measuring serializer into the layout it recorded.
+    pub(crate) fn into_layout(self) -> Layout {
+        match self.output {
+            Output::Measure { layout, .. } => layout,
+            _ => Layout::default(),
+        }
+    }
+
+    /// The number of bytes serialized so far, including any that have been flushed.
+    pub(crate) fn bytes_serialized(&self) -> usize {
+        self.position()
+    }
+
+    /// The position in the output at which the next byte will be written.
+    #[inline]
+    fn position(&self) -> usize {
+        self.flushed + self.bytes.len()
+    }
+
+    /// Write any buffered bytes to the output if it is not a buffer. If `force` is false, this
+    /// only happens once enough bytes have accumulated.
+    pub(crate) fn flush(&mut self, force: bool) -> Result<()> {
+        if !force && self.bytes.len() < FLUSH_THRESHOLD {
+            return Ok(());
+        }
+        match self.output {
+            Output::Buffer => return Ok(()),
+            Output::Measure { .. } => {}
+            Output::Stream { ref mut writer, .. } => writer.write_all(&self.bytes)?,
+        }
+        self.flushed += self.bytes.len();
+        self.bytes.clear();
+        Ok(())
+    }
+
     /// Reserve a spot for the element type to be set retroactively via `update_element_type`.
     #[inline]
-    fn reserve_element_type(&mut self) {
-        self.type_index = self.bytes.len(); // record index
+    fn reserve_element_type(&mut self) -> Result<()> {
+        self.type_index = self.position(); // record index
+        if !matches!(self.output, Output::Buffer) {
+            return self.reserve_unbuffered_element_type();
+        }
         self.bytes.push(0); // push temporary placeholder
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn reserve_unbuffered_element_type(&mut self) -> Result<()> {
+        self.flush(false)?;
+        let placeholder = match self.output {
+            Output::Buffer => 0,
+            Output::Measure { ref mut layout, .. } => {
+                layout.types.push(0);
+                0
+            }
+            Output::Stream {
+                ref layout,
+                ref mut next_type,
+                ..
+            } => {
+                let t = *layout.types.get(*next_type).ok_or_else(layout_mismatch)?;
+                *next_type += 1;
+                t
+            }
+        };
+        self.bytes.push(placeholder);
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn update_unbuffered_element_type(&mut self, t: ElementType) -> Result<()> {
+        match self.output {
+            Output::Buffer => {}
+            Output::Measure { ref mut layout, .. } => {
+                *layout.types.last_mut().ok_or_else(layout_mismatch)? = t as u8;
+            }
+            Output::Stream {
+                ref layout,
+                next_type,
+                ..
+            } => {
+                let expected = next_type.checked_sub(1).and_then(|i| layout.types.get(i));
+                if expected != Some(&(t as u8)) {
+                    return Err(layout_mismatch());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The tag key of the internally tagged representation, if it is in use.
+    fn internal_tag(&self) -> Option<&str> {
+        match self.enum_representation {
+            Some(EnumRepresentation::Internal { ref tag }) => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// Write the pending internally tagged variant name, if any, as the first field of the document
+    /// that has just been started, returning the tag key.
+    fn write_variant_tag(&mut self) -> Result<Option<String>> {
+        let variant = match self.variant_tag.take() {
+            Some(variant) => variant,
+            None => return Ok(None),
+        };
+        let tag = self.internal_tag().unwrap_or_default().to_string();
+        self.bytes.push(ElementType::String as u8);
+        write_cstring(&mut self.bytes, &tag)?;
+        write_string(&mut self.bytes, variant);
+        Ok(Some(tag))
     }
 
     /// Retroactively set the element type of the most recently serialized element.
     #[inline]
     fn update_element_type(&mut self, t: ElementType) -> Result<()> {
+        if let Some(variant) = self.variant_tag {
+            if !matches!(t, ElementType::EmbeddedDocument) {
+                return Err(untaggable_variant(variant, t));
+            }
+        }
+
         if self.type_index == 0 {
             if matches!(t, ElementType::EmbeddedDocument) {
                 // don't need to set the element type for the top level document
@@ -94,29 +328,106 @@ impl Serializer {
             }
         }
 
+        if !matches!(self.output, Output::Buffer) {
+            return self.update_unbuffered_element_type(t);
+        }
         self.bytes[self.type_index] = t as u8;
         Ok(())
     }
 
-    /// Replace an i32 value at the given index with the given value.
+    /// Write a placeholder for a length to be set retroactively via `update_length`, returning
+    /// its position.
     #[inline]
-    fn replace_i32(&mut self, at: usize, with: i32) {
+    fn reserve_length(&mut self) -> Result<usize> {
+        let at = self.position();
+        let placeholder = if matches!(self.output, Output::Buffer) {
+            0
+        } else {
+            self.reserve_unbuffered_length()?
+        };
+        write_i32(&mut self.bytes, placeholder)?;
+        Ok(at)
+    }
+
+    #[inline(never)]
+    fn reserve_unbuffered_length(&mut self) -> Result<i32> {
+        match self.output {
+            Output::Buffer => Ok(0),
+            Output::Measure {
+                ref mut layout,
+                ref mut open_lengths,
+            } => {
+                open_lengths.push(layout.lengths.len());
+                layout.lengths.push(0);
+                Ok(0)
+            }
+            Output::Stream {
+                ref layout,
+                ref mut next_length,
+                ref mut open_lengths,
+                ..
+            } => {
+                let len = *layout
+                    .lengths
+                    .get(*next_length)
+                    .ok_or_else(layout_mismatch)?;
+                open_lengths.push(*next_length);
+                *next_length += 1;
+                Ok(len)
+            }
+        }
+    }
+
+    /// Set the length reserved at `at` to the number of bytes written since. Lengths must be
+    /// updated in the reverse order of their reservation.
+    #[inline]
+    fn update_length(&mut self, at: usize) -> Result<()> {
+        let length = (self.position() - at) as i32;
+        if !matches!(self.output, Output::Buffer) {
+            return self.update_unbuffered_length(length);
+        }
         let portion = &mut self.bytes[at..at + 4];
-        portion.copy_from_slice(&with.to_le_bytes());
+        portion.copy_from_slice(&length.to_le_bytes());
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn update_unbuffered_length(&mut self, length: i32) -> Result<()> {
+        match self.output {
+            Output::Buffer => {}
+            Output::Measure {
+                ref mut layout,
+                ref mut open_lengths,
+            } => {
+                let index = open_lengths.pop().ok_or_else(layout_mismatch)?;
+                layout.lengths[index] = length;
+            }
+            Output::Stream {
+                ref layout,
+                ref mut open_lengths,
+                ..
+            } => {
+                let index = open_lengths.pop().ok_or_else(layout_mismatch)?;
+                if layout.lengths[index] != length {
+                    return Err(layout_mismatch());
+                }
+            }
+        }
+        Ok(())
     }
 }
 
-impl<'a> serde::Serializer for &'a mut Serializer {
+impl<'a, 'w> serde::Serializer for &'a mut Serializer<'w> {
     type Ok = ();
     type Error = Error;
 
-    type SerializeSeq = DocumentSerializer<'a>;
-    type SerializeTuple = DocumentSerializer<'a>;
-    type SerializeTupleStruct = DocumentSerializer<'a>;
-    type SerializeTupleVariant = VariantSerializer<'a>;
-    type SerializeMap = DocumentSerializer<'a>;
-    type SerializeStruct = StructSerializer<'a>;
-    type SerializeStructVariant = VariantSerializer<'a>;
+    type SerializeSeq = DocumentSerializer<'a, 'w>;
+    type SerializeTuple = DocumentSerializer<'a, 'w>;
+    type SerializeTupleStruct = DocumentSerializer<'a, 'w>;
+    type SerializeTupleVariant = VariantSerializer<'a, 'w>;
+    type SerializeMap = DocumentSerializer<'a, 'w>;
+    type SerializeStruct = StructSerializer<'a, 'w>;
+    type SerializeStructVariant = VariantSerializer<'a, 'w>;
 
     fn is_human_readable(&self) -> bool {
         self.human_readable
@@ -185,6 +496,11 @@ impl<'a> serde::Serializer for &'a mut Serializer {
 
     #[inline]
     fn serialize_f64(self, v: f64) -> Result<Self::Ok> {
+        if v.is_nan() && self.reject_nan {
+            return Err(Error::SerializationError {
+                message: "NaN values are not permitted".to_string(),
+            });
+        }
         self.update_element_type(ElementType::Double)?;
         write_f64(&mut self.bytes, v)
     }
@@ -260,7 +576,16 @@ impl<'a> serde::Serializer for &'a mut Serializer {
         _variant_index: u32,
         variant: &'static str,
     ) -> Result<Self::Ok> {
-        self.serialize_str(variant)
+        let tag = match self.enum_representation {
+            Some(EnumRepresentation::Internal { ref tag })
+            | Some(EnumRepresentation::Adjacent { ref tag, .. }) => tag.clone(),
+            _ => return self.serialize_str(variant),
+        };
+        self.update_element_type(ElementType::EmbeddedDocument)?;
+        let mut d = DocumentSerializer::start(&mut *self)?;
+        d.serialize_entry(tag.as_str(), variant)?;
+        d.end_doc()?;
+        Ok(())
     }
 
     #[inline]
@@ -296,9 +621,34 @@ impl<'a> serde::Serializer for &'a mut Serializer {
         T: serde::Serialize + ?Sized,
     {
         self.update_element_type(ElementType::EmbeddedDocument)?;
-        let mut d = DocumentSerializer::start(&mut *self)?;
-        d.serialize_entry(variant, value)?;
-        d.end_doc()?;
+        match self.enum_representation {
+            Some(EnumRepresentation::Internal { ref tag }) => {
+                if self.variant_tag.is_some() {
+                    return Err(tag_conflict(variant, tag));
+                }
+                // the tag is written by the document that the contents start
+                self.variant_tag = Some(variant);
+                value.serialize(&mut *self)?;
+                if self.variant_tag.take().is_some() {
+                    return Err(untaggable_variant(variant, ElementType::EmbeddedDocument));
+                }
+            }
+            Some(EnumRepresentation::Adjacent {
+                ref tag,
+                ref content,
+            }) => {
+                let (tag, content) = (tag.clone(), content.clone());
+                let mut d = DocumentSerializer::start(&mut *self)?;
+                d.serialize_entry(tag.as_str(), variant)?;
+                d.serialize_entry(content.as_str(), value)?;
+                d.end_doc()?;
+            }
+            _ => {
+                let mut d = DocumentSerializer::start(&mut *self)?;
+                d.serialize_entry(variant, value)?;
+                d.end_doc()?;
+            }
+        }
         Ok(())
     }
 
@@ -330,6 +680,9 @@ impl<'a> serde::Serializer for &'a mut Serializer {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
+        if self.internal_tag().is_some() {
+            return Err(untaggable_variant(variant, ElementType::Array));
+        }
         self.update_element_type(ElementType::EmbeddedDocument)?;
         VariantSerializer::start(&mut *self, variant, VariantInnerType::Tuple)
     }
@@ -383,15 +736,15 @@ impl<'a> serde::Serializer for &'a mut Serializer {
     }
 }
 
-pub(crate) enum StructSerializer<'a> {
+pub(crate) enum StructSerializer<'a, 'w> {
     /// Serialize a BSON value currently represented in serde as a struct (e.g. ObjectId)
-    Value(ValueSerializer<'a>),
+    Value(ValueSerializer<'a, 'w>),
 
     /// Serialize the struct as a document.
-    Document(DocumentSerializer<'a>),
+    Document(DocumentSerializer<'a, 'w>),
 }
 
-impl SerializeStruct for StructSerializer<'_> {
+impl SerializeStruct for StructSerializer<'_, '_> {
     type Ok = ();
     type Error = Error;
 
@@ -422,45 +775,82 @@ enum VariantInnerType {
 
 /// Serializer used for enum variants, including both tuple (e.g. Foo::Bar(1, 2, 3)) and
 /// struct (e.g. Foo::Bar { a: 1 }).
-pub(crate) struct VariantSerializer<'a> {
-    root_serializer: &'a mut Serializer,
+pub(crate) struct VariantSerializer<'a, 'w> {
+    root_serializer: &'a mut Serializer<'w>,
 
-    /// Variants are serialized as documents of the form `{ <variant name>: <document or array> }`,
-    /// and `doc_start` indicates the index at which the outer document begins.
+    /// By default, variants are serialized as documents of the form
+    /// `{ <variant name>: <document or array> }`, and `doc_start` indicates the index at which the
+    /// outer document begins.
     doc_start: usize,
 
-    /// `inner_start` indicates the index at which the inner document or array begins.
-    inner_start: usize,
+    /// `inner_start` indicates the index at which the inner document or array begins, or is
+    /// [`None`] if the fields are written to the outer document, as for internally tagged
+    /// variants.
+    inner_start: Option<usize>,
+
+    /// The name of this variant.
+    variant: &'static str,
+
+    /// The tag key written to the outer document alongside the fields, for internally tagged
+    /// variants.
+    tag: Option<String>,
 
     /// How many elements have been serialized in the inner document / array so far.
     num_elements_serialized: usize,
 }
 
-impl<'a> VariantSerializer<'a> {
+impl<'a, 'w> VariantSerializer<'a, 'w> {
     fn start(
-        rs: &'a mut Serializer,
+        rs: &'a mut Serializer<'w>,
         variant: &'static str,
         inner_type: VariantInnerType,
     ) -> Result<Self> {
-        let doc_start = rs.bytes.len();
         // write placeholder length for document, will be updated at end
-        write_i32(&mut rs.bytes, 0)?;
+        let doc_start = rs.reserve_length()?;
+
+        let mut tag = None;
+        let inner_key = match rs.enum_representation {
+            Some(EnumRepresentation::Internal { .. }) => {
+                if let Some(outer) = rs.variant_tag {
+                    return Err(tag_conflict(outer, rs.internal_tag().unwrap_or_default()));
+                }
+                rs.variant_tag = Some(variant);
+                tag = rs.write_variant_tag()?;
+                None
+            }
+            Some(EnumRepresentation::Adjacent {
+                tag: ref tag_key,
+                ref content,
+            }) => {
+                rs.bytes.push(ElementType::String as u8);
+                write_cstring(&mut rs.bytes, tag_key)?;
+                write_string(&mut rs.bytes, variant);
+                Some(content.as_str())
+            }
+            _ => Some(variant),
+        };
 
-        let inner = match inner_type {
-            VariantInnerType::Struct => ElementType::EmbeddedDocument,
-            VariantInnerType::Tuple => ElementType::Array,
+        let inner_start = match inner_key {
+            Some(key) => {
+                let inner = match inner_type {
+                    VariantInnerType::Struct => ElementType::EmbeddedDocument,
+                    VariantInnerType::Tuple => ElementType::Array,
+                };
+                rs.bytes.push(inner as u8);
+                write_cstring(&mut rs.bytes, key)?;
+                // write placeholder length for inner, will be updated at end
+                Some(rs.reserve_length()?)
+            }
+            None => None,
         };
-        rs.bytes.push(inner as u8);
-        write_cstring(&mut rs.bytes, variant)?;
-        let inner_start = rs.bytes.len();
-        // write placeholder length for inner, will be updated at end
-        write_i32(&mut rs.bytes, 0)?;
 
         Ok(Self {
             root_serializer: rs,
             num_elements_serialized: 0,
             doc_start,
             inner_start,
+            variant,
+            tag,
         })
     }
 
@@ -469,7 +859,12 @@ impl<'a> VariantSerializer<'a> {
     where
         T: Serialize + ?Sized,
     {
-        self.root_serializer.reserve_element_type();
+        if let Some(ref tag) = self.tag {
+            if tag == k {
+                return Err(tag_conflict(self.variant, tag));
+            }
+        }
+        self.root_serializer.reserve_element_type()?;
         write_cstring(&mut self.root_serializer.bytes, k)?;
         v.serialize(&mut *self.root_serializer)?;
 
@@ -479,21 +874,19 @@ impl<'a> VariantSerializer<'a> {
 
     #[inline]
     fn end_both(self) -> Result<()> {
-        // null byte for the inner
-        self.root_serializer.bytes.push(0);
-        let arr_length = (self.root_serializer.bytes.len() - self.inner_start) as i32;
-        self.root_serializer
-            .replace_i32(self.inner_start, arr_length);
+        if let Some(inner_start) = self.inner_start {
+            // null byte for the inner
+            self.root_serializer.bytes.push(0);
+            self.root_serializer.update_length(inner_start)?;
+        }
 
         // null byte for document
         self.root_serializer.bytes.push(0);
-        let doc_length = (self.root_serializer.bytes.len() - self.doc_start) as i32;
-        self.root_serializer.replace_i32(self.doc_start, doc_length);
-        Ok(())
+        self.root_serializer.update_length(self.doc_start)
     }
 }
 
-impl serde::ser::SerializeTupleVariant for VariantSerializer<'_> {
+impl serde::ser::SerializeTupleVariant for VariantSerializer<'_, '_> {
     type Ok = ();
 
     type Error = Error;
@@ -512,7 +905,7 @@ impl serde::ser::SerializeTupleVariant for VariantSerializer<'_> {
     }
 }
 
-impl serde::ser::SerializeStructVariant for VariantSerializer<'_> {
+impl serde::ser::SerializeStructVariant for VariantSerializer<'_, '_> {
     type Ok = ();
 
     type Error = Error;
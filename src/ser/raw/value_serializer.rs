@@ -18,8 +18,8 @@ use super::{document_serializer::DocumentSerializer, Serializer};
 
 /// A serializer used specifically for serializing the serde-data-model form of a BSON type (e.g.
 /// [`Binary`]) to raw bytes.
-pub(crate) struct ValueSerializer<'a> {
-    root_serializer: &'a mut Serializer,
+pub(crate) struct ValueSerializer<'a, 'w> {
+    root_serializer: &'a mut Serializer<'w>,
     state: SerializationStep,
 }
 
@@ -117,8 +117,8 @@ impl From<ValueType> for ElementType {
     }
 }
 
-impl<'a> ValueSerializer<'a> {
-    pub(super) fn new(rs: &'a mut Serializer, value_type: ValueType) -> Self {
+impl<'a, 'w> ValueSerializer<'a, 'w> {
+    pub(super) fn new(rs: &'a mut Serializer<'w>, value_type: ValueType) -> Self {
         let state = match value_type {
             ValueType::DateTime => SerializationStep::DateTime,
             ValueType::Binary => SerializationStep::Binary,
@@ -148,7 +148,7 @@ impl<'a> ValueSerializer<'a> {
     }
 }
 
-impl<'b> serde::Serializer for &'b mut ValueSerializer<'_> {
+impl<'b, 'w> serde::Serializer for &'b mut ValueSerializer<'_, 'w> {
     type Ok = ();
     type Error = Error;
 
@@ -156,7 +156,7 @@ impl<'b> serde::Serializer for &'b mut ValueSerializer<'_> {
     type SerializeTuple = Impossible<(), Error>;
     type SerializeTupleStruct = Impossible<(), Error>;
     type SerializeTupleVariant = Impossible<(), Error>;
-    type SerializeMap = CodeWithScopeSerializer<'b>;
+    type SerializeMap = CodeWithScopeSerializer<'b, 'w>;
     type SerializeStruct = Self;
     type SerializeStructVariant = Impossible<(), Error>;
 
@@ -450,7 +450,7 @@ impl<'b> serde::Serializer for &'b mut ValueSerializer<'_> {
     }
 }
 
-impl SerializeStruct for &mut ValueSerializer<'_> {
+impl SerializeStruct for &mut ValueSerializer<'_, '_> {
     type Ok = ();
     type Error = Error;
 
@@ -580,16 +580,15 @@ impl SerializeStruct for &mut ValueSerializer<'_> {
     }
 }
 
-pub(crate) struct CodeWithScopeSerializer<'a> {
+pub(crate) struct CodeWithScopeSerializer<'a, 'w> {
     start: usize,
-    doc: DocumentSerializer<'a>,
+    doc: DocumentSerializer<'a, 'w>,
 }
 
-impl<'a> CodeWithScopeSerializer<'a> {
+impl<'a, 'w> CodeWithScopeSerializer<'a, 'w> {
     #[inline]
-    fn start(code: &str, rs: &'a mut Serializer) -> Result<Self> {
-        let start = rs.bytes.len();
-        write_i32(&mut rs.bytes, 0)?; // placeholder length
+    fn start(code: &str, rs: &'a mut Serializer<'w>) -> Result<Self> {
+        let start = rs.reserve_length()?; // placeholder length
         write_string(&mut rs.bytes, code);
 
         let doc = DocumentSerializer::start(rs)?;
@@ -597,7 +596,7 @@ impl<'a> CodeWithScopeSerializer<'a> {
     }
 }
 
-impl SerializeMap for CodeWithScopeSerializer<'_> {
+impl SerializeMap for CodeWithScopeSerializer<'_, '_> {
     type Ok = ();
     type Error = Error;
 
@@ -621,8 +620,6 @@ impl SerializeMap for CodeWithScopeSerializer<'_> {
     fn end(self) -> Result<Self::Ok> {
         let result = self.doc.end_doc()?;
 
-        let total_len = (result.root_serializer.bytes.len() - self.start) as i32;
-        result.root_serializer.replace_i32(self.start, total_len);
-        Ok(())
+        result.root_serializer.update_length(self.start)
     }
 }
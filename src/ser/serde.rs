@@ -18,7 +18,7 @@ use crate::{
     oid::ObjectId,
     raw::{RawDbPointerRef, RawRegexRef, RAW_ARRAY_NEWTYPE, RAW_DOCUMENT_NEWTYPE},
     serde_helpers::HUMAN_READABLE_NEWTYPE,
-    spec::BinarySubtype,
+    spec::{BinarySubtype, ElementType},
     uuid::UUID_NEWTYPE_NAME,
     Binary,
     Decimal128,
@@ -118,6 +118,108 @@ pub struct SerializerOptions {
     /// The default value is true.
     #[deprecated = "use bson::serde_helpers::HumanReadable"]
     pub human_readable: Option<bool>,
+
+    /// Whether the [`Serializer`] should return an error when asked to serialize a NaN
+    /// floating-point value rather than encoding it as a BSON double.
+    /// The default value is false.
+    pub reject_nan: Option<bool>,
+
+    /// How enum variants that carry a name should be represented in the serialized output.
+    /// The default value is [`EnumRepresentation::External`].
+    pub enum_representation: Option<EnumRepresentation>,
+}
+
+/// The shapes that the [`Serializer`] can produce for enum variants, mirroring serde's
+/// [enum representations](https://serde.rs/enum-representations.html).
+///
+/// This is intended for types using serde's default externally tagged representation; types that
+/// already declare `#[serde(tag = "...")]` should be serialized with
+/// [`EnumRepresentation::External`]. Deserializing a value produced with another representation
+/// back into an enum requires a type with the matching `#[serde(tag = "...")]` attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EnumRepresentation {
+    /// Unit variants are serialized as a string and all other variants as a single-key document
+    /// mapping the variant name to its contents, e.g. `{ "Variant": { "a": 1 } }`.
+    External,
+
+    /// Variants are serialized as a document containing the variant name under `tag` alongside
+    /// the variant's own fields, e.g. `{ "type": "Variant", "a": 1 }`. Serializing a variant
+    /// whose contents are not a document (such as a tuple variant) will return an error.
+    Internal {
+        /// The key used for the variant name.
+        tag: String,
+    },
+
+    /// Variants are serialized as a document containing the variant name under `tag` and its
+    /// contents under `content`, e.g. `{ "type": "Variant", "data": { "a": 1 } }`. Unit variants
+    /// omit the `content` key.
+    Adjacent {
+        /// The key used for the variant name.
+        tag: String,
+
+        /// The key used for the variant contents.
+        content: String,
+    },
+}
+
+impl EnumRepresentation {
+    /// Build the serialized form of `variant` according to this representation, where `content`
+    /// is [`None`] for unit variants.
+    fn tag_variant(
+        &self,
+        variant: &'static str,
+        content: Option<Bson>,
+    ) -> crate::ser::Result<Bson> {
+        match (self, content) {
+            (EnumRepresentation::External, None) => Ok(Bson::String(variant.to_string())),
+            (EnumRepresentation::External, Some(content)) => {
+                let mut doc = Document::new();
+                doc.insert(variant, content);
+                Ok(Bson::Document(doc))
+            }
+            (EnumRepresentation::Internal { tag }, content) => {
+                let mut doc = Document::new();
+                doc.insert(tag.as_str(), variant);
+                match content {
+                    None => {}
+                    Some(Bson::Document(fields)) => {
+                        if fields.contains_key(tag) {
+                            return Err(tag_conflict(variant, tag));
+                        }
+                        doc.extend(fields);
+                    }
+                    Some(other) => return Err(untaggable_variant(variant, other.element_type())),
+                }
+                Ok(Bson::Document(doc))
+            }
+            (EnumRepresentation::Adjacent { tag, content: key }, content) => {
+                let mut doc = Document::new();
+                doc.insert(tag.as_str(), variant);
+                if let Some(content) = content {
+                    doc.insert(key.as_str(), content);
+                }
+                Ok(Bson::Document(doc))
+            }
+        }
+    }
+}
+
+/// The error returned when the contents of an internally tagged variant contain a field with the
+/// same name as the tag.
+pub(crate) fn tag_conflict(variant: &str, tag: &str) -> Error {
+    Error::custom(format!(
+        "variant {} contains a field that conflicts with the tag {:?}",
+        variant, tag
+    ))
+}
+
+/// The error returned when the contents of an internally tagged variant are not a document.
+pub(crate) fn untaggable_variant(variant: &str, element_type: ElementType) -> Error {
+    Error::custom(format!(
+        "cannot serialize variant {} as internally tagged: expected a document but got {:?}",
+        variant, element_type
+    ))
 }
 
 impl SerializerOptions {
@@ -143,12 +245,37 @@ impl SerializerOptionsBuilder {
         self
     }
 
+    /// Set the value for [`SerializerOptions::reject_nan`].
+    pub fn reject_nan(mut self, value: impl Into<Option<bool>>) -> Self {
+        self.options.reject_nan = value.into();
+        self
+    }
+
+    /// Set the value for [`SerializerOptions::enum_representation`].
+    pub fn enum_representation(mut self, value: impl Into<Option<EnumRepresentation>>) -> Self {
+        self.options.enum_representation = value.into();
+        self
+    }
+
     /// Consume this builder and produce a [`SerializerOptions`].
     pub fn build(self) -> SerializerOptions {
         self.options
     }
 }
 
+impl SerializerOptions {
+    fn tag_variant(
+        &self,
+        variant: &'static str,
+        content: Option<Bson>,
+    ) -> crate::ser::Result<Bson> {
+        self.enum_representation
+            .as_ref()
+            .unwrap_or(&EnumRepresentation::External)
+            .tag_variant(variant, content)
+    }
+}
+
 impl Serializer {
     /// Construct a new [`Serializer`].
     #[allow(clippy::new_without_default)]
@@ -233,6 +360,11 @@ impl ser::Serializer for Serializer {
 
     #[inline]
     fn serialize_f64(self, value: f64) -> crate::ser::Result<Bson> {
+        if value.is_nan() && self.options.reject_nan.unwrap_or(false) {
+            return Err(Error::SerializationError {
+                message: "NaN values are not permitted".to_string(),
+            });
+        }
         Ok(Bson::Double(value))
     }
 
@@ -290,7 +422,7 @@ impl ser::Serializer for Serializer {
         _variant_index: u32,
         variant: &'static str,
     ) -> crate::ser::Result<Bson> {
-        Ok(Bson::String(variant.to_string()))
+        self.options.tag_variant(variant, None)
     }
 
     #[inline]
@@ -367,9 +499,8 @@ impl ser::Serializer for Serializer {
     where
         T: Serialize + ?Sized,
     {
-        let mut newtype_variant = Document::new();
-        newtype_variant.insert(variant, to_bson_with_options(value, self.options)?);
-        Ok(newtype_variant.into())
+        let content = to_bson_with_options(value, self.options.clone())?;
+        self.options.tag_variant(variant, Some(content))
     }
 
     #[inline]
@@ -538,9 +669,8 @@ impl SerializeTupleVariant for TupleVariantSerializer {
     }
 
     fn end(self) -> crate::ser::Result<Bson> {
-        let mut tuple_variant = Document::new();
-        tuple_variant.insert(self.name, self.inner);
-        Ok(tuple_variant.into())
+        self.options
+            .tag_variant(self.name, Some(Bson::Array(self.inner)))
     }
 }
 
@@ -623,11 +753,7 @@ impl SerializeStructVariant for StructVariantSerializer {
 
     fn end(self) -> crate::ser::Result<Bson> {
         let var = Bson::from_extended_document(self.inner);
-
-        let mut struct_variant = Document::new();
-        struct_variant.insert(self.name, var);
-
-        Ok(Bson::Document(struct_variant))
+        self.options.tag_variant(self.name, Some(var))
     }
 }
 
@@ -6,11 +6,21 @@ use serde::{de::Visitor, ser, Deserialize, Serialize, Serializer};
 
 use crate::oid::ObjectId;
 
+#[doc(inline)]
+pub use bson_as_extjson::{
+    deserialize as deserialize_bson_from_extjson,
+    serialize as serialize_bson_as_extjson,
+};
 #[doc(inline)]
 pub use bson_datetime_as_rfc3339_string::{
     deserialize as deserialize_bson_datetime_from_rfc3339_string,
     serialize as serialize_bson_datetime_as_rfc3339_string,
 };
+#[doc(inline)]
+pub use char_as_i32::{
+    deserialize as deserialize_char_from_i32,
+    serialize as serialize_char_as_i32,
+};
 #[cfg(feature = "chrono-0_4")]
 #[doc(inline)]
 pub use chrono_datetime_as_bson_datetime::{
@@ -24,16 +34,46 @@ pub use chrono_datetime_as_bson_datetime_optional::{
     serialize as serialize_chrono_datetime_as_bson_datetime_optional,
 };
 #[doc(inline)]
+pub use duration_as_millis_i64::{
+    deserialize as deserialize_duration_from_millis_i64,
+    serialize as serialize_duration_as_millis_i64,
+};
+#[doc(inline)]
+pub use duration_as_millis_i64_optional::{
+    deserialize as deserialize_duration_from_millis_i64_optional,
+    serialize as serialize_duration_as_millis_i64_optional,
+};
+#[doc(inline)]
+pub use flexible_object_id::{
+    deserialize as deserialize_flexible_object_id,
+    serialize as serialize_flexible_object_id,
+};
+#[doc(inline)]
 pub use hex_string_as_object_id::{
     deserialize as deserialize_hex_string_from_object_id,
     serialize as serialize_hex_string_as_object_id,
 };
 #[doc(inline)]
+pub use hex_string_as_object_id_optional::{
+    deserialize as deserialize_hex_string_from_object_id_optional,
+    serialize as serialize_hex_string_as_object_id_optional,
+};
+#[doc(inline)]
+pub use i128_as_decimal128::{
+    deserialize as deserialize_i128_from_decimal128,
+    serialize as serialize_i128_as_decimal128,
+};
+#[doc(inline)]
 pub use i64_as_bson_datetime::{
     deserialize as deserialize_i64_from_bson_datetime,
     serialize as serialize_i64_as_bson_datetime,
 };
 #[doc(inline)]
+pub use object_id_vec_as_hex::{
+    deserialize as deserialize_object_id_vec_from_hex,
+    serialize as serialize_object_id_vec_as_hex,
+};
+#[doc(inline)]
 pub use rfc3339_string_as_bson_datetime::{
     deserialize as deserialize_rfc3339_string_from_bson_datetime,
     serialize as serialize_rfc3339_string_as_bson_datetime,
@@ -226,6 +266,41 @@ pub mod u64_as_f64 {
     }
 }
 
+/// Contains functions to serialize a `char` as its Unicode code point in an i32 and deserialize a
+/// `char` from an i32.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use bson::serde_helpers::char_as_i32;
+/// #[derive(Serialize, Deserialize)]
+/// struct Grade {
+///     #[serde(with = "char_as_i32")]
+///     pub letter: char,
+/// }
+/// ```
+pub mod char_as_i32 {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::convert::TryFrom;
+
+    /// Deserializes a `char` from an i32. Errors if the i32 is not a valid Unicode scalar value.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<char, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let i = i32::deserialize(deserializer)?;
+        u32::try_from(i)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| de::Error::custom(format!("cannot convert i32 {} to a valid char", i)))
+    }
+
+    /// Serializes a `char` as its Unicode code point in an i32.
+    pub fn serialize<S: Serializer>(val: &char, serializer: S) -> Result<S::Ok, S::Error> {
+        // every char is at most 0x10FFFF, so this conversion is lossless
+        serializer.serialize_i32(*val as u32 as i32)
+    }
+}
+
 /// Contains functions to serialize a [`time::OffsetDateTime`] as a [`crate::DateTime`] and
 /// deserialize a [`time::OffsetDateTime`] from a [`crate::DateTime`].
 ///
@@ -431,6 +506,117 @@ pub mod bson_datetime_as_rfc3339_string {
     }
 }
 
+/// Contains functions to serialize an [`ObjectId`] as usual and deserialize an [`ObjectId`] from
+/// either a bare 24-character hex string or an extended JSON `{ "$oid": <hex> }` object, in any
+/// format.
+///
+/// This allows a single field to accept ids from clients that use either convention, including
+/// when the value is stored as a BSON string rather than an ObjectId.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use bson::{oid::ObjectId, serde_helpers::flexible_object_id};
+/// #[derive(Serialize, Deserialize)]
+/// struct Item {
+///     #[serde(with = "flexible_object_id")]
+///     pub id: ObjectId,
+/// }
+///
+/// let hex = "507f1f77bcf86cd799439011";
+/// let a: Item = serde_json::from_str(&format!(r#"{{ "id": "{}" }}"#, hex))?;
+/// let b: Item = serde_json::from_str(&format!(r#"{{ "id": {{ "$oid": "{}" }} }}"#, hex))?;
+/// assert_eq!(a.id, b.id);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// With the `serde_with` or `serde_with-3` feature enabled, [`FlexibleObjectId`] can also be used
+/// with `#[serde_as(as = "FlexibleObjectId")]`.
+///
+/// [`FlexibleObjectId`]: flexible_object_id::FlexibleObjectId
+pub mod flexible_object_id {
+    use crate::{de::ObjectIdVisitor, oid::ObjectId};
+    use serde::{Deserializer, Serialize, Serializer};
+
+    /// Deserializes an ObjectId from a hex string, an extended JSON object, or an ObjectId.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ObjectId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ObjectIdVisitor)
+    }
+
+    /// Serializes an ObjectId.
+    pub fn serialize<S: Serializer>(val: &ObjectId, serializer: S) -> Result<S::Ok, S::Error> {
+        val.serialize(serializer)
+    }
+
+    /// Marker type for use with `serde_with` that deserializes an [`ObjectId`] from either a hex
+    /// string or an extended JSON object.
+    pub struct FlexibleObjectId;
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl<'de> serde_with::DeserializeAs<'de, ObjectId> for FlexibleObjectId {
+        fn deserialize_as<D>(deserializer: D) -> Result<ObjectId, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize(deserializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl serde_with::SerializeAs<ObjectId> for FlexibleObjectId {
+        fn serialize_as<S: Serializer>(
+            source: &ObjectId,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize(source, serializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl<'de> serde_with_3::DeserializeAs<'de, ObjectId> for FlexibleObjectId {
+        fn deserialize_as<D>(deserializer: D) -> Result<ObjectId, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize(deserializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl serde_with_3::SerializeAs<ObjectId> for FlexibleObjectId {
+        fn serialize_as<S: Serializer>(
+            source: &ObjectId,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize(source, serializer)
+        }
+    }
+}
+
+/// Helpers for (de)serializing [`ObjectId`] values.
+pub mod object_id {
+    /// Contains functions to serialize an [`ObjectId`](crate::oid::ObjectId) as usual and
+    /// deserialize one from either a bare hex string or an extended JSON `{ "$oid": <hex> }`
+    /// object. This is the same module as [`flexible_object_id`](super::flexible_object_id).
+    ///
+    /// ```rust
+    /// # use serde::{Serialize, Deserialize};
+    /// # use bson::oid::ObjectId;
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Item {
+    ///     #[serde(with = "bson::serde_helpers::object_id::flexible")]
+    ///     pub id: ObjectId,
+    /// }
+    /// ```
+    pub use super::flexible_object_id as flexible;
+}
+
 /// Contains functions to serialize a hex string as an ObjectId and deserialize a
 /// hex string from an ObjectId
 ///
@@ -468,6 +654,92 @@ pub mod hex_string_as_object_id {
     }
 }
 
+/// Contains functions to `serialize` an `Option<String>` hex string as an `Option<ObjectId>` and
+/// `deserialize` an `Option<String>` hex string from an `Option<ObjectId>`.
+///
+/// Serialization fails if the string is not a valid 24-character hex ObjectId.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use bson::serde_helpers::hex_string_as_object_id_optional;
+/// #[derive(Serialize, Deserialize)]
+/// struct Item {
+///     #[serde(with = "hex_string_as_object_id_optional")]
+///     pub parent_id: Option<String>,
+/// }
+/// ```
+pub mod hex_string_as_object_id_optional {
+    use crate::oid::ObjectId;
+    use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes an optional hex string from an optional ObjectId.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let object_id = Option::<ObjectId>::deserialize(deserializer)?;
+        Ok(object_id.map(|oid| oid.to_hex()))
+    }
+
+    /// Serializes an optional hex string as an optional ObjectId.
+    pub fn serialize<S: Serializer>(
+        val: &Option<String>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let object_id = val
+            .as_deref()
+            .map(|s| {
+                ObjectId::parse_str(s)
+                    .map_err(|_| ser::Error::custom(format!("cannot convert {} to ObjectId", s)))
+            })
+            .transpose()?;
+        object_id.serialize(serializer)
+    }
+}
+
+/// Contains functions to `serialize` a `Vec<ObjectId>` as a sequence of hex strings and
+/// `deserialize` a `Vec<ObjectId>` from a sequence of hex strings.
+///
+/// Deserialization fails if any of the strings is not a valid ObjectId hex string.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use bson::{oid::ObjectId, serde_helpers::object_id_vec_as_hex};
+/// #[derive(Serialize, Deserialize)]
+/// struct Item {
+///     #[serde(with = "object_id_vec_as_hex")]
+///     pub ids: Vec<ObjectId>,
+/// }
+/// ```
+pub mod object_id_vec_as_hex {
+    use crate::oid::ObjectId;
+    use serde::{de, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+    /// Deserializes a `Vec<ObjectId>` from a sequence of hex strings.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<ObjectId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let strings = Vec::<String>::deserialize(deserializer)?;
+        strings
+            .iter()
+            .map(|s| {
+                ObjectId::parse_str(s)
+                    .map_err(|_| de::Error::custom(format!("cannot convert {} to ObjectId", s)))
+            })
+            .collect()
+    }
+
+    /// Serializes a `Vec<ObjectId>` as a sequence of hex strings.
+    pub fn serialize<S: Serializer>(val: &[ObjectId], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(val.len()))?;
+        for oid in val {
+            seq.serialize_element(&oid.to_hex())?;
+        }
+        seq.end()
+    }
+}
+
 /// Contains functions to `serialize` a `i64` integer as [`DateTime`](crate::DateTime) and
 /// `deserialize` a `i64` integer from [`DateTime`](crate::DateTime).
 ///
@@ -502,6 +774,342 @@ pub mod i64_as_bson_datetime {
     }
 }
 
+/// Contains functions to `serialize` a [`std::time::Duration`] as an `i64` count of milliseconds
+/// and `deserialize` a [`std::time::Duration`] from one.
+///
+/// Any sub-millisecond precision is truncated during serialization, which fails if the number of
+/// milliseconds does not fit in an `i64`. Deserialization fails for negative values.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use std::time::Duration;
+/// # use bson::serde_helpers::duration_as_millis_i64;
+/// #[derive(Serialize, Deserialize)]
+/// struct Item {
+///     #[serde(with = "duration_as_millis_i64")]
+///     pub timeout: Duration,
+/// }
+/// ```
+///
+/// With the `serde_with` or `serde_with-3` feature enabled, [`DurationMillisI64`] can also be used
+/// with `#[serde_as(as = "DurationMillisI64")]`, including on fields of type `Option<Duration>`
+/// via `#[serde_as(as = "Option<DurationMillisI64>")]`.
+///
+/// [`DurationMillisI64`]: duration_as_millis_i64::DurationMillisI64
+pub mod duration_as_millis_i64 {
+    use serde::{de, ser, Deserialize, Deserializer, Serializer};
+    use std::{convert::TryFrom, time::Duration};
+
+    /// Deserializes a Duration from an i64 count of milliseconds.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let millis = i64::deserialize(deserializer)?;
+        match u64::try_from(millis) {
+            Ok(millis) => Ok(Duration::from_millis(millis)),
+            Err(_) => Err(de::Error::custom(format!(
+                "cannot convert negative value {} to Duration",
+                millis
+            ))),
+        }
+    }
+
+    /// Serializes a Duration as an i64 count of milliseconds.
+    pub fn serialize<S: Serializer>(val: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        match i64::try_from(val.as_millis()) {
+            Ok(millis) => serializer.serialize_i64(millis),
+            Err(_) => Err(ser::Error::custom(format!(
+                "cannot convert {:?} to i64 milliseconds",
+                val
+            ))),
+        }
+    }
+
+    /// Marker type for use with `serde_with` that (de)serializes a [`Duration`] as an `i64` count
+    /// of milliseconds.
+    pub struct DurationMillisI64;
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl<'de> serde_with::DeserializeAs<'de, Duration> for DurationMillisI64 {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize(deserializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl serde_with::SerializeAs<Duration> for DurationMillisI64 {
+        fn serialize_as<S: Serializer>(
+            source: &Duration,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize(source, serializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl<'de> serde_with_3::DeserializeAs<'de, Duration> for DurationMillisI64 {
+        fn deserialize_as<D>(deserializer: D) -> Result<Duration, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize(deserializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl serde_with_3::SerializeAs<Duration> for DurationMillisI64 {
+        fn serialize_as<S: Serializer>(
+            source: &Duration,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize(source, serializer)
+        }
+    }
+}
+
+/// Contains functions to `serialize` an `Option<Duration>` as an optional `i64` count of
+/// milliseconds and `deserialize` an `Option<Duration>` from one. See
+/// [`duration_as_millis_i64`] for details of the conversion.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use std::time::Duration;
+/// # use bson::serde_helpers::duration_as_millis_i64_optional;
+/// #[derive(Serialize, Deserialize)]
+/// struct Item {
+///     #[serde(with = "duration_as_millis_i64_optional", default)]
+///     pub timeout: Option<Duration>,
+/// }
+/// ```
+pub mod duration_as_millis_i64_optional {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Duration;
+
+    #[derive(Serialize, Deserialize)]
+    struct Millis(#[serde(with = "super::duration_as_millis_i64")] Duration);
+
+    /// Deserializes an optional Duration from an optional i64 count of milliseconds.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let millis = Option::<Millis>::deserialize(deserializer)?;
+        Ok(millis.map(|m| m.0))
+    }
+
+    /// Serializes an optional Duration as an optional i64 count of milliseconds.
+    pub fn serialize<S: Serializer>(
+        val: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        val.map(Millis).serialize(serializer)
+    }
+}
+
+/// Contains functions to `serialize` an `i128` as a BSON decimal128 and `deserialize` an `i128`
+/// from one. This allows storing integers that are too large for BSON's 64-bit integer type
+/// without losing precision.
+///
+/// Serialization fails if the value has more than 34 significant digits, the maximum precision of
+/// decimal128. Deserialization accepts any decimal128 that represents an integer within the range
+/// of `i128`, regardless of its exponent (e.g. `1.2E+3` or `1200.00`), and fails otherwise.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use bson::serde_helpers::i128_as_decimal128;
+/// #[derive(Serialize, Deserialize)]
+/// struct Account {
+///     #[serde(with = "i128_as_decimal128")]
+///     pub balance: i128,
+/// }
+/// ```
+///
+/// With the `serde_with` or `serde_with-3` feature enabled, [`I128AsDecimal128`] can also be used
+/// with `#[serde_as(as = "I128AsDecimal128")]`.
+///
+/// [`I128AsDecimal128`]: i128_as_decimal128::I128AsDecimal128
+pub mod i128_as_decimal128 {
+    use crate::Decimal128;
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes an i128 from a decimal128 holding an integer.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<i128, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let decimal = Decimal128::deserialize(deserializer)?;
+        decimal.to_i128_exact().ok_or_else(|| {
+            de::Error::custom(format!("cannot convert decimal128 {} to i128", decimal))
+        })
+    }
+
+    /// Serializes an i128 as a decimal128. Errors if the value has more than 34 digits.
+    pub fn serialize<S: Serializer>(val: &i128, serializer: S) -> Result<S::Ok, S::Error> {
+        match Decimal128::from_i128_exact(*val) {
+            Some(decimal) => decimal.serialize(serializer),
+            None => Err(ser::Error::custom(format!(
+                "cannot convert {} to decimal128 without losing precision",
+                val
+            ))),
+        }
+    }
+
+    /// Marker type for use with `serde_with` that (de)serializes an `i128` as a decimal128.
+    pub struct I128AsDecimal128;
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl<'de> serde_with::DeserializeAs<'de, i128> for I128AsDecimal128 {
+        fn deserialize_as<D>(deserializer: D) -> Result<i128, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize(deserializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl serde_with::SerializeAs<i128> for I128AsDecimal128 {
+        fn serialize_as<S: Serializer>(source: &i128, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(source, serializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl<'de> serde_with_3::DeserializeAs<'de, i128> for I128AsDecimal128 {
+        fn deserialize_as<D>(deserializer: D) -> Result<i128, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize(deserializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl serde_with_3::SerializeAs<i128> for I128AsDecimal128 {
+        fn serialize_as<S: Serializer>(source: &i128, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(source, serializer)
+        }
+    }
+}
+
+/// Contains functions to serialize a [`Bson`](crate::Bson) value as canonical extended JSON and
+/// deserialize it from extended JSON when used with a human-readable format such as `serde_json`.
+///
+/// [`Bson`](crate::Bson)'s own [`Serialize`] implementation does not preserve type information in
+/// formats other than BSON, so e.g. an `Int64` may come back as an `Int32`. Routing the field
+/// through [canonical extended JSON](https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/)
+/// makes the round trip lossless. Deserialization accepts both canonical and relaxed extended
+/// JSON. In non-human-readable formats such as BSON itself, the value is (de)serialized as-is.
+///
+/// ```rust
+/// # use serde::{Serialize, Deserialize};
+/// # use bson::{serde_helpers::bson_as_extjson, Bson};
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Event {
+///     #[serde(with = "bson_as_extjson")]
+///     pub payload: Bson,
+/// }
+///
+/// let event = Event { payload: Bson::Int64(5) };
+/// let json = serde_json::to_string(&event)?;
+/// assert_eq!(json, r#"{"payload":{"$numberLong":"5"}}"#);
+/// assert_eq!(serde_json::from_str::<Event>(&json)?, event);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// With the `serde_with` or `serde_with-3` feature enabled, [`BsonAsExtJson`] can also be used
+/// with `#[serde_as(as = "BsonAsExtJson")]`.
+///
+/// [`BsonAsExtJson`]: bson_as_extjson::BsonAsExtJson
+pub mod bson_as_extjson {
+    use std::convert::TryFrom;
+
+    use crate::{extjson::ser::ExtJson, Bson};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes a [`Bson`] value from extended JSON in human-readable formats.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Bson, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let value = Bson::deserialize(deserializer)?;
+        if human_readable {
+            // Parse any extended JSON type wrappers that the visitor left as plain documents.
+            Bson::try_from(value.into_canonical_extjson()).map_err(de::Error::custom)
+        } else {
+            Ok(value)
+        }
+    }
+
+    /// Serializes a [`Bson`] value as canonical extended JSON in human-readable formats.
+    pub fn serialize<S: Serializer>(val: &Bson, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            ExtJson {
+                value: val,
+                canonical: true,
+            }
+            .serialize(serializer)
+        } else {
+            val.serialize(serializer)
+        }
+    }
+
+    /// Marker type for use with `serde_with` that (de)serializes a [`Bson`] value as canonical
+    /// extended JSON.
+    pub struct BsonAsExtJson;
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl<'de> serde_with::DeserializeAs<'de, Bson> for BsonAsExtJson {
+        fn deserialize_as<D>(deserializer: D) -> Result<Bson, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize(deserializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl serde_with::SerializeAs<Bson> for BsonAsExtJson {
+        fn serialize_as<S: Serializer>(source: &Bson, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(source, serializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl<'de> serde_with_3::DeserializeAs<'de, Bson> for BsonAsExtJson {
+        fn deserialize_as<D>(deserializer: D) -> Result<Bson, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize(deserializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl serde_with_3::SerializeAs<Bson> for BsonAsExtJson {
+        fn serialize_as<S: Serializer>(source: &Bson, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize(source, serializer)
+        }
+    }
+}
+
 #[allow(unused_macros)]
 macro_rules! as_binary_mod {
     ($feat:meta, $uu:path) => {
@@ -846,6 +1454,116 @@ pub mod timestamp_as_u32 {
     }
 }
 
+/// Helpers for (de)serializing [`DateTime`](crate::DateTime) values.
+pub mod datetime {
+    use crate::{Bson, DateTime};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Wrapper type that deserializes a [`DateTime`] from a BSON datetime, an extended JSON
+    /// `$date` object, or a plain RFC 3339 string such as `"2023-01-01T00:00:00Z"`. This is useful
+    /// when ingesting data that may have come from sources other than MongoDB.
+    ///
+    /// Serializing a `FlexibleDateTime` produces the same output as serializing the wrapped
+    /// [`DateTime`].
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use bson::serde_helpers::datetime::FlexibleDateTime;
+    /// #[derive(Deserialize)]
+    /// struct Item {
+    ///     created: FlexibleDateTime,
+    /// }
+    ///
+    /// let item: Item = serde_json::from_str(r#"{ "created": "2023-01-01T00:00:00Z" }"#)?;
+    /// assert_eq!(item.created.0.timestamp_millis(), 1_672_531_200_000);
+    ///
+    /// let item: Item = serde_json::from_str(
+    ///     r#"{ "created": { "$date": { "$numberLong": "1672531200000" } } }"#,
+    /// )?;
+    /// assert_eq!(item.created.0.timestamp_millis(), 1_672_531_200_000);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// With the `serde_with` or `serde_with-3` feature enabled, `FlexibleDateTime` can also be used
+    /// with `#[serde_as(as = "FlexibleDateTime")]` on a field of type [`DateTime`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct FlexibleDateTime(pub DateTime);
+
+    impl From<FlexibleDateTime> for DateTime {
+        fn from(value: FlexibleDateTime) -> Self {
+            value.0
+        }
+    }
+
+    impl Serialize for FlexibleDateTime {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FlexibleDateTime {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match Bson::deserialize(deserializer)? {
+                Bson::DateTime(dt) => Ok(FlexibleDateTime(dt)),
+                Bson::String(s) => DateTime::parse_rfc3339_str(&s)
+                    .map(FlexibleDateTime)
+                    .map_err(de::Error::custom),
+                other => Err(de::Error::custom(format!(
+                    "expected a DateTime or RFC 3339 string, got {:?}",
+                    other.element_type()
+                ))),
+            }
+        }
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl<'de> serde_with::DeserializeAs<'de, DateTime> for FlexibleDateTime {
+        fn deserialize_as<D>(deserializer: D) -> Result<DateTime, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            FlexibleDateTime::deserialize(deserializer).map(|dt| dt.0)
+        }
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+    impl serde_with::SerializeAs<DateTime> for FlexibleDateTime {
+        fn serialize_as<S: Serializer>(
+            source: &DateTime,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            source.serialize(serializer)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl<'de> serde_with_3::DeserializeAs<'de, DateTime> for FlexibleDateTime {
+        fn deserialize_as<D>(deserializer: D) -> Result<DateTime, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            FlexibleDateTime::deserialize(deserializer).map(|dt| dt.0)
+        }
+    }
+
+    #[cfg(feature = "serde_with-3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_with-3")))]
+    impl serde_with_3::SerializeAs<DateTime> for FlexibleDateTime {
+        fn serialize_as<S: Serializer>(
+            source: &DateTime,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            source.serialize(serializer)
+        }
+    }
+}
+
 /// Wrapping a type in `HumanReadable` signals to the BSON serde integration that it and all
 /// recursively contained types should be handled as if
 /// [`SerializerOptions::human_readable`](crate::SerializerOptions::human_readable) and
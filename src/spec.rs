@@ -21,7 +21,12 @@
 
 //! Constants derived from the [BSON Specification Version 1.1](http://bsonspec.org/spec.html).
 
-use std::{convert::From, fmt};
+use std::{
+    convert::{From, TryFrom},
+    error,
+    fmt,
+    str::FromStr,
+};
 
 impl fmt::LowerHex for BinarySubtype {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -148,8 +153,122 @@ impl ElementType {
             _ => return None,
         })
     }
+
+    /// Returns the name of this element type, as used by MongoDB's
+    /// [`$type`](https://www.mongodb.com/docs/manual/reference/operator/query/type/) operator
+    /// (e.g. `"string"`, `"int"`, `"long"`, or `"objectId"`). This is also the
+    /// [`Display`](fmt::Display) output of the element type, and is accepted by its
+    /// [`TryFrom<&str>`] implementation.
+    ///
+    /// ```
+    /// use bson::spec::ElementType;
+    ///
+    /// assert_eq!(ElementType::EmbeddedDocument.name(), "object");
+    /// assert_eq!(ElementType::Decimal128.to_string(), "decimal");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            ElementType::Double => "double",
+            ElementType::String => "string",
+            ElementType::EmbeddedDocument => "object",
+            ElementType::Array => "array",
+            ElementType::Binary => "binData",
+            ElementType::Undefined => "undefined",
+            ElementType::ObjectId => "objectId",
+            ElementType::Boolean => "bool",
+            ElementType::DateTime => "date",
+            ElementType::Null => "null",
+            ElementType::RegularExpression => "regex",
+            ElementType::DbPointer => "dbPointer",
+            ElementType::JavaScriptCode => "javascript",
+            ElementType::Symbol => "symbol",
+            ElementType::JavaScriptCodeWithScope => "javascriptWithScope",
+            ElementType::Int32 => "int",
+            ElementType::Timestamp => "timestamp",
+            ElementType::Int64 => "long",
+            ElementType::Decimal128 => "decimal",
+            ElementType::MaxKey => "maxKey",
+            ElementType::MinKey => "minKey",
+        }
+    }
+}
+
+impl fmt::Display for ElementType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl TryFrom<&str> for ElementType {
+    type Error = UnknownElementTypeError;
+
+    /// Parses an element type from its [`ElementType::name`]. Names are case-sensitive.
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use bson::spec::ElementType;
+    ///
+    /// assert_eq!(ElementType::try_from("objectId"), Ok(ElementType::ObjectId));
+    /// assert!(ElementType::try_from("ObjectId").is_err());
+    /// ```
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        use self::ElementType::*;
+        Ok(match name {
+            "double" => Double,
+            "string" => String,
+            "object" => EmbeddedDocument,
+            "array" => Array,
+            "binData" => Binary,
+            "undefined" => Undefined,
+            "objectId" => ObjectId,
+            "bool" => Boolean,
+            "date" => DateTime,
+            "null" => Null,
+            "regex" => RegularExpression,
+            "dbPointer" => DbPointer,
+            "javascript" => JavaScriptCode,
+            "symbol" => Symbol,
+            "javascriptWithScope" => JavaScriptCodeWithScope,
+            "int" => Int32,
+            "timestamp" => Timestamp,
+            "long" => Int64,
+            "decimal" => Decimal128,
+            "maxKey" => MaxKey,
+            "minKey" => MinKey,
+            _ => {
+                return Err(UnknownElementTypeError {
+                    name: name.to_string(),
+                })
+            }
+        })
+    }
+}
+
+impl FromStr for ElementType {
+    type Err = UnknownElementTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Error returned when parsing an [`ElementType`] from a string that is not the name of any
+/// element type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnknownElementTypeError {
+    /// The unrecognized name.
+    pub name: String,
 }
 
+impl fmt::Display for UnknownElementTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown BSON element type name: {:?}", self.name)
+    }
+}
+
+impl error::Error for UnknownElementTypeError {}
+
 /// The available binary subtypes, plus a user-defined slot.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[non_exhaustive]
@@ -168,6 +287,34 @@ pub enum BinarySubtype {
     Reserved(u8),
 }
 
+impl BinarySubtype {
+    /// Returns whether this is the standard UUID subtype ([`BinarySubtype::Uuid`], `0x04`).
+    ///
+    /// ```
+    /// use bson::spec::BinarySubtype;
+    ///
+    /// assert!(BinarySubtype::Uuid.is_uuid());
+    /// assert!(!BinarySubtype::UuidOld.is_uuid());
+    /// ```
+    pub fn is_uuid(&self) -> bool {
+        matches!(self, BinarySubtype::Uuid)
+    }
+
+    /// Returns whether this is the legacy UUID subtype ([`BinarySubtype::UuidOld`], `0x03`), whose
+    /// byte order depends on the driver that wrote it. See
+    /// [`UuidRepresentation`](crate::uuid::UuidRepresentation) for the supported layouts.
+    ///
+    /// ```
+    /// use bson::spec::BinarySubtype;
+    ///
+    /// assert!(BinarySubtype::UuidOld.is_legacy_uuid());
+    /// assert!(!BinarySubtype::Uuid.is_legacy_uuid());
+    /// ```
+    pub fn is_legacy_uuid(&self) -> bool {
+        matches!(self, BinarySubtype::UuidOld)
+    }
+}
+
 impl From<BinarySubtype> for u8 {
     #[inline]
     fn from(t: BinarySubtype) -> u8 {
@@ -59,3 +59,195 @@ fn duration_since() {
     assert!(date1.checked_duration_since(date2).is_none());
     assert_eq!(date1.saturating_duration_since(date2), Duration::ZERO);
 }
+
+#[test]
+fn millis_roundtrip() {
+    for millis in [0, 1, -1, 1_600_000_000_123, i64::MIN, i64::MAX] {
+        let dt = crate::DateTime::from_millis(millis);
+        assert_eq!(dt.as_millis(), millis);
+        assert_eq!(dt.as_millis(), dt.timestamp_millis());
+    }
+}
+
+#[test]
+fn timestamp_datetime_conversion() {
+    use crate::{DateTime, Timestamp};
+
+    let ts = Timestamp {
+        time: 1_672_531_200,
+        increment: 42,
+    };
+    let dt = ts.to_datetime();
+    assert_eq!(dt.timestamp_millis(), 1_672_531_200_000);
+    assert_eq!(Timestamp::from_datetime(dt, 42), ts);
+
+    // sub-second precision is truncated
+    let dt = DateTime::from_millis(1_672_531_200_999);
+    assert_eq!(Timestamp::from_datetime(dt, 0).time, 1_672_531_200);
+
+    assert_eq!(
+        Timestamp::from_datetime(DateTime::from_millis(-1), 0).time,
+        0
+    );
+    assert_eq!(Timestamp::from_datetime(DateTime::MAX, 0).time, u32::MAX);
+    assert_eq!(
+        Timestamp {
+            time: u32::MAX,
+            increment: 0
+        }
+        .to_datetime()
+        .timestamp_millis(),
+        i64::from(u32::MAX) * 1000
+    );
+}
+
+#[test]
+fn rfc3339_with_precision() {
+    use crate::datetime::TimePrecision;
+
+    let cases = [
+        (0, "1970-01-01T00:00:00Z", "1970-01-01T00:00:00.000Z"),
+        (
+            1_672_531_200_007,
+            "2023-01-01T00:00:00Z",
+            "2023-01-01T00:00:00.007Z",
+        ),
+        // before the epoch, the milliseconds still count forward from the whole second
+        (-1, "1969-12-31T23:59:59Z", "1969-12-31T23:59:59.999Z"),
+    ];
+    for (millis, seconds, with_millis) in cases {
+        let dt = crate::DateTime::from_millis(millis);
+        assert_eq!(
+            dt.to_rfc3339_string_with_precision(TimePrecision::Seconds)
+                .unwrap(),
+            seconds
+        );
+        assert_eq!(
+            dt.to_rfc3339_string_with_precision(TimePrecision::Millis)
+                .unwrap(),
+            with_millis
+        );
+    }
+
+    let dt = crate::DateTime::from_millis(1_672_531_200_123);
+    assert_eq!(
+        dt.to_rfc3339_string_with_precision(TimePrecision::Nanos)
+            .unwrap(),
+        "2023-01-01T00:00:00.123000000Z"
+    );
+
+    // out-of-range datetimes return an error rather than panicking
+    for dt in [crate::DateTime::MIN, crate::DateTime::MAX] {
+        for precision in [
+            TimePrecision::Seconds,
+            TimePrecision::Millis,
+            TimePrecision::Micros,
+            TimePrecision::Nanos,
+        ] {
+            assert!(dt.to_rfc3339_string_with_precision(precision).is_err());
+        }
+    }
+}
+
+#[test]
+fn from_millis_saturating() {
+    use crate::DateTime;
+
+    assert_eq!(
+        DateTime::from_millis_saturating(0),
+        DateTime::from_millis(0)
+    );
+    assert_eq!(
+        DateTime::from_millis_saturating(i64::MAX as i128),
+        DateTime::MAX
+    );
+    assert_eq!(
+        DateTime::from_millis_saturating(i64::MAX as i128 + 1),
+        DateTime::MAX
+    );
+    assert_eq!(
+        DateTime::from_millis_saturating(i64::MIN as i128 - 1),
+        DateTime::MIN
+    );
+    assert_eq!(DateTime::MIN.timestamp_millis(), i64::MIN);
+    assert_eq!(DateTime::MAX.timestamp_millis(), i64::MAX);
+}
+
+#[test]
+fn from_ymd_hms_milli() {
+    use crate::DateTime;
+
+    assert_eq!(
+        DateTime::from_ymd_hms_milli(1970, 1, 1, 0, 0, 0, 0).unwrap(),
+        DateTime::from_millis(0)
+    );
+    assert_eq!(
+        DateTime::from_ymd_hms_milli(2020, 6, 9, 10, 58, 7, 95).unwrap(),
+        DateTime::parse_rfc3339_str("2020-06-09T10:58:07.095Z").unwrap()
+    );
+    assert_eq!(
+        DateTime::from_ymd_hms_milli(1969, 12, 31, 23, 59, 59, 999)
+            .unwrap()
+            .timestamp_millis(),
+        -1
+    );
+
+    // leap years
+    assert!(DateTime::from_ymd_hms_milli(2000, 2, 29, 0, 0, 0, 0).is_ok());
+    assert!(DateTime::from_ymd_hms_milli(2024, 2, 29, 0, 0, 0, 0).is_ok());
+    assert!(DateTime::from_ymd_hms_milli(1900, 2, 29, 0, 0, 0, 0).is_err());
+    assert!(DateTime::from_ymd_hms_milli(2023, 2, 29, 0, 0, 0, 0).is_err());
+
+    // out-of-range components
+    assert!(DateTime::from_ymd_hms_milli(2023, 0, 1, 0, 0, 0, 0).is_err());
+    assert!(DateTime::from_ymd_hms_milli(2023, 13, 1, 0, 0, 0, 0).is_err());
+    assert!(DateTime::from_ymd_hms_milli(2023, 4, 31, 0, 0, 0, 0).is_err());
+    assert!(DateTime::from_ymd_hms_milli(2023, 1, 0, 0, 0, 0, 0).is_err());
+    assert!(DateTime::from_ymd_hms_milli(2023, 1, 1, 24, 0, 0, 0).is_err());
+    assert!(DateTime::from_ymd_hms_milli(2023, 1, 1, 0, 60, 0, 0).is_err());
+    assert!(DateTime::from_ymd_hms_milli(2023, 1, 1, 0, 0, 60, 0).is_err());
+    assert!(DateTime::from_ymd_hms_milli(2023, 1, 1, 0, 0, 0, 1000).is_err());
+}
+
+#[test]
+#[cfg(feature = "chrono-0_4")]
+fn chrono_comparison() {
+    use crate::DateTime;
+    use chrono::TimeZone;
+
+    let bson_dt = DateTime::from_millis(1_500);
+    let chrono_dt = chrono::Utc.timestamp_millis_opt(1_500).unwrap();
+    assert!(bson_dt == chrono_dt);
+    assert!(chrono_dt == bson_dt);
+
+    // Sub-millisecond precision is truncated before comparing.
+    let precise = chrono_dt + chrono::Duration::microseconds(999);
+    assert!(bson_dt == precise);
+    assert!(precise == bson_dt);
+
+    let later = chrono_dt + chrono::Duration::milliseconds(1);
+    assert!(bson_dt < later);
+    assert!(later > bson_dt);
+    assert!(bson_dt != later);
+}
+
+#[test]
+#[cfg(feature = "time-0_3")]
+fn time_comparison() {
+    use crate::DateTime;
+
+    let bson_dt = DateTime::from_millis(1_500);
+    let time_dt = time::OffsetDateTime::from_unix_timestamp_nanos(1_500_000_000).unwrap();
+    assert!(bson_dt == time_dt);
+    assert!(time_dt == bson_dt);
+
+    // Sub-millisecond precision is truncated before comparing.
+    let precise = time_dt + time::Duration::microseconds(999);
+    assert!(bson_dt == precise);
+    assert!(precise == bson_dt);
+
+    let earlier = time_dt - time::Duration::milliseconds(1);
+    assert!(bson_dt > earlier);
+    assert!(earlier < bson_dt);
+    assert!(bson_dt != earlier);
+}
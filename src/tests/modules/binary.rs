@@ -1,4 +1,14 @@
-use crate::{spec::BinarySubtype, tests::LOCK, Binary};
+use std::convert::TryFrom;
+
+use crate::{
+    binary::PackedBitVector,
+    doc,
+    spec::BinarySubtype,
+    tests::LOCK,
+    Binary,
+    Bson,
+    Document,
+};
 
 #[test]
 fn binary_from_base64() {
@@ -19,3 +29,115 @@ fn binary_from_base64() {
     };
     assert_eq!(produced, expected);
 }
+
+#[test]
+fn binary_to_base64() {
+    let _guard = LOCK.run_concurrently();
+
+    let binary = Binary {
+        bytes: vec![0, 1, 2, 253, 254, 255],
+        subtype: BinarySubtype::UserDefined(0x80),
+    };
+    let encoded = binary.to_base64();
+    assert_eq!(encoded, "AAEC/f7/");
+    assert_eq!(
+        Binary::from_base64(&encoded, BinarySubtype::UserDefined(0x80)).unwrap(),
+        binary
+    );
+
+    let empty = Binary {
+        bytes: Vec::new(),
+        subtype: BinarySubtype::Generic,
+    };
+    assert_eq!(empty.to_base64(), "");
+
+    assert!(Binary::from_base64("not base64!", None).is_err());
+}
+
+#[test]
+fn binary_function_subtype_roundtrip() {
+    let _guard = LOCK.run_concurrently();
+
+    assert_eq!(u8::from(BinarySubtype::Function), 0x01);
+    assert_eq!(BinarySubtype::from(0x01), BinarySubtype::Function);
+
+    let binary = Binary {
+        bytes: vec![1, 2, 3],
+        subtype: BinarySubtype::Function,
+    };
+    let doc = doc! { "f": binary.clone() };
+
+    let bytes = crate::to_vec(&doc).unwrap();
+    let decoded: Document = crate::from_slice(&bytes).unwrap();
+    assert_eq!(decoded.get("f"), Some(&Bson::Binary(binary.clone())));
+
+    let extjson = Bson::Binary(binary.clone()).into_canonical_extjson();
+    assert_eq!(extjson["$binary"]["subType"], "01");
+    let parsed = Bson::try_from(extjson).unwrap();
+    assert_eq!(parsed, Bson::Binary(binary));
+}
+
+#[test]
+fn binary_debug_truncated() {
+    let _guard = LOCK.run_concurrently();
+
+    let binary = Binary {
+        bytes: vec![1, 2, 3, 4, 5],
+        subtype: BinarySubtype::Md5,
+    };
+    assert_eq!(
+        format!("{:?}", binary.debug_truncated(2)),
+        "Binary { subtype: Md5, bytes: [1, 2, ... (3 more)] }"
+    );
+    assert_eq!(
+        format!("{:?}", binary.debug_truncated(5)),
+        format!("{:?}", binary)
+    );
+    assert_eq!(
+        format!("{:?}", binary.debug_truncated(100)),
+        format!("{:?}", binary)
+    );
+    assert_eq!(
+        format!("{:?}", binary.debug_truncated(0)),
+        "Binary { subtype: Md5, bytes: [... (5 more)] }"
+    );
+}
+
+#[test]
+fn vector_helpers() {
+    let _guard = LOCK.run_concurrently();
+
+    let floats = Binary::from_f32_vector(&[1.0, -0.5, 3.25]);
+    assert_eq!(floats.subtype, BinarySubtype::Vector);
+    assert_eq!(&floats.bytes[..2], &[0x27, 0]);
+    assert_eq!(floats.to_f32_vector().unwrap(), vec![1.0, -0.5, 3.25]);
+    assert!(floats.to_int8_vector().is_err());
+
+    let ints = Binary::from_int8_vector(&[-1, 0, 127]);
+    assert_eq!(ints.bytes, vec![0x03, 0, 0xFF, 0, 0x7F]);
+    assert_eq!(ints.to_int8_vector().unwrap(), vec![-1, 0, 127]);
+    assert!(ints.to_f32_vector().is_err());
+
+    let bits = PackedBitVector::new(vec![0b1110_0000], 4).unwrap();
+    let packed = Binary::from_packed_bit_vector(bits.clone());
+    assert_eq!(packed.bytes, vec![0x10, 4, 0b1110_0000]);
+    assert_eq!(packed.to_packed_bit_vector().unwrap(), bits);
+    assert!(packed.to_f32_vector().is_err());
+
+    let generic = Binary {
+        subtype: BinarySubtype::Generic,
+        bytes: vec![0x27, 0],
+    };
+    assert!(generic.to_f32_vector().is_err());
+}
+
+#[test]
+fn binary_subtype_uuid_predicates() {
+    let _guard = LOCK.run_concurrently();
+
+    for byte in 0..=u8::MAX {
+        let subtype = BinarySubtype::from(byte);
+        assert_eq!(subtype.is_uuid(), byte == 4, "{:?}", subtype);
+        assert_eq!(subtype.is_legacy_uuid(), byte == 3, "{:?}", subtype);
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    cmp::Ordering,
     convert::TryFrom,
     time::{Duration, SystemTime},
 };
@@ -6,11 +7,12 @@ use std::{
 use crate::{
     doc,
     oid::ObjectId,
-    spec::BinarySubtype,
+    spec::{BinarySubtype, ElementType},
     tests::LOCK,
     Binary,
     Bson,
     DateTime,
+    Decimal128,
     Document,
     JavaScriptCodeWithScope,
     Regex,
@@ -502,3 +504,855 @@ fn test_hashable() {
 
     assert!(map.is_empty());
 }
+
+#[cfg(feature = "hashable")]
+#[test]
+fn test_hashable_doubles() {
+    use std::collections::HashSet;
+
+    let _guard = LOCK.run_concurrently();
+
+    let mut set = HashSet::new();
+    assert!(set.insert(Bson::Double(0.0)));
+    assert!(!set.insert(Bson::Double(-0.0)));
+    assert!(set.contains(&Bson::Double(-0.0)));
+
+    // NaN can be hashed, but is never equal to itself and so is never found again.
+    assert!(set.insert(Bson::Double(f64::NAN)));
+    assert!(set.insert(Bson::Double(f64::NAN)));
+    assert!(!set.contains(&Bson::Double(f64::NAN)));
+    assert_eq!(set.len(), 3);
+
+    let mut docs = HashSet::new();
+    docs.insert(doc! { "a": 1, "b": [1.5, "x"] });
+    assert!(docs.contains(&doc! { "b": [1.5, "x"], "a": 1 }));
+    assert!(!docs.contains(&doc! { "a": 1, "b": ["x", 1.5] }));
+}
+
+#[test]
+fn pointer() {
+    let _guard = LOCK.run_concurrently();
+
+    let value = bson!({
+        "foo": ["bar", "baz"],
+        "": 0,
+        "a/b": 1,
+        "m~n": 2,
+        "nested": { "arr": [{ "x": true }] },
+    });
+
+    assert_eq!(value.pointer(""), Some(&value));
+    assert_eq!(value.pointer("/foo"), Some(&bson!(["bar", "baz"])));
+    assert_eq!(value.pointer("/foo/0"), Some(&Bson::String("bar".into())));
+    assert_eq!(value.pointer("/"), Some(&Bson::Int32(0)));
+    assert_eq!(value.pointer("/a~1b"), Some(&Bson::Int32(1)));
+    assert_eq!(value.pointer("/m~0n"), Some(&Bson::Int32(2)));
+    assert_eq!(value.pointer("/nested/arr/0/x"), Some(&Bson::Boolean(true)));
+
+    assert_eq!(value.pointer("foo"), None);
+    assert_eq!(value.pointer("/foo/2"), None);
+    assert_eq!(value.pointer("/foo/01"), None);
+    assert_eq!(value.pointer("/foo/+1"), None);
+    assert_eq!(value.pointer("/foo/0/bar"), None);
+    assert_eq!(value.pointer("/missing"), None);
+}
+
+#[test]
+fn from_str() {
+    let _guard = LOCK.run_concurrently();
+
+    let oid = ObjectId::new();
+    let parsed: Bson = format!(
+        r#"{{ "_id": {{ "$oid": "{}" }}, "n": {{ "$numberLong": "5" }}, "arr": [1, "two"] }}"#,
+        oid.to_hex()
+    )
+    .parse()
+    .unwrap();
+    assert_eq!(parsed, bson!({ "_id": oid, "n": 5_i64, "arr": [1, "two"] }));
+
+    assert_eq!("true".parse::<Bson>().unwrap(), Bson::Boolean(true));
+    assert!("{ \"a\": ".parse::<Bson>().is_err());
+    assert!(r#"{ "$oid": "not hex" }"#.parse::<Bson>().is_err());
+}
+
+#[test]
+fn decimal128_constants() {
+    let _guard = LOCK.run_concurrently();
+
+    let parse = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!(Decimal128::ZERO, parse("0"));
+    assert_eq!(Decimal128::ONE, parse("1"));
+    assert_eq!(Decimal128::NAN, parse("NaN"));
+    assert_eq!(Decimal128::POSITIVE_INFINITY, parse("Infinity"));
+    assert_eq!(Decimal128::NEGATIVE_INFINITY, parse("-Infinity"));
+
+    assert_eq!(Decimal128::ZERO.to_string(), "0");
+    assert_eq!(Decimal128::ONE.to_string(), "1");
+    assert_eq!(Decimal128::NAN.to_string(), "NaN");
+    assert_eq!(Decimal128::POSITIVE_INFINITY.to_string(), "Infinity");
+    assert_eq!(Decimal128::NEGATIVE_INFINITY.to_string(), "-Infinity");
+}
+
+#[test]
+fn decimal128_f64_conversion() {
+    let _guard = LOCK.run_concurrently();
+
+    for value in [
+        0.0,
+        1.0,
+        -2.5,
+        0.1,
+        1.0 / 3.0,
+        1e-7,
+        123456789.125,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        5e-324,
+    ] {
+        let dec = Decimal128::from_f64(value);
+        assert_eq!(dec.to_f64(), value, "{}", dec);
+    }
+    assert_eq!(Decimal128::from_f64(0.1).to_string(), "0.1");
+    assert_eq!(
+        Decimal128::from_f64(-0.0).to_f64().to_bits(),
+        (-0.0f64).to_bits()
+    );
+
+    assert_eq!(Decimal128::from_f64(f64::NAN), Decimal128::NAN);
+    assert_eq!(
+        Decimal128::from_f64(f64::INFINITY),
+        Decimal128::POSITIVE_INFINITY
+    );
+    assert_eq!(
+        Decimal128::from_f64(f64::NEG_INFINITY),
+        Decimal128::NEGATIVE_INFINITY
+    );
+    assert!(Decimal128::NAN.to_f64().is_nan());
+    assert_eq!(Decimal128::POSITIVE_INFINITY.to_f64(), f64::INFINITY);
+    assert_eq!(Decimal128::NEGATIVE_INFINITY.to_f64(), f64::NEG_INFINITY);
+
+    // Values beyond the range of f64 saturate.
+    assert_eq!(
+        "1E+400".parse::<Decimal128>().unwrap().to_f64(),
+        f64::INFINITY
+    );
+    assert_eq!("1E-400".parse::<Decimal128>().unwrap().to_f64(), 0.0);
+}
+
+#[test]
+fn decimal128_predicates() {
+    let _guard = LOCK.run_concurrently();
+
+    let parse = |s: &str| s.parse::<Decimal128>().unwrap();
+    let with_high_byte = |high: u8| {
+        let mut bytes = [0; 16];
+        bytes[15] = high;
+        Decimal128::from_bytes(bytes)
+    };
+
+    // (value, nan, infinite, zero, negative)
+    let cases = [
+        (Decimal128::ZERO, false, false, true, false),
+        (parse("-0"), false, false, true, true),
+        (parse("0E+100"), false, false, true, false),
+        (Decimal128::ONE, false, false, false, false),
+        (parse("-1.5E-20"), false, false, false, true),
+        (
+            parse("9.999999999999999999999999999999999E+6144"),
+            false,
+            false,
+            false,
+            false,
+        ),
+        (Decimal128::NAN, true, false, false, false),
+        (with_high_byte(0xFC), true, false, false, true),
+        (with_high_byte(0x7E), true, false, false, false),
+        (Decimal128::POSITIVE_INFINITY, false, true, false, false),
+        (Decimal128::NEGATIVE_INFINITY, false, true, false, true),
+    ];
+    for (value, nan, infinite, zero, negative) in cases {
+        assert_eq!(value.is_nan(), nan, "{}", value);
+        assert_eq!(value.is_infinite(), infinite, "{}", value);
+        assert_eq!(value.is_finite(), !nan && !infinite, "{}", value);
+        assert_eq!(value.is_zero(), zero, "{}", value);
+        assert_eq!(value.is_negative(), negative, "{}", value);
+    }
+
+    // Coefficients larger than 34 digits are non-canonical and treated as zero.
+    let mut bytes = [0xFF; 16];
+    bytes[15] = 0x6F;
+    let non_canonical = Decimal128::from_bytes(bytes);
+    assert!(non_canonical.is_finite());
+    assert!(non_canonical.is_zero());
+}
+
+#[test]
+fn regex_extjson_sorted_options() {
+    let _guard = LOCK.run_concurrently();
+
+    let regex = Bson::RegularExpression(Regex {
+        pattern: "abc".to_string(),
+        options: "xmi".to_string(),
+    });
+    let expected = json!({ "$regularExpression": { "pattern": "abc", "options": "imx" } });
+
+    let canonical = regex.clone().into_canonical_extjson();
+    assert_eq!(canonical, expected);
+    assert_eq!(regex.clone().into_relaxed_extjson(), expected);
+
+    let sorted = Bson::RegularExpression(Regex {
+        pattern: "abc".to_string(),
+        options: "imx".to_string(),
+    });
+    assert_eq!(Bson::try_from(canonical).unwrap(), sorted);
+
+    let bytes = crate::to_vec(&doc! { "r": regex }).unwrap();
+    let decoded: Document = crate::from_slice(&bytes).unwrap();
+    assert_eq!(decoded.get("r"), Some(&sorted));
+}
+
+#[test]
+fn walk() {
+    use crate::PathSegment::*;
+
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! { "a": 1, "b": { "c": ["x", { "d": true }] } };
+    let mut visited = Vec::new();
+    doc.walk(|path, value| visited.push((path.to_vec(), value.element_type())));
+
+    assert_eq!(
+        visited,
+        vec![
+            (vec![Key("a")], ElementType::Int32),
+            (vec![Key("b")], ElementType::EmbeddedDocument),
+            (vec![Key("b"), Key("c")], ElementType::Array),
+            (vec![Key("b"), Key("c"), Index(0)], ElementType::String),
+            (
+                vec![Key("b"), Key("c"), Index(1)],
+                ElementType::EmbeddedDocument
+            ),
+            (
+                vec![Key("b"), Key("c"), Index(1), Key("d")],
+                ElementType::Boolean
+            ),
+        ]
+    );
+
+    let mut count = 0;
+    Bson::Int32(5).walk(|path, _| {
+        assert!(path.is_empty());
+        count += 1;
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn into_document_and_array() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! { "a": [1, "b"] };
+    let value = Bson::Document(doc.clone());
+    assert_eq!(value.into_document().unwrap(), doc);
+
+    let arr = Bson::Array(vec![Bson::Int32(1), Bson::Null]);
+    assert_eq!(
+        arr.clone().into_array().unwrap(),
+        vec![Bson::Int32(1), Bson::Null]
+    );
+
+    let err = arr.into_document().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: sequence, expected a document"
+    );
+
+    let err = Bson::String("x".to_string()).into_array().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: string \"x\", expected an array"
+    );
+}
+
+#[test]
+fn as_i32_checked() {
+    let _guard = LOCK.run_concurrently();
+
+    assert_eq!(Bson::Int32(-3).as_i32_checked(), Some(-3));
+    assert_eq!(
+        Bson::Int64(i32::MAX as i64).as_i32_checked(),
+        Some(i32::MAX)
+    );
+    assert_eq!(
+        Bson::Int64(i32::MIN as i64).as_i32_checked(),
+        Some(i32::MIN)
+    );
+    assert_eq!(Bson::Int64(i32::MAX as i64 + 1).as_i32_checked(), None);
+    assert_eq!(Bson::Double(-7.0).as_i32_checked(), Some(-7));
+    assert_eq!(Bson::Double(2147483648.0).as_i32_checked(), None);
+    assert_eq!(Bson::Double(0.5).as_i32_checked(), None);
+    assert_eq!(Bson::Double(f64::NAN).as_i32_checked(), None);
+    assert_eq!(Bson::String("1".to_string()).as_i32_checked(), None);
+
+    assert_eq!(Bson::Int64(1).as_i32(), None);
+}
+
+#[test]
+fn type_name() {
+    let _guard = LOCK.run_concurrently();
+
+    let cases = [
+        (Bson::Double(1.5), "double"),
+        (Bson::String("x".to_string()), "string"),
+        (Bson::Document(doc! {}), "object"),
+        (Bson::Array(vec![]), "array"),
+        (
+            Bson::Binary(Binary {
+                subtype: BinarySubtype::Generic,
+                bytes: vec![],
+            }),
+            "binData",
+        ),
+        (Bson::Undefined, "undefined"),
+        (Bson::ObjectId(ObjectId::new()), "objectId"),
+        (Bson::Boolean(true), "bool"),
+        (Bson::DateTime(DateTime::now()), "date"),
+        (Bson::Null, "null"),
+        (Bson::RegularExpression(Regex::new("a", "i")), "regex"),
+        (Bson::JavaScriptCode("x".to_string()), "javascript"),
+        (Bson::Symbol("x".to_string()), "symbol"),
+        (Bson::Int32(1), "int"),
+        (
+            Bson::Timestamp(Timestamp {
+                time: 1,
+                increment: 1,
+            }),
+            "timestamp",
+        ),
+        (Bson::Int64(1), "long"),
+        (Bson::Decimal128(Decimal128::ZERO), "decimal"),
+        (Bson::MaxKey, "maxKey"),
+        (Bson::MinKey, "minKey"),
+    ];
+    for (value, name) in cases {
+        assert_eq!(value.type_name(), name, "{:?}", value);
+        assert_eq!(value.element_type().to_string(), name, "{:?}", value);
+        assert_eq!(ElementType::try_from(name), Ok(value.element_type()));
+        assert_eq!(name.parse::<ElementType>(), Ok(value.element_type()));
+    }
+
+    for element_type in [ElementType::DbPointer, ElementType::JavaScriptCodeWithScope] {
+        assert_eq!(ElementType::try_from(element_type.name()), Ok(element_type));
+    }
+
+    for name in ["number", "Int", "objectid", ""] {
+        let err = ElementType::try_from(name).unwrap_err();
+        assert_eq!(err.name, name);
+    }
+}
+
+#[test]
+fn matches_type() {
+    let _guard = LOCK.run_concurrently();
+
+    let cases = [
+        (Bson::Double(1.5), "double", "1"),
+        (Bson::String("x".to_string()), "string", "2"),
+        (Bson::Document(doc! {}), "object", "3"),
+        (Bson::Array(vec![]), "array", "4"),
+        (Bson::Undefined, "undefined", "6"),
+        (Bson::ObjectId(ObjectId::new()), "objectId", "7"),
+        (Bson::Boolean(true), "bool", "8"),
+        (Bson::DateTime(DateTime::now()), "date", "9"),
+        (Bson::Null, "null", "10"),
+        (Bson::RegularExpression(Regex::new("a", "i")), "regex", "11"),
+        (Bson::JavaScriptCode("x".to_string()), "javascript", "13"),
+        (Bson::Symbol("x".to_string()), "symbol", "14"),
+        (Bson::Int32(1), "int", "16"),
+        (Bson::Int64(1), "long", "18"),
+        (Bson::Decimal128(Decimal128::ZERO), "decimal", "19"),
+        (Bson::MinKey, "minKey", "-1"),
+        (Bson::MaxKey, "maxKey", "127"),
+    ];
+    for (value, name, code) in &cases {
+        assert!(value.matches_type(name), "{:?}", value);
+        assert!(value.matches_type(code), "{:?}", value);
+        for (other, other_name, _) in &cases {
+            if other_name != name {
+                assert!(!other.matches_type(name), "{:?} {}", other, name);
+                assert!(!other.matches_type(code), "{:?} {}", other, code);
+            }
+        }
+    }
+
+    for value in [
+        Bson::Double(1.5),
+        Bson::Int32(1),
+        Bson::Int64(1),
+        Bson::Decimal128(Decimal128::ZERO),
+    ] {
+        assert!(value.matches_type("number"), "{:?}", value);
+    }
+    assert!(!Bson::String("1".to_string()).matches_type("number"));
+    assert!(!Bson::Boolean(true).matches_type("number"));
+
+    assert!(!Bson::Int32(1).matches_type("integer"));
+    assert!(!Bson::Int32(1).matches_type("Int"));
+    assert!(!Bson::MinKey.matches_type("255"));
+    assert!(!Bson::Double(1.5).matches_type("0"));
+}
+
+#[test]
+fn portable() {
+    let _guard = LOCK.run_concurrently();
+
+    let oid = ObjectId::new();
+    let dt = DateTime::from_millis(1_672_531_200_000);
+    let binary = Binary {
+        subtype: BinarySubtype::Md5,
+        bytes: vec![1, 2, 3],
+    };
+    let decimal: Decimal128 = "1.5".parse().unwrap();
+
+    let original = Bson::Document(doc! {
+        "_id": oid,
+        "nested": { "when": dt, "blobs": [binary.clone()] },
+        "price": decimal,
+        "plain": { "a": 1, "b": "x" },
+    });
+    let portable = original.clone().to_portable();
+    assert_eq!(
+        portable,
+        Bson::Document(doc! {
+            "_id": { "__bson_type": "objectId", "value": oid.to_hex() },
+            "nested": {
+                "when": { "__bson_type": "date", "value": 1_672_531_200_000_i64 },
+                "blobs": [{ "__bson_type": "binData", "value": "AQID", "subtype": 5 }],
+            },
+            "price": { "__bson_type": "decimal", "value": "1.5" },
+            "plain": { "a": 1, "b": "x" },
+        })
+    );
+    assert_eq!(Bson::from_portable(portable.clone()).unwrap(), original);
+
+    // Formats other than BSON narrow integers that fit in 32 bits.
+    let json = serde_json::to_string(&portable).unwrap();
+    let from_json: Bson = serde_json::from_str(&json).unwrap();
+    assert_eq!(Bson::from_portable(from_json).unwrap(), original);
+
+    let small = Bson::Document(doc! {
+        "when": DateTime::from_millis(1_000),
+        "blob": binary.clone(),
+    });
+    let json = serde_json::to_string(&small.clone().to_portable()).unwrap();
+    let from_json: Bson = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        from_json,
+        Bson::Document(doc! {
+            "when": { "__bson_type": "date", "value": 1_000 },
+            "blob": { "__bson_type": "binData", "value": "AQID", "subtype": 5 },
+        })
+    );
+    assert_eq!(Bson::from_portable(from_json).unwrap(), small);
+
+    // Documents that already use the type key are escaped.
+    let tagged = Bson::Document(doc! {
+        "t": { "__bson_type": "tag", "value": 1 },
+        "oid": { "__bson_type": "objectId", "value": oid },
+    });
+    let portable = tagged.clone().to_portable();
+    assert_eq!(
+        portable,
+        Bson::Document(doc! {
+            "t": {
+                "__bson_type": "object",
+                "value": { "__bson_type": "tag", "value": 1 },
+            },
+            "oid": {
+                "__bson_type": "object",
+                "value": {
+                    "__bson_type": "objectId",
+                    "value": { "__bson_type": "objectId", "value": oid.to_hex() },
+                },
+            },
+        })
+    );
+    let json = serde_json::to_string(&portable).unwrap();
+    let from_json: Bson = serde_json::from_str(&json).unwrap();
+    assert_eq!(Bson::from_portable(from_json).unwrap(), tagged);
+
+    let invalid = [
+        doc! { "__bson_type": "objectId", "value": "xyz" },
+        doc! { "__bson_type": "objectId", "value": oid.to_hex(), "extra": 1 },
+        doc! { "__bson_type": "date", "value": "now" },
+        doc! { "__bson_type": "binData", "value": "AQID" },
+        doc! { "__bson_type": "binData", "value": "AQID", "subtype": 256 },
+        doc! { "__bson_type": "object", "value": 1 },
+        doc! { "__bson_type": "object", "value": {}, "extra": 1 },
+        doc! { "__bson_type": "unknown", "value": 1 },
+    ];
+    for doc in invalid {
+        assert!(
+            Bson::from_portable(Bson::Document(doc! { "a": [doc.clone()] })).is_err(),
+            "{}",
+            doc
+        );
+    }
+}
+
+#[test]
+fn approx_eq() {
+    let _guard = LOCK.run_concurrently();
+
+    let a = Bson::Document(doc! { "x": 1.0, "y": [2.0, { "z": 3.0 }], "s": "str" });
+    let b = Bson::Document(doc! { "y": [2.0005, { "z": 2.9995 }], "x": 1.0001, "s": "str" });
+    assert!(a.approx_eq(&b, 1e-3));
+    assert!(!a.approx_eq(&b, 1e-5));
+
+    assert!(Bson::Double(f64::INFINITY).approx_eq(&Bson::Double(f64::INFINITY), 0.0));
+    assert!(!Bson::Double(f64::NAN).approx_eq(&Bson::Double(f64::NAN), 1.0));
+
+    // non-float types are compared exactly
+    assert!(!Bson::Int32(1).approx_eq(&Bson::Int32(2), 10.0));
+    assert!(!Bson::Int32(1).approx_eq(&Bson::Double(1.0), 10.0));
+    assert!(!Bson::Array(vec![Bson::Double(1.0)]).approx_eq(&Bson::Array(vec![]), 1.0));
+    assert!(!Bson::Document(doc! { "a": 1.0 }).approx_eq(&Bson::Document(doc! { "b": 1.0 }), 1.0));
+}
+
+#[test]
+fn redact() {
+    let _guard = LOCK.run_concurrently();
+
+    let mut value = Bson::Document(doc! {
+        "user": "alice",
+        "password": { "hash": "abc" },
+        "sessions": [{ "id": 1, "refresh_token": "xyz" }, 5],
+        "config": { "API-Key": "k", "region": "us" },
+    });
+    value.redact(Bson::is_sensitive_key);
+    assert_eq!(
+        value,
+        Bson::Document(doc! {
+            "user": "alice",
+            "password": "***",
+            "sessions": [{ "id": 1, "refresh_token": "***" }, 5],
+            "config": { "API-Key": "***", "region": "us" },
+        })
+    );
+
+    let mut doc = doc! { "a": 1, "b": { "a": 2 } };
+    doc.redact(|key| key == "a");
+    assert_eq!(doc, doc! { "a": "***", "b": { "a": "***" } });
+
+    let mut scalar = Bson::String("password".to_string());
+    scalar.redact(|_| true);
+    assert_eq!(scalar, Bson::String("password".to_string()));
+}
+
+#[test]
+fn code_with_scope_encoding() {
+    let _guard = LOCK.run_concurrently();
+
+    let code = JavaScriptCodeWithScope::new("function() { return x; }", doc! { "x": 1 });
+    let bytes = code.try_to_vec().unwrap();
+    assert_eq!(code.encoded_len().unwrap(), bytes.len());
+    assert_eq!(
+        i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+        bytes.len()
+    );
+
+    // The helper's output must match the value bytes produced when encoding a full document.
+    let doc = doc! { "c": code.clone() };
+    let encoded = crate::to_vec(&doc).unwrap();
+    let value_start = 4 + 1 + 2;
+    assert_eq!(
+        &encoded[value_start..value_start + bytes.len()],
+        bytes.as_slice()
+    );
+
+    let too_big = JavaScriptCodeWithScope::new("x".repeat(16 * 1024 * 1024), doc! {});
+    assert!(too_big.try_to_vec().is_err());
+    assert!(too_big.encoded_len().is_err());
+
+    let invalid_scope = JavaScriptCodeWithScope::new("x", doc! { "a\0b": 1 });
+    assert!(invalid_scope.encoded_len().is_err());
+    assert!(invalid_scope.try_to_vec().is_err());
+}
+
+#[test]
+fn node_count_and_max_depth() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! {
+        "a": 1,
+        "b": { "c": [1, 2, { "d": null }] },
+        "e": JavaScriptCodeWithScope::new("f()", doc! { "g": { "h": true } }),
+    };
+    // a, b, c, 1, 2, {d}, d, e, g, h
+    assert_eq!(doc.node_count(), 10);
+    assert_eq!(Bson::Document(doc.clone()).node_count(), 11);
+    assert_eq!(doc.max_depth(), 4);
+    assert_eq!(Bson::Document(doc.clone()).max_depth(), 4);
+
+    assert_eq!(Document::new().node_count(), 0);
+    assert_eq!(Document::new().max_depth(), 1);
+    assert_eq!(Bson::Array(vec![]).max_depth(), 1);
+    assert_eq!(Bson::Int32(1).max_depth(), 0);
+
+    // The depth agrees with the limit enforced by raw validation.
+    let raw = crate::RawDocumentBuf::from_document(&doc).unwrap();
+    let options = |depth| {
+        crate::raw::ValidationOptions::builder()
+            .max_depth(depth)
+            .build()
+    };
+    assert!(raw.validate(options(4)).is_ok());
+    assert!(raw.validate(options(3)).is_err());
+}
+
+#[test]
+fn extjson_string() {
+    let _guard = LOCK.run_concurrently();
+
+    let value = bson!({
+        "int32": 1,
+        "int64": 2_i64,
+        "doubles": [1.0, -0.0, 0.0, 1.5, f64::NAN, f64::NEG_INFINITY, 5e-324, 1e300],
+        "string": "quote \" and \u{1F980}",
+        "null": null,
+        "bool": false,
+        "regex": Regex { pattern: "^a".to_string(), options: "xi".to_string() },
+        "code": Bson::JavaScriptCode("f()".to_string()),
+        "code_w_scope": JavaScriptCodeWithScope::new("g()", doc! { "x": 1, "y": [2.5] }),
+        "timestamp": Timestamp { time: 1, increment: 2 },
+        "binary": Binary { subtype: BinarySubtype::Uuid, bytes: vec![1, 2, 3] },
+        "oid": ObjectId::new(),
+        "dates": [DateTime::from_millis(1_000), DateTime::from_millis(-1), DateTime::MAX],
+        "symbol": Bson::Symbol("s".to_string()),
+        "decimal": "1.5".parse::<Decimal128>().unwrap(),
+        "undefined": Bson::Undefined,
+        "min": Bson::MinKey,
+        "max": Bson::MaxKey,
+        "db_pointer": Bson::DbPointer(crate::DbPointer {
+            namespace: "db.coll".to_string(),
+            id: ObjectId::new(),
+        }),
+        "nested": { "a": { "b": [{ "c": 1 }] } },
+    });
+
+    assert_eq!(
+        value.to_relaxed_extjson_string(),
+        value.clone().into_relaxed_extjson().to_string()
+    );
+    assert_eq!(
+        value.to_canonical_extjson_string(),
+        value.clone().into_canonical_extjson().to_string()
+    );
+}
+
+#[test]
+fn array_helpers() {
+    let _guard = LOCK.run_concurrently();
+
+    let mut value = bson!([1, "two"]);
+    assert_eq!(value.array_len(), Some(2));
+    assert_eq!(value.array_get(1), Some(&Bson::String("two".to_string())));
+    assert_eq!(value.array_get(2), None);
+
+    value.array_push(3.0).unwrap();
+    value.array_push(doc! { "four": 4 }).unwrap();
+    assert_eq!(value, bson!([1, "two", 3.0, { "four": 4 }]));
+    assert_eq!(value.array_len(), Some(4));
+
+    let mut not_array = bson!({ "a": 1 });
+    let err = not_array.array_push(1).unwrap_err();
+    assert_eq!(err.expected, ElementType::Array);
+    assert_eq!(err.actual, ElementType::EmbeddedDocument);
+    assert_eq!(
+        err.to_string(),
+        "expected a value of type array, found object"
+    );
+    assert_eq!(not_array, bson!({ "a": 1 }));
+    assert_eq!(not_array.array_get(0), None);
+    assert_eq!(not_array.array_len(), None);
+}
+
+#[test]
+fn into_document_iter() {
+    let _guard = LOCK.run_concurrently();
+
+    let value = bson!({ "x": 1, "y": { "z": [true] } });
+    let mut iter = value.into_document_iter().unwrap();
+    assert_eq!(iter.next(), Some(("x".to_string(), bson!(1))));
+    assert_eq!(iter.next(), Some(("y".to_string(), bson!({ "z": [true] }))));
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(bson!({}).into_document_iter().unwrap().count(), 0);
+
+    let err = bson!([1, 2]).into_document_iter().err().unwrap();
+    assert!(err.to_string().contains("a document"), "{}", err);
+    assert!(Bson::Null.into_document_iter().is_err());
+}
+
+#[test]
+fn sort_array() {
+    let _guard = LOCK.run_concurrently();
+
+    let mut value = bson!([3, 1, 2]);
+    value
+        .sort_array(|a, b| a.as_i32().cmp(&b.as_i32()).reverse())
+        .unwrap();
+    assert_eq!(value, bson!([3, 2, 1]));
+
+    let mut docs = bson!([
+        { "k": 2, "id": "a" },
+        { "k": 1, "id": "b" },
+        { "k": 2, "id": "c" },
+        { "id": "d" },
+        { "k": 1, "id": "e" },
+    ]);
+    docs.sort_array_by_key(|d| d.as_document().and_then(|d| d.get_i32("k").ok()))
+        .unwrap();
+    let ids: Vec<_> = docs
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d.as_document().unwrap().get_str("id").unwrap())
+        .collect();
+    // the sort is stable
+    assert_eq!(ids, vec!["d", "b", "e", "a", "c"]);
+
+    let mut empty = bson!([]);
+    empty.sort_array_by_key(|_| 0).unwrap();
+    assert_eq!(empty, bson!([]));
+
+    let mut not_array = bson!({ "b": 1, "a": 2 });
+    let err = not_array.sort_array(|_, _| Ordering::Equal).unwrap_err();
+    assert_eq!(err.expected, ElementType::Array);
+    assert_eq!(err.actual, ElementType::EmbeddedDocument);
+    assert_eq!(not_array.sort_array_by_key(|_| 0).unwrap_err(), err);
+    assert_eq!(not_array.sort_array_by_field("a").unwrap_err(), err);
+    assert_eq!(not_array, bson!({ "b": 1, "a": 2 }));
+}
+
+#[test]
+fn sort_array_by_field() {
+    let _guard = LOCK.run_concurrently();
+
+    let mut docs = bson!([
+        { "k": Bson::MaxKey, "id": "max" },
+        { "k": true, "id": "bool" },
+        { "k": 2.5, "id": "double" },
+        { "k": "b", "id": "string" },
+        { "k": 2_i64, "id": "int64" },
+        { "k": Bson::Null, "id": "null" },
+        { "k": { "x": 1 }, "id": "doc" },
+        { "k": f64::NAN, "id": "nan" },
+        { "id": "missing" },
+        { "k": [1], "id": "array" },
+        { "k": "3".parse::<Decimal128>().unwrap(), "id": "decimal" },
+        { "k": "a", "id": "string2" },
+        { "k": Bson::MinKey, "id": "min" },
+        { "k": DateTime::from_millis(0), "id": "date" },
+        { "k": -1, "id": "int32" },
+        { "k": i64::MAX, "id": "int64_max" },
+        { "k": i64::MAX - 1, "id": "int64_max2" },
+        "not a document",
+    ]);
+    docs.sort_array_by_field("k").unwrap();
+    let ids: Vec<_> = docs
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d.as_document().map_or("-", |d| d.get_str("id").unwrap()))
+        .collect();
+    // elements without the field sort as null, keeping their relative order
+    assert_eq!(
+        ids,
+        vec![
+            "min",
+            "null",
+            "missing",
+            "-",
+            "nan",
+            "int32",
+            "int64",
+            "double",
+            "decimal",
+            "int64_max2",
+            "int64_max",
+            "string2",
+            "string",
+            "doc",
+            "array",
+            "bool",
+            "date",
+            "max",
+        ]
+    );
+
+    let mut empty = bson!([]);
+    empty.sort_array_by_field("k").unwrap();
+    assert_eq!(empty, bson!([]));
+}
+
+#[test]
+fn timestamp_extjson_round_trip() {
+    let _guard = LOCK.run_concurrently();
+
+    for ts in [
+        Timestamp {
+            time: 0,
+            increment: 0,
+        },
+        Timestamp {
+            time: 1_672_531_200,
+            increment: 7,
+        },
+        Timestamp {
+            time: u32::MAX,
+            increment: u32::MAX,
+        },
+    ] {
+        let expected = json!({ "$timestamp": { "t": ts.time, "i": ts.increment } });
+        let canonical = Bson::Timestamp(ts).into_canonical_extjson();
+        assert_eq!(canonical, expected);
+        assert_eq!(Bson::Timestamp(ts).into_relaxed_extjson(), expected);
+
+        assert_eq!(Timestamp::from_extjson(&canonical).unwrap(), ts);
+        assert_eq!(Bson::try_from(canonical).unwrap(), Bson::Timestamp(ts));
+    }
+
+    // `t` and `i` must be integers that fit in a u32.
+    for invalid in [
+        json!({ "$timestamp": { "t": "1", "i": 1 } }),
+        json!({ "$timestamp": { "t": 1, "i": "1" } }),
+        json!({ "$timestamp": { "t": -1, "i": 1 } }),
+        json!({ "$timestamp": { "t": 1, "i": u64::from(u32::MAX) + 1 } }),
+        json!({ "$timestamp": { "t": 1.5, "i": 1 } }),
+        json!({ "$timestamp": { "t": 1 } }),
+        json!({ "$timestamp": { "t": 1, "i": 1, "x": 1 } }),
+        json!({ "$timestamp": { "t": 1, "i": 1 }, "x": 1 }),
+        json!({ "t": 1, "i": 1 }),
+    ] {
+        assert!(Timestamp::from_extjson(&invalid).is_err(), "{}", invalid);
+        assert!(Bson::try_from(invalid.clone())
+            .ok()
+            .and_then(|b| b.as_timestamp())
+            .is_none());
+    }
+}
+
+#[test]
+fn as_null_matches_raw() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! { "null": null, "int": 1, "string": "", "doc": {}, "array": [null] };
+    let raw = crate::RawDocumentBuf::from_document(&doc).unwrap();
+    for (key, value) in doc.iter() {
+        let raw_value = raw.get(key).unwrap().unwrap();
+        assert_eq!(value.as_null(), raw_value.as_null(), "{}", key);
+    }
+    assert_eq!(Bson::Null.as_null(), Some(()));
+    assert_eq!(Bson::Undefined.as_null(), None);
+}
@@ -1,12 +1,16 @@
 use crate::{
     doc,
-    document::ValueAccessError,
+    document::{DocumentBuilder, KeyCollisionError, ValueAccessError},
     oid::ObjectId,
     spec::BinarySubtype,
     tests::LOCK,
     Binary,
     Bson,
+    DateTime,
+    Decimal128,
     Document,
+    JavaScriptCodeWithScope,
+    Regex,
     Timestamp,
 };
 use time::OffsetDateTime;
@@ -142,6 +146,40 @@ fn test_getters() {
         doc.get("binary")
     );
     assert_eq!(Ok(&binary), doc.get_binary_generic("binary"));
+
+    let decimal: Decimal128 = "1.25".parse().unwrap();
+    doc.insert("decimal", decimal);
+    assert_eq!(Some(&Bson::Decimal128(decimal)), doc.get("decimal"));
+    assert_eq!(Ok(&decimal), doc.get_decimal128("decimal"));
+    assert_eq!(
+        Err(ValueAccessError::UnexpectedType),
+        doc.get_decimal128("i32")
+    );
+}
+
+#[test]
+fn get_binary_generic() {
+    let _guard = LOCK.run_concurrently();
+    let doc = doc! {
+        "blob": Binary { subtype: BinarySubtype::Generic, bytes: vec![1, 2, 3] },
+        "md5": Binary { subtype: BinarySubtype::Md5, bytes: vec![4, 5, 6] },
+        "string": "blob",
+    };
+
+    let bytes: &[u8] = doc.get_binary_generic("blob").unwrap();
+    assert_eq!(bytes, &[1, 2, 3]);
+    assert_eq!(
+        doc.get_binary_generic("md5"),
+        Err(ValueAccessError::UnexpectedType)
+    );
+    assert_eq!(
+        doc.get_binary_generic("string"),
+        Err(ValueAccessError::UnexpectedType)
+    );
+    assert_eq!(
+        doc.get_binary_generic("missing"),
+        Err(ValueAccessError::NotPresent)
+    );
 }
 
 #[test]
@@ -415,3 +453,498 @@ fn test_indexing_on_wrong_bson_type() {
     let val = &d["x"]["y"]["z"];
     assert!(val.as_null().is_some());
 }
+
+#[test]
+fn rename_key() {
+    let _guard = LOCK.run_concurrently();
+    let mut doc = doc! { "a": 1, "b": 2, "c": 3 };
+
+    assert_eq!(doc.rename_key("a", "x"), Ok(true));
+    assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["x", "b", "c"]);
+    assert_eq!(doc.get_i32("x"), Ok(1));
+
+    assert_eq!(doc.rename_key("c", "y"), Ok(true));
+    assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["x", "b", "y"]);
+
+    assert_eq!(doc.rename_key("b", "b"), Ok(true));
+    assert_eq!(doc.rename_key("nope", "z"), Ok(false));
+
+    assert_eq!(
+        doc.rename_key("x", "y"),
+        Err(KeyCollisionError {
+            key: "y".to_string()
+        })
+    );
+    assert_eq!(doc, doc! { "x": 1, "b": 2, "y": 3 });
+}
+
+#[test]
+fn contains_deprecated_types() {
+    let _guard = LOCK.run_concurrently();
+
+    let code = crate::JavaScriptCodeWithScope {
+        code: "x".to_string(),
+        scope: doc! { "s": Bson::Symbol("sym".to_string()) },
+    };
+    let deprecated = vec![
+        doc! { "a": Bson::Undefined },
+        doc! { "a": Bson::Symbol("sym".to_string()) },
+        doc! { "a": { "b": [1, { "c": Bson::Undefined }] } },
+        doc! { "a": code },
+    ];
+    for doc in deprecated {
+        assert!(doc.contains_deprecated_types(), "{}", doc);
+    }
+
+    let modern = doc! {
+        "a": 1,
+        "b": { "c": [Bson::Null, Bson::MinKey, "str"] },
+        "d": Bson::JavaScriptCode("x".to_string()),
+    };
+    assert!(!modern.contains_deprecated_types());
+}
+
+#[test]
+fn capacity() {
+    let _guard = LOCK.run_concurrently();
+
+    let mut doc = Document::with_capacity(10);
+    assert!(doc.is_empty());
+    assert!(doc.capacity() >= 10);
+
+    doc.insert("a", 1);
+    doc.reserve(100);
+    assert!(doc.capacity() >= 101);
+
+    doc.shrink_to_fit();
+    assert!(doc.capacity() >= 1);
+    assert_eq!(doc, doc! { "a": 1 });
+}
+
+#[test]
+fn extend_from_pairs() {
+    let _guard = LOCK.run_concurrently();
+
+    let mut doc = doc! { "a": 1, "b": 2 };
+    doc.extend(vec![("c".to_string(), Bson::Int32(3))]);
+    doc.extend(vec![("b", Bson::Int32(20)), ("d", Bson::Int32(4))]);
+
+    assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["a", "b", "c", "d"]);
+    assert_eq!(doc.get_i32("b"), Ok(20));
+}
+
+#[test]
+fn get_bool_lenient() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! {
+        "bool": false,
+        "i32_one": 1,
+        "i32_zero": 0,
+        "i64_one": 1_i64,
+        "i64_zero": 0_i64,
+        "true": "True",
+        "false": "fALSE",
+        "two": 2,
+        "negative": -1_i64,
+        "yes": "yes",
+        "double": 1.0,
+        "null": Bson::Null,
+    };
+
+    assert_eq!(doc.get_bool_lenient("bool"), Ok(false));
+    assert_eq!(doc.get_bool_lenient("i32_one"), Ok(true));
+    assert_eq!(doc.get_bool_lenient("i32_zero"), Ok(false));
+    assert_eq!(doc.get_bool_lenient("i64_one"), Ok(true));
+    assert_eq!(doc.get_bool_lenient("i64_zero"), Ok(false));
+    assert_eq!(doc.get_bool_lenient("true"), Ok(true));
+    assert_eq!(doc.get_bool_lenient("false"), Ok(false));
+    for key in ["two", "negative", "yes", "double"] {
+        assert_eq!(
+            doc.get_bool_lenient(key),
+            Err(ValueAccessError::UnexpectedType)
+        );
+    }
+    assert_eq!(
+        doc.get_bool_lenient("null"),
+        Err(ValueAccessError::UnexpectedNull)
+    );
+    assert_eq!(
+        doc.get_bool_lenient("missing"),
+        Err(ValueAccessError::NotPresent)
+    );
+
+    // the strict getter is unchanged
+    assert_eq!(
+        doc.get_bool("i32_one"),
+        Err(ValueAccessError::UnexpectedType)
+    );
+}
+
+#[test]
+fn document_builder() {
+    let _guard = LOCK.run_concurrently();
+
+    let present: Option<i64> = Some(5);
+    let absent: Option<String> = None;
+    let doc = Document::builder()
+        .field("a", 1)
+        .field_if_some("b", present)
+        .field_if_some("c", absent)
+        .field_if("d", "yes", true)
+        .field_if("e", "no", false)
+        .field("f", doc! { "g": [1, 2] })
+        .build();
+    assert_eq!(
+        doc,
+        doc! { "a": 1, "b": 5_i64, "d": "yes", "f": { "g": [1, 2] } }
+    );
+    assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["a", "b", "d", "f"]);
+
+    // repeated keys replace the earlier value in place
+    let doc = DocumentBuilder::new()
+        .field("x", 1)
+        .field("y", 2)
+        .field("x", 3)
+        .build();
+    assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["x", "y"]);
+    assert_eq!(doc.get_i32("x"), Ok(3));
+
+    assert!(Document::builder().build().is_empty());
+}
+
+#[test]
+fn byte_size() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! {
+        "double": 1.5,
+        "string": "hello",
+        "doc": { "nested": { "deeper": [] } },
+        "array": (0..120).map(Bson::Int32).collect::<Vec<_>>(),
+        "binary": Binary { subtype: BinarySubtype::Generic, bytes: vec![1, 2, 3] },
+        "binary_old": Binary { subtype: BinarySubtype::BinaryOld, bytes: vec![1, 2, 3] },
+        "undefined": Bson::Undefined,
+        "oid": ObjectId::new(),
+        "bool": true,
+        "datetime": DateTime::now(),
+        "null": Bson::Null,
+        "regex": Regex::new("ab+c", "im"),
+        "db_pointer": Bson::DbPointer(crate::DbPointer {
+            namespace: "db.coll".to_string(),
+            id: ObjectId::new(),
+        }),
+        "code": Bson::JavaScriptCode("function() {}".to_string()),
+        "symbol": Bson::Symbol("sym".to_string()),
+        "code_w_scope": JavaScriptCodeWithScope::new("x", doc! { "x": 1 }),
+        "i32": 1,
+        "timestamp": Timestamp { time: 1, increment: 2 },
+        "i64": 1_i64,
+        "decimal": Decimal128::ONE,
+        "max": Bson::MaxKey,
+        "min": Bson::MinKey,
+        "unicode ключ": "значение",
+    };
+    assert_eq!(doc.byte_size().unwrap(), crate::to_vec(&doc).unwrap().len());
+
+    for (key, value) in &doc {
+        let single = doc! { key: value.clone() };
+        let encoded = crate::to_vec(&single).unwrap();
+        assert_eq!(single.byte_size().unwrap(), encoded.len(), "{}", key);
+        assert_eq!(
+            value.byte_size().unwrap(),
+            encoded.len() - 4 - 1 - key.len() - 1 - 1,
+            "{}",
+            key
+        );
+    }
+
+    assert_eq!(Document::new().byte_size().unwrap(), 5);
+
+    // values that cannot be encoded have no size
+    assert!(doc! { "a\0b": 1 }.byte_size().is_err());
+    assert!(Bson::Document(doc! { "x": { "a\0b": 1 } })
+        .byte_size()
+        .is_err());
+}
+
+#[test]
+fn sort_keys() {
+    let _guard = LOCK.run_concurrently();
+
+    let original = doc! {
+        "c": 1,
+        "a": { "z": 1, "y": 2 },
+        "b": [{ "q": 1, "p": 2 }, 5],
+    };
+
+    let mut doc = original.clone();
+    doc.sort_keys();
+    assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(
+        doc.get_document("a").unwrap().keys().collect::<Vec<_>>(),
+        vec!["z", "y"]
+    );
+
+    let mut doc = original;
+    doc.sort_keys_recursive();
+    assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(
+        doc.get_document("a").unwrap().keys().collect::<Vec<_>>(),
+        vec!["y", "z"]
+    );
+    let nested = doc.get_array("b").unwrap()[0].as_document().unwrap();
+    assert_eq!(nested.keys().collect::<Vec<_>>(), vec!["p", "q"]);
+}
+
+#[test]
+fn to_canonical_vec() {
+    let _guard = LOCK.run_concurrently();
+
+    let a = doc! {
+        "c": 1,
+        "a": { "z": 1, "y": 2 },
+        "b": [{ "q": 1, "p": 2 }, 5],
+    };
+    let b = doc! {
+        "b": [{ "p": 2, "q": 1 }, 5],
+        "a": { "y": 2, "z": 1 },
+        "c": 1,
+    };
+    assert_ne!(crate::to_vec(&a).unwrap(), crate::to_vec(&b).unwrap());
+
+    let canonical = a.to_canonical_vec().unwrap();
+    assert_eq!(canonical, b.to_canonical_vec().unwrap());
+
+    let mut sorted = a.clone();
+    sorted.sort_keys_recursive();
+    assert_eq!(canonical, crate::to_vec(&sorted).unwrap());
+
+    // the original document is left untouched
+    assert_eq!(a.keys().collect::<Vec<_>>(), vec!["c", "a", "b"]);
+
+    // array order is significant
+    let c = doc! { "a": { "y": 2, "z": 1 }, "b": [5, { "p": 2, "q": 1 }], "c": 1 };
+    assert_ne!(canonical, c.to_canonical_vec().unwrap());
+
+    // so are the scopes of code with scope values
+    let code = |scope: Document| {
+        doc! {
+            "f": JavaScriptCodeWithScope {
+                code: "x + y".to_string(),
+                scope,
+            },
+        }
+    };
+    let d = code(doc! { "x": 1, "y": { "b": 2, "a": 1 } });
+    let e = code(doc! { "y": { "a": 1, "b": 2 }, "x": 1 });
+    assert_ne!(crate::to_vec(&d).unwrap(), crate::to_vec(&e).unwrap());
+    assert_eq!(d.to_canonical_vec().unwrap(), e.to_canonical_vec().unwrap());
+}
+
+#[test]
+fn transform_keys() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! { "a": 1, "b": { "c": [{ "d": 2 }] } };
+    let mut seen = Vec::new();
+    let transformed = doc.transform_keys_recursive(|k| {
+        seen.push(k.to_string());
+        format!("{}{}", k, k)
+    });
+    assert_eq!(transformed, doc! { "aa": 1, "bb": { "cc": [{ "dd": 2 }] } });
+    assert_eq!(seen, vec!["a", "b", "c", "d"]);
+
+    assert_eq!(
+        doc.transform_keys(|k| format!("{}{}", k, k)),
+        doc! { "aa": 1, "bb": { "c": [{ "d": 2 }] } }
+    );
+
+    // colliding keys keep the later value
+    assert_eq!(
+        doc! { "a": 1, "b": 2 }.transform_keys(|_| "x".to_string()),
+        doc! { "x": 2 }
+    );
+    let transformed =
+        doc! { "a": 1, "c": { "a": 2, "b": 3 }, "b": 4 }.transform_keys_recursive(|k| {
+            if k == "c" {
+                k.to_string()
+            } else {
+                "x".to_string()
+            }
+        });
+    assert_eq!(transformed, doc! { "x": 4, "c": { "x": 3 } });
+    assert_eq!(transformed.keys().collect::<Vec<_>>(), vec!["x", "c"]);
+}
+
+#[test]
+fn key_casing() {
+    let _guard = LOCK.run_concurrently();
+
+    let camel = doc! {
+        "_id": 1,
+        "firstName": "a",
+        "userID": 2,
+        "HTTPServer": 3,
+        "addressLine2": 4,
+        "nested": [{ "zipCode": 5 }],
+    };
+    let snake = doc! {
+        "_id": 1,
+        "first_name": "a",
+        "user_id": 2,
+        "http_server": 3,
+        "address_line2": 4,
+        "nested": [{ "zip_code": 5 }],
+    };
+    assert_eq!(camel.keys_to_snake_case(), snake);
+
+    assert_eq!(
+        snake.keys_to_camel_case(),
+        doc! {
+            "_id": 1,
+            "firstName": "a",
+            "userId": 2,
+            "httpServer": 3,
+            "addressLine2": 4,
+            "nested": [{ "zipCode": 5 }],
+        }
+    );
+    assert_eq!(
+        doc! { "__private_key": 1, "trailing_": 2, "user__id": 3 }.keys_to_camel_case(),
+        doc! { "__privateKey": 1, "trailing_": 2, "user__id": 3 }
+    );
+
+    // keys that convert to the same name keep the later value
+    assert_eq!(
+        doc! { "first_name": 1, "firstName": 2, "nested": { "zipCode": 3, "zip_code": 4 } }
+            .keys_to_camel_case(),
+        doc! { "firstName": 2, "nested": { "zipCode": 4 } }
+    );
+    assert_eq!(
+        doc! { "firstName": 1, "first_name": 2 }.keys_to_snake_case(),
+        doc! { "first_name": 2 }
+    );
+}
+
+#[test]
+fn values_mut() {
+    let _guard = LOCK.run_concurrently();
+
+    let mut doc = doc! { "a": 1, "b": 2, "c": 3 };
+    for value in doc.values_mut() {
+        if let Bson::Int32(i) = value {
+            *i *= 10;
+        }
+    }
+    assert_eq!(doc, doc! { "a": 10, "b": 20, "c": 30 });
+    assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(
+        doc.values().cloned().collect::<Vec<_>>(),
+        vec![Bson::Int32(10), Bson::Int32(20), Bson::Int32(30)]
+    );
+}
+
+#[test]
+fn append() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = Document::new()
+        .append("a", 1)
+        .append("b", "two")
+        .append("c", doc! { "d": true })
+        .append("a", 3);
+    assert_eq!(doc, doc! { "a": 3, "b": "two", "c": { "d": true } });
+    assert_eq!(doc.keys().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+}
+
+#[test]
+fn get_null_value() {
+    let _guard = LOCK.run_concurrently();
+
+    let mut doc = doc! { "null": null, "int": 1 };
+    assert_eq!(doc.get_str("null"), Err(ValueAccessError::UnexpectedNull));
+    assert_eq!(
+        doc.get_i32_mut("null"),
+        Err(ValueAccessError::UnexpectedNull)
+    );
+    assert_eq!(
+        doc.get_document("null"),
+        Err(ValueAccessError::UnexpectedNull)
+    );
+    assert_eq!(doc.get_str("int"), Err(ValueAccessError::UnexpectedType));
+    assert_eq!(doc.get_str("missing"), Err(ValueAccessError::NotPresent));
+    assert_eq!(
+        ValueAccessError::UnexpectedNull.to_string(),
+        "field is null"
+    );
+}
+
+#[test]
+fn partition() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! { "a": 1, "b": "two", "c": 3, "d": { "e": 4 }, "f": null };
+    let (ints, rest) = doc.clone().partition(|_, value| value.as_i32().is_some());
+    assert_eq!(ints, doc! { "a": 1, "c": 3 });
+    assert_eq!(rest, doc! { "b": "two", "d": { "e": 4 }, "f": null });
+    assert_eq!(rest.keys().collect::<Vec<_>>(), vec!["b", "d", "f"]);
+
+    let (all, none) = doc.clone().partition(|_, _| true);
+    assert_eq!(all, doc);
+    assert!(none.is_empty());
+
+    let (none, all) = doc.clone().partition(|_, _| false);
+    assert!(none.is_empty());
+    assert_eq!(all, doc);
+}
+
+#[test]
+fn remove_nulls() {
+    let _guard = LOCK.run_concurrently();
+
+    let original = doc! {
+        "a": null,
+        "b": 1,
+        "c": { "d": null, "e": { "f": null } },
+        "g": [null, { "h": null, "i": "x" }, []],
+        "j": [],
+        "k": "y",
+    };
+
+    let mut doc = original.clone();
+    doc.remove_nulls();
+    assert_eq!(
+        doc,
+        doc! {
+            "b": 1,
+            "c": { "e": {} },
+            "g": [null, { "i": "x" }, []],
+            "j": [],
+            "k": "y",
+        }
+    );
+    assert_eq!(
+        doc.keys().collect::<Vec<_>>(),
+        vec!["b", "c", "g", "j", "k"]
+    );
+
+    let mut doc = original.clone();
+    doc.remove_nulls_and_empty();
+    assert_eq!(
+        doc,
+        doc! {
+            "b": 1,
+            "g": [null, { "i": "x" }, []],
+            "k": "y",
+        }
+    );
+
+    let mut value = Bson::Document(original);
+    value.remove_nulls_and_empty();
+    assert_eq!(value, Bson::Document(doc));
+
+    let mut value = Bson::Null;
+    value.remove_nulls();
+    assert_eq!(value, Bson::Null);
+}
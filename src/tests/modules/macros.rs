@@ -1,3 +1,5 @@
+use std::convert::TryFrom;
+
 use crate::{
     doc,
     oid::ObjectId,
@@ -273,3 +275,62 @@ fn can_use_macro_with_into_bson() {
         "a": Custom,
     };
 }
+
+#[test]
+fn rawbson_matches_bson() {
+    let _guard = LOCK.run_concurrently();
+
+    macro_rules! assert_same {
+        ($($tt:tt)+) => {{
+            let raw: RawBson = rawbson!($($tt)+);
+            let owned: Bson = bson!($($tt)+);
+            assert_eq!(Bson::try_from(raw).unwrap(), owned);
+        }};
+    }
+
+    assert_same!(1);
+    assert_same!("string");
+    assert_same!(null);
+    assert_same!([]);
+    assert_same!({});
+    assert_same!([1, "two", [3.0, null], { "four": true }]);
+    assert_same!({
+        "a": [1, { "b": [] }],
+        "c": { "d": { "e": "f" } },
+        "g": Timestamp { time: 1, increment: 2 },
+    });
+}
+
+#[test]
+fn try_bson_and_try_doc() {
+    let _guard = LOCK.run_concurrently();
+
+    let oid = ObjectId::new();
+    let doc = try_doc! {
+        "id": serde_json::json!({ "$oid": oid.to_hex() }),
+        "plain": 1,
+        "nested": { "raw": RawBson::Int64(2), "list": [null, "x", RawBson::Boolean(true)] },
+        "empty": {},
+    }
+    .unwrap();
+    assert_eq!(
+        doc,
+        doc! {
+            "id": oid,
+            "plain": 1,
+            "nested": { "raw": 2_i64, "list": [null, "x", true] },
+            "empty": {},
+        }
+    );
+
+    assert_eq!(
+        try_bson!([1, [2], { "a": 3 }]).unwrap(),
+        bson!([1, [2], { "a": 3 }])
+    );
+    assert_eq!(try_bson!(null).unwrap(), Bson::Null);
+    assert_eq!(try_doc! {}.unwrap(), doc! {});
+
+    let bad = serde_json::json!({ "$oid": "not an oid" });
+    assert!(try_doc! { "a": 1, "b": { "c": [bad.clone()] } }.is_err());
+    assert!(try_bson!(bad).is_err());
+}
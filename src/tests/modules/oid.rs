@@ -1,4 +1,7 @@
-use crate::{oid::ObjectId, tests::LOCK};
+use crate::{
+    oid::{DefaultObjectIdGenerator, ObjectId, ObjectIdGenerator},
+    tests::LOCK,
+};
 
 #[test]
 fn string_oid() {
@@ -73,3 +76,110 @@ fn oid_from_parts() {
     assert_eq!(&oid.bytes()[4..9], &process_id);
     assert_eq!(&oid.bytes()[9..], &counter);
 }
+
+#[test]
+fn oid_ordering() {
+    let _guard = LOCK.run_concurrently();
+
+    // the timestamp takes precedence over the process id and counter
+    let earlier = ObjectId::from_parts(100, [0xFF; 5], [0xFF; 3]);
+    let later = ObjectId::from_parts(101, [0; 5], [0; 3]);
+    assert!(earlier < later);
+
+    // within the same second and process, the counter decides
+    let first = ObjectId::from_parts(100, [1; 5], [0, 0, 1]);
+    let second = ObjectId::from_parts(100, [1; 5], [0, 1, 0]);
+    assert!(first < second);
+    assert_eq!(first.cmp(&first), std::cmp::Ordering::Equal);
+
+    // ordering matches the ordering of the hex representations
+    let mut oids = vec![later, second, earlier, first];
+    let mut hex: Vec<String> = oids.iter().map(|oid| oid.to_hex()).collect();
+    oids.sort();
+    hex.sort();
+    assert_eq!(oids, vec![first, second, earlier, later]);
+    assert_eq!(hex, oids.iter().map(|oid| oid.to_hex()).collect::<Vec<_>>());
+}
+
+#[test]
+fn oid_new_ordering() {
+    let _guard = LOCK.run_concurrently();
+
+    let a = ObjectId::new();
+    let b = ObjectId::new();
+    // the counter is only allowed to wrap between two ids if the second id has a zero counter
+    if a.bytes()[..4] == b.bytes()[..4] && b.bytes()[9..] != [0, 0, 0] {
+        assert!(a < b);
+    }
+    assert!(a.timestamp() <= b.timestamp());
+}
+
+#[test]
+fn oid_components() {
+    let _guard = LOCK.run_concurrently();
+
+    let oid = ObjectId::from_parts(0x01020304, [5, 6, 7, 8, 9], [0x0A, 0x0B, 0x0C]);
+    assert_eq!(oid.timestamp_bytes(), [1, 2, 3, 4]);
+    assert_eq!(oid.process_bytes(), [5, 6, 7, 8, 9]);
+    assert_eq!(oid.counter(), 0x0A0B0C);
+    assert_eq!(oid.timestamp().timestamp_millis(), 0x01020304_i64 * 1000);
+
+    let max = ObjectId::from_bytes([0xFF; 12]);
+    assert_eq!(max.counter(), 0xFF_FFFF);
+}
+
+#[test]
+fn oid_custom_generator() {
+    use std::cell::Cell;
+
+    let _guard = LOCK.run_concurrently();
+
+    struct Fixed {
+        counter: Cell<u8>,
+    }
+
+    impl ObjectIdGenerator for Fixed {
+        fn generate(&self) -> ObjectId {
+            let count = self.counter.get();
+            self.counter.set(count + 1);
+            ObjectId::from_parts(42, [1, 2, 3, 4, 5], [0, 0, count])
+        }
+    }
+
+    let generator = Fixed {
+        counter: Cell::new(7),
+    };
+    let first = ObjectId::new_with(&generator);
+    let second = ObjectId::new_with(&generator);
+    assert_eq!(first.to_hex(), "0000002a0102030405000007");
+    assert_eq!(second.counter(), 8);
+
+    let generator: &dyn ObjectIdGenerator = &generator;
+    assert_eq!(ObjectId::new_with(generator).counter(), 9);
+
+    // The default generator behaves like `ObjectId::new`.
+    let default = ObjectId::new_with(&DefaultObjectIdGenerator);
+    let new = ObjectId::new();
+    assert_eq!(default.process_bytes(), new.process_bytes());
+}
+
+#[test]
+fn oid_new_with_timestamp() {
+    use crate::DateTime;
+
+    let _guard = LOCK.run_concurrently();
+
+    let dt = DateTime::from_millis(1_700_000_000_500);
+    let first = ObjectId::new_with_timestamp(dt);
+    let second = ObjectId::new_with_timestamp(dt);
+    assert_eq!(first.timestamp(), DateTime::from_millis(1_700_000_000_000));
+    assert_eq!(first.timestamp_bytes(), second.timestamp_bytes());
+    assert_ne!(first, second);
+    assert_eq!(first.process_bytes(), ObjectId::new().process_bytes());
+
+    // times outside of the representable range are clamped
+    let before_epoch = ObjectId::new_with_timestamp(DateTime::from_millis(-1));
+    assert_eq!(before_epoch.timestamp_bytes(), [0; 4]);
+    let far_future = ObjectId::new_with_timestamp(DateTime::MAX);
+    assert_eq!(far_future.timestamp_bytes(), [0xFF; 4]);
+}
@@ -1,8 +1,27 @@
 use std::collections::BTreeMap;
 
 use assert_matches::assert_matches;
-
-use crate::{from_bson, oid::ObjectId, ser, tests::LOCK, to_bson, to_vec, Bson, Document, Regex};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    doc,
+    from_bson,
+    from_document,
+    oid::ObjectId,
+    ser,
+    tests::LOCK,
+    to_bson,
+    to_bson_with_options,
+    to_vec,
+    to_vec_with_buffer,
+    to_vec_with_options,
+    to_writer,
+    Bson,
+    Document,
+    EnumRepresentation,
+    Regex,
+    SerializerOptions,
+};
 
 #[test]
 #[allow(clippy::float_cmp)]
@@ -167,3 +186,343 @@ fn cstring_null_bytes_error() {
         ));
     }
 }
+
+#[test]
+fn reject_nan() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! { "a": 1.5, "b": [f64::NAN] };
+    let options = SerializerOptions::builder().reject_nan(true).build();
+
+    assert_matches!(
+        to_vec_with_options(&doc, options.clone())
+            .unwrap_err()
+            .strip_path(),
+        ser::Error::SerializationError { .. }
+    );
+    assert_matches!(
+        to_bson_with_options(&doc, options.clone())
+            .unwrap_err()
+            .strip_path(),
+        ser::Error::SerializationError { .. }
+    );
+
+    let finite = doc! { "a": 1.5, "b": [f64::INFINITY] };
+    assert_eq!(
+        to_vec_with_options(&finite, options).unwrap(),
+        to_vec(&finite).unwrap()
+    );
+    assert!(to_vec(&doc).is_ok());
+}
+
+#[test]
+fn enum_representation() {
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Debug, Clone, Serialize)]
+    enum Shape {
+        Unit,
+        Newtype(i32),
+        Tuple(i32, String),
+        Struct { a: i32 },
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(tag = "type", content = "data")]
+    enum Adjacent {
+        Unit,
+        Newtype(i32),
+        Tuple(i32, String),
+        Struct { a: i32 },
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(tag = "type")]
+    enum Internal {
+        Unit,
+        Struct { a: i32 },
+    }
+
+    let adjacent = SerializerOptions::builder()
+        .enum_representation(EnumRepresentation::Adjacent {
+            tag: "type".to_string(),
+            content: "data".to_string(),
+        })
+        .build();
+    for (value, expected, parsed) in [
+        (Shape::Unit, doc! { "type": "Unit" }, Adjacent::Unit),
+        (
+            Shape::Newtype(1),
+            doc! { "type": "Newtype", "data": 1 },
+            Adjacent::Newtype(1),
+        ),
+        (
+            Shape::Tuple(1, "x".to_string()),
+            doc! { "type": "Tuple", "data": [1, "x"] },
+            Adjacent::Tuple(1, "x".to_string()),
+        ),
+        (
+            Shape::Struct { a: 1 },
+            doc! { "type": "Struct", "data": { "a": 1 } },
+            Adjacent::Struct { a: 1 },
+        ),
+    ] {
+        assert_eq!(
+            to_bson_with_options(&value, adjacent.clone()).unwrap(),
+            Bson::Document(expected.clone())
+        );
+        assert_eq!(from_document::<Adjacent>(expected).unwrap(), parsed);
+    }
+
+    let internal = SerializerOptions::builder()
+        .enum_representation(EnumRepresentation::Internal {
+            tag: "type".to_string(),
+        })
+        .build();
+    let expected = doc! { "type": "Struct", "a": 1 };
+    assert_eq!(
+        to_bson_with_options(&Shape::Struct { a: 1 }, internal.clone()).unwrap(),
+        Bson::Document(expected.clone())
+    );
+    assert_eq!(
+        from_document::<Internal>(expected).unwrap(),
+        Internal::Struct { a: 1 }
+    );
+    assert_eq!(
+        to_bson_with_options(&Shape::Unit, internal.clone()).unwrap(),
+        Bson::Document(doc! { "type": "Unit" })
+    );
+    assert!(to_bson_with_options(&Shape::Tuple(1, "x".to_string()), internal.clone()).is_err());
+
+    #[derive(Serialize)]
+    struct Wrapper {
+        v: Shape,
+    }
+    let bytes = to_vec_with_options(
+        &Wrapper {
+            v: Shape::Struct { a: 1 },
+        },
+        internal,
+    )
+    .unwrap();
+    assert_eq!(
+        Document::from_reader(bytes.as_slice()).unwrap(),
+        doc! { "v": { "type": "Struct", "a": 1 } }
+    );
+
+    assert_eq!(
+        to_bson_with_options(&Shape::Unit, SerializerOptions::default()).unwrap(),
+        Bson::String("Unit".to_string())
+    );
+}
+
+#[test]
+fn vec_with_buffer() {
+    let _guard = LOCK.run_concurrently();
+
+    let mut buffer = Vec::new();
+    to_vec_with_buffer(&doc! { "a": "x".repeat(100) }, &mut buffer).unwrap();
+    let capacity = buffer.capacity();
+
+    let small = doc! { "b": 1 };
+    to_vec_with_buffer(&small, &mut buffer).unwrap();
+    assert_eq!(buffer, to_vec(&small).unwrap());
+    assert_eq!(buffer.capacity(), capacity);
+
+    // only documents can be serialized at the top level
+    assert!(to_vec_with_buffer(&5, &mut buffer).is_err());
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.capacity(), capacity);
+}
+
+#[test]
+fn writer() {
+    use std::cell::Cell;
+
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Serialize)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        Rect { w: i32, h: i32 },
+        Poly(i32, i32, i32),
+    }
+
+    #[derive(Serialize)]
+    struct Item {
+        id: ObjectId,
+        shapes: Vec<Shape>,
+        nested: Document,
+        raw: crate::RawDocumentBuf,
+        code: Bson,
+        missing: Option<i32>,
+    }
+
+    let item = Item {
+        id: ObjectId::new(),
+        shapes: vec![
+            Shape::Point,
+            Shape::Circle(1.5),
+            Shape::Rect { w: 2, h: 3 },
+            Shape::Poly(1, 2, 3),
+        ],
+        nested: doc! { "a": [1, { "b": "c" }, []], "d": {} },
+        raw: crate::rawdoc! { "x": { "y": [true] } },
+        code: Bson::JavaScriptCodeWithScope(crate::JavaScriptCodeWithScope {
+            code: "x".to_string(),
+            scope: doc! { "x": { "y": 1 } },
+        }),
+        missing: None,
+    };
+    let mut output = Vec::new();
+    to_writer(&mut output, &item).unwrap();
+    assert_eq!(output, to_vec(&item).unwrap());
+
+    // Large values are written in chunks rather than all at once.
+    struct ChunkWriter {
+        output: Vec<u8>,
+        largest_write: usize,
+    }
+
+    impl std::io::Write for ChunkWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.largest_write = self.largest_write.max(buf.len());
+            self.output.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let large: Document = (0..10_000)
+        .map(|i| (i.to_string(), Bson::from(doc! { "i": i, "s": "value" })))
+        .collect();
+    let mut writer = ChunkWriter {
+        output: Vec::new(),
+        largest_write: 0,
+    };
+    to_writer(&mut writer, &large).unwrap();
+    assert_eq!(writer.output, to_vec(&large).unwrap());
+    assert!(writer.largest_write < writer.output.len() / 10);
+
+    // Values that serialize differently the second time are rejected.
+    struct Changing(Cell<i32>);
+
+    impl Serialize for Changing {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let count = self.0.get();
+            self.0.set(count + 1);
+            let mut map = BTreeMap::new();
+            map.insert("s", "x".repeat(count as usize));
+            map.serialize(serializer)
+        }
+    }
+
+    let mut output = Vec::new();
+    assert!(to_writer(&mut output, &Changing(Cell::new(0))).is_err());
+
+    // As with `to_vec`, only documents can be written at the top level.
+    assert!(to_writer(Vec::new(), &1).is_err());
+}
+
+#[test]
+fn enum_representation_raw_matches_bson() {
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Debug, Clone, Serialize)]
+    struct Fields {
+        a: i32,
+        #[serde(rename = "type")]
+        kind: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Serialize)]
+    enum Inner {
+        Unit,
+        Struct { a: i32 },
+    }
+
+    #[derive(Debug, Clone, Serialize)]
+    enum Shape {
+        Unit,
+        Newtype(Fields),
+        Scalar(i32),
+        Nested(Inner),
+        Tuple(i32, String),
+        Struct { a: i32, b: Vec<i32> },
+        Conflict { r#type: i32 },
+    }
+
+    #[derive(Serialize)]
+    struct Wrapper {
+        v: Shape,
+        after: i32,
+    }
+
+    let values = [
+        Shape::Unit,
+        Shape::Newtype(Fields { a: 1, kind: None }),
+        Shape::Newtype(Fields {
+            a: 1,
+            kind: Some("x".to_string()),
+        }),
+        Shape::Scalar(1),
+        Shape::Nested(Inner::Unit),
+        Shape::Nested(Inner::Struct { a: 1 }),
+        Shape::Tuple(1, "x".to_string()),
+        Shape::Struct { a: 1, b: vec![2] },
+        Shape::Conflict { r#type: 1 },
+    ];
+    let representations = [
+        EnumRepresentation::External,
+        EnumRepresentation::Internal {
+            tag: "type".to_string(),
+        },
+        EnumRepresentation::Adjacent {
+            tag: "type".to_string(),
+            content: "data".to_string(),
+        },
+    ];
+    for representation in representations {
+        let options = SerializerOptions::builder()
+            .enum_representation(representation.clone())
+            .build();
+        for v in values.iter() {
+            let wrapper = Wrapper {
+                v: v.clone(),
+                after: 2,
+            };
+            let expected = to_bson_with_options(&wrapper, options.clone());
+            let actual = to_vec_with_options(&wrapper, options.clone());
+            match (expected, actual) {
+                (Ok(Bson::Document(expected)), Ok(bytes)) => {
+                    assert_eq!(
+                        Document::from_reader(bytes.as_slice()).unwrap(),
+                        expected,
+                        "{:?} as {:?}",
+                        v,
+                        representation
+                    );
+                }
+                (Err(expected), Err(actual)) => {
+                    // the raw serializer can also report the path to the error
+                    assert!(
+                        actual.to_string().ends_with(&expected.to_string()),
+                        "{:?} as {:?}: expected {}, got {}",
+                        v,
+                        representation,
+                        expected,
+                        actual
+                    );
+                }
+                (expected, actual) => panic!(
+                    "{:?} as {:?}: expected {:?}, got {:?}",
+                    v, representation, expected, actual
+                ),
+            }
+        }
+    }
+}
@@ -572,3 +572,207 @@ fn test_deserialize_invalid_old_binary_length() {
     Document::from_reader(&mut std::io::Cursor::new(buffer))
         .expect_err("expected deserialization to fail");
 }
+
+#[test]
+fn test_write_read_documents() {
+    let _guard = LOCK.run_concurrently();
+    let docs = vec![doc! { "a": 1 }, doc! { "b": "two" }, doc! {}];
+
+    let mut buf = Vec::new();
+    crate::write_documents(&mut buf, docs.clone()).unwrap();
+
+    let read: Vec<Document> = crate::DocumentReader::new(Cursor::new(buf.clone()))
+        .collect::<crate::de::Result<_>>()
+        .unwrap();
+    assert_eq!(read, docs);
+
+    assert!(crate::DocumentReader::new(Cursor::new(Vec::new()))
+        .next()
+        .is_none());
+
+    // truncated in the middle of a length prefix
+    let mut reader = crate::DocumentReader::new(Cursor::new(buf[..buf.len() - 3].to_vec()));
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+
+    // truncated in the middle of a document body
+    let mut reader = crate::DocumentReader::new(Cursor::new(buf[..buf.len() - 7].to_vec()));
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_from_reader_capped() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! { "x": "a".repeat(100) };
+    let mut bytes = Vec::new();
+    doc.to_writer(&mut bytes).unwrap();
+
+    assert_eq!(
+        Document::from_reader_capped(bytes.as_slice(), bytes.len()).unwrap(),
+        doc
+    );
+    assert!(Document::from_reader_capped(bytes.as_slice(), bytes.len() - 1).is_err());
+
+    // A huge declared length is rejected up front rather than waiting on the missing bytes.
+    let mut malicious = i32::MAX.to_le_bytes().to_vec();
+    malicious.extend_from_slice(&[0; 16]);
+    let err = Document::from_reader_capped(malicious.as_slice(), 1024).unwrap_err();
+    assert!(err.to_string().contains("exceeds maximum"), "{}", err);
+}
+
+#[test]
+fn test_from_reader_buffered() {
+    let _guard = LOCK.run_concurrently();
+
+    let docs = vec![
+        doc! { "x": "a".repeat(100) },
+        doc! { "y": 2 },
+        doc! { "z": { "nested": [1, 2, 3] } },
+    ];
+    let mut bytes = Vec::new();
+    for doc in &docs {
+        doc.to_writer(&mut bytes).unwrap();
+    }
+
+    let mut reader = bytes.as_slice();
+    let mut scratch = Vec::new();
+    let first = Document::from_reader_buffered(&mut reader, &mut scratch).unwrap();
+    assert_eq!(first, docs[0]);
+    let capacity = scratch.capacity();
+    let ptr = scratch.as_ptr();
+
+    for doc in &docs[1..] {
+        assert_eq!(
+            &Document::from_reader_buffered(&mut reader, &mut scratch).unwrap(),
+            doc
+        );
+        // the smaller documents fit in the existing allocation
+        assert_eq!(scratch.capacity(), capacity);
+        assert_eq!(scratch.as_ptr(), ptr);
+        assert_eq!(scratch, crate::to_vec(doc).unwrap());
+    }
+    assert!(reader.is_empty());
+    assert!(Document::from_reader_buffered(&mut reader, &mut scratch).is_err());
+
+    // stale contents of the scratch buffer are ignored
+    let mut scratch = vec![0xFF; 64];
+    let doc = Document::from_reader_buffered(bytes.as_slice(), &mut scratch).unwrap();
+    assert_eq!(doc, docs[0]);
+}
+
+#[test]
+fn test_from_slice_prefix() {
+    let _guard = LOCK.run_concurrently();
+
+    let first = doc! { "x": 1 };
+    let second = doc! { "y": "two", "z": [3] };
+    let mut bytes = crate::to_vec(&first).unwrap();
+    bytes.extend(crate::to_vec(&second).unwrap());
+    bytes.extend([1, 2, 3]);
+
+    let (doc, rest) = Document::from_slice_prefix(&bytes).unwrap();
+    assert_eq!(doc, first);
+    let (doc, rest) = Document::from_slice_prefix(rest).unwrap();
+    assert_eq!(doc, second);
+    assert_eq!(rest, &[1, 2, 3]);
+
+    // Too short to hold a length, or a length larger than the input.
+    assert!(Document::from_slice_prefix(rest).is_err());
+    assert!(Document::from_slice_prefix(&bytes[..bytes.len() / 4]).is_err());
+}
+
+#[test]
+fn test_to_raw_document_buf() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! {
+        "x": 1,
+        "nested": { "array": [1, "two", { "three": 3.0 }] },
+        "binary": Binary { subtype: BinarySubtype::Generic, bytes: vec![1, 2, 3] },
+    };
+    let raw = doc.to_raw_document_buf().unwrap();
+    assert_eq!(raw.as_bytes(), crate::to_vec(&doc).unwrap());
+    assert_eq!(raw.to_document().unwrap(), doc);
+}
+
+#[test]
+fn test_owned_document_conversions() {
+    let _guard = LOCK.run_concurrently();
+
+    let doc = doc! { "x": 1, "y": { "z": [true, "two"] } };
+    assert_eq!(doc.clone().into_bson(), Bson::Document(doc.clone()));
+
+    let raw = crate::RawDocumentBuf::try_from(doc.clone()).unwrap();
+    assert_eq!(raw, doc.to_raw_document_buf().unwrap());
+    assert_eq!(raw.to_document().unwrap(), doc);
+}
+
+#[test]
+fn test_raw_document_buf_from_invalid_document() {
+    let _guard = LOCK.run_concurrently();
+
+    let errors = [
+        crate::RawDocumentBuf::try_from(doc! { "a\0b": 1 }).unwrap_err(),
+        crate::RawDocumentBuf::try_from(&doc! { "a\0b": 1 }).unwrap_err(),
+        crate::RawDocumentBuf::try_from(doc! { "x": { "a\0b": 1 } }).unwrap_err(),
+        crate::RawDocumentBuf::try_from(doc! { "x": [{ "a\0b": 1 }] }).unwrap_err(),
+    ];
+    for err in errors {
+        assert!(
+            matches!(err.kind, crate::raw::ErrorKind::MalformedValue { .. }),
+            "{:?}",
+            err
+        );
+        assert_eq!(err.key(), Some("a\0b"));
+    }
+}
+
+#[test]
+fn test_from_reader_duplicate_keys() {
+    use crate::{de::DuplicateKeyPolicy, DeserializerOptions};
+
+    let _guard = LOCK.run_concurrently();
+
+    let raw = rawdoc! {
+        "a": 1,
+        "b": { "x": "first", "x": "second" },
+        "c": [{ "y": true, "y": false }],
+        "a": 2,
+    };
+    let decode = |policy| {
+        let options = DeserializerOptions::builder()
+            .on_duplicate_key(policy)
+            .build();
+        Document::from_reader_with_options(raw.as_bytes(), options)
+    };
+
+    // The default matches Document::from_reader.
+    let default = Document::from_reader(raw.as_bytes()).unwrap();
+    assert_eq!(
+        default,
+        doc! { "a": 2, "b": { "x": "second" }, "c": [{ "y": false }] }
+    );
+    assert_eq!(
+        Document::from_reader_with_options(raw.as_bytes(), Default::default()).unwrap(),
+        default
+    );
+    assert_eq!(decode(DuplicateKeyPolicy::KeepLast).unwrap(), default);
+
+    assert_eq!(
+        decode(DuplicateKeyPolicy::KeepFirst).unwrap(),
+        doc! { "a": 1, "b": { "x": "first" }, "c": [{ "y": true }] }
+    );
+
+    let err = decode(DuplicateKeyPolicy::Error).unwrap_err();
+    assert!(err.to_string().contains("duplicate key"), "{}", err);
+    let nested_only = rawdoc! { "b": { "x": 1, "x": 2 } };
+    let options = DeserializerOptions::builder()
+        .on_duplicate_key(DuplicateKeyPolicy::Error)
+        .build();
+    assert!(Document::from_reader_with_options(nested_only.as_bytes(), options).is_err());
+}
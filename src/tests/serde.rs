@@ -953,6 +953,35 @@ fn test_oid_helpers() {
     assert_eq!(doc.get_object_id("oid").unwrap(), oid);
     let a: A = from_document(doc).unwrap();
     assert_eq!(a.oid, oid.to_string());
+
+    let invalid = A {
+        oid: "not an oid".to_string(),
+    };
+    assert!(to_document(&invalid).is_err());
+
+    #[derive(Serialize, Deserialize)]
+    struct B {
+        #[serde(with = "serde_helpers::hex_string_as_object_id_optional")]
+        oid: Option<String>,
+    }
+
+    let b = B {
+        oid: Some(oid.to_hex()),
+    };
+    let doc = to_document(&b).unwrap();
+    assert_eq!(doc.get_object_id("oid").unwrap(), oid);
+    let b: B = from_document(doc).unwrap();
+    assert_eq!(b.oid, Some(oid.to_hex()));
+
+    let doc = to_document(&B { oid: None }).unwrap();
+    assert_eq!(doc, doc! { "oid": Bson::Null });
+    let b: B = from_document(doc).unwrap();
+    assert_eq!(b.oid, None);
+
+    let invalid = B {
+        oid: Some("abc".to_string()),
+    };
+    assert!(to_document(&invalid).is_err());
 }
 
 #[test]
@@ -1111,6 +1140,475 @@ fn oid_as_hex_string() {
     assert_eq!(doc.get_str("oid").unwrap(), oid.to_hex());
 }
 
+#[test]
+fn oid_vec_as_hex() {
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Foo {
+        #[serde(with = "serde_helpers::object_id_vec_as_hex")]
+        oids: Vec<ObjectId>,
+    }
+
+    let foo = Foo {
+        oids: vec![ObjectId::new(), ObjectId::new()],
+    };
+    let doc = to_document(&foo).unwrap();
+    let expected: Vec<Bson> = foo.oids.iter().map(|o| Bson::String(o.to_hex())).collect();
+    assert_eq!(doc.get_array("oids").unwrap(), &expected);
+    assert_eq!(from_document::<Foo>(doc).unwrap(), foo);
+
+    let bad = doc! { "oids": [foo.oids[0].to_hex(), "not an oid"] };
+    assert!(from_document::<Foo>(bad).is_err());
+}
+
+#[test]
+fn flexible_datetime() {
+    use serde_helpers::datetime::FlexibleDateTime;
+
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Deserialize, Serialize)]
+    struct Foo {
+        date: FlexibleDateTime,
+    }
+
+    let expected = DateTime::from_millis(1_672_531_200_000);
+
+    let docs = vec![
+        doc! { "date": expected },
+        doc! { "date": "2023-01-01T00:00:00Z" },
+        doc! { "date": { "$date": { "$numberLong": "1672531200000" } } },
+    ];
+    for doc in docs {
+        let foo: Foo = from_document(doc.clone()).unwrap();
+        assert_eq!(foo.date.0, expected, "{}", doc);
+    }
+
+    let foo: Foo = serde_json::from_value(json!({ "date": "2023-01-01T00:00:00Z" })).unwrap();
+    assert_eq!(foo.date.0, expected);
+
+    assert!(from_document::<Foo>(doc! { "date": "not a date" }).is_err());
+    assert!(from_document::<Foo>(doc! { "date": 5 }).is_err());
+
+    let doc = to_document(&Foo {
+        date: FlexibleDateTime(expected),
+    })
+    .unwrap();
+    assert_eq!(doc, doc! { "date": expected });
+}
+
+#[test]
+fn duration_as_millis_i64() {
+    use std::time::Duration;
+
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Foo {
+        #[serde(with = "serde_helpers::duration_as_millis_i64")]
+        timeout: Duration,
+        #[serde(with = "serde_helpers::duration_as_millis_i64_optional", default)]
+        retry: Option<Duration>,
+    }
+
+    let foo = Foo {
+        timeout: Duration::from_micros(1_500_999),
+        retry: Some(Duration::from_secs(2)),
+    };
+    let doc = to_document(&foo).unwrap();
+    assert_eq!(doc, doc! { "timeout": 1500_i64, "retry": 2000_i64 });
+    assert_eq!(
+        from_document::<Foo>(doc).unwrap(),
+        Foo {
+            timeout: Duration::from_millis(1500),
+            retry: Some(Duration::from_secs(2)),
+        }
+    );
+
+    let foo: Foo = from_document(doc! { "timeout": 5, "retry": null }).unwrap();
+    assert_eq!(foo.timeout, Duration::from_millis(5));
+    assert_eq!(foo.retry, None);
+    let foo: Foo = from_document(doc! { "timeout": 5 }).unwrap();
+    assert_eq!(foo.retry, None);
+
+    assert!(from_document::<Foo>(doc! { "timeout": -1 }).is_err());
+    assert!(to_document(&Foo {
+        timeout: Duration::MAX,
+        retry: None,
+    })
+    .is_err());
+}
+
+#[test]
+fn char_as_i32() {
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Foo {
+        #[serde(with = "serde_helpers::char_as_i32")]
+        letter: char,
+    }
+
+    for letter in ['a', '\0', '\u{10FFFF}', '\u{1F980}'] {
+        let doc = to_document(&Foo { letter }).unwrap();
+        assert_eq!(doc, doc! { "letter": letter as u32 as i32 });
+        assert_eq!(from_document::<Foo>(doc).unwrap(), Foo { letter });
+    }
+
+    assert!(from_document::<Foo>(doc! { "letter": -1 }).is_err());
+    assert!(from_document::<Foo>(doc! { "letter": 0xD800 }).is_err());
+    assert!(from_document::<Foo>(doc! { "letter": 0x110000 }).is_err());
+}
+
+#[test]
+fn cow_str_borrows_from_input() {
+    use std::borrow::Cow;
+
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Deserialize)]
+    struct Foo<'a> {
+        #[serde(borrow)]
+        s: Cow<'a, str>,
+        #[serde(borrow)]
+        nested: Bar<'a>,
+    }
+
+    #[derive(Deserialize)]
+    struct Bar<'a> {
+        #[serde(borrow)]
+        t: Cow<'a, str>,
+    }
+
+    let borrows_from = |cow: &Cow<str>, bytes: &[u8]| match cow {
+        Cow::Borrowed(s) => bytes.as_ptr_range().contains(&s.as_ptr()),
+        Cow::Owned(_) => false,
+    };
+
+    let mut bytes = crate::to_vec(&doc! { "s": "hello", "nested": { "t": "world" } }).unwrap();
+    let foo: Foo = crate::from_slice(&bytes).unwrap();
+    assert!(borrows_from(&foo.s, &bytes));
+    assert!(borrows_from(&foo.nested.t, &bytes));
+
+    // Lossy decoding only allocates for strings that need to be rewritten.
+    let foo: Foo = crate::from_slice_utf8_lossy(&bytes).unwrap();
+    assert!(borrows_from(&foo.s, &bytes));
+    assert!(borrows_from(&foo.nested.t, &bytes));
+
+    let pos = bytes.windows(5).position(|w| w == b"world").unwrap();
+    bytes[pos] = 0xFF;
+    let foo: Foo = crate::from_slice_utf8_lossy(&bytes).unwrap();
+    assert!(borrows_from(&foo.s, &bytes));
+    assert!(matches!(foo.nested.t, Cow::Owned(_)));
+    assert_eq!(foo.nested.t, "\u{FFFD}orld");
+}
+
+#[test]
+fn i128_as_decimal128() {
+    use crate::Decimal128;
+
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Foo {
+        #[serde(with = "serde_helpers::i128_as_decimal128")]
+        amount: i128,
+    }
+
+    let max = 9_999_999_999_999_999_999_999_999_999_999_999_i128;
+    for amount in [0, 1, -1, i128::from(i64::MAX) * 1000, max, -max] {
+        let doc = to_document(&Foo { amount }).unwrap();
+        let expected: Decimal128 = amount.to_string().parse().unwrap();
+        assert_eq!(doc, doc! { "amount": expected });
+        assert_eq!(from_document::<Foo>(doc).unwrap(), Foo { amount });
+    }
+
+    // Values with more than 34 digits cannot be represented exactly.
+    assert!(to_document(&Foo { amount: max + 1 }).is_err());
+    assert!(to_document(&Foo { amount: i128::MIN }).is_err());
+
+    // Any integral decimal128 in range is accepted, regardless of exponent.
+    for (decimal, amount) in [
+        ("1.2E+3", 1200),
+        ("1200.00", 1200),
+        ("-0", 0),
+        ("0E-50", 0),
+        ("1E+38", 10_i128.pow(38)),
+        ("1.7E+38", 17 * 10_i128.pow(37)),
+        ("-1.7E+38", -17 * 10_i128.pow(37)),
+    ] {
+        let doc = doc! { "amount": decimal.parse::<Decimal128>().unwrap() };
+        assert_eq!(
+            from_document::<Foo>(doc).unwrap(),
+            Foo { amount },
+            "{}",
+            decimal
+        );
+    }
+
+    for decimal in ["1.5", "1.8E+38", "1E+39", "NaN", "Infinity", "1E-40"] {
+        let doc = doc! { "amount": decimal.parse::<Decimal128>().unwrap() };
+        assert!(from_document::<Foo>(doc).is_err(), "{}", decimal);
+    }
+    assert!(from_document::<Foo>(doc! { "amount": 5 }).is_err());
+}
+
+#[test]
+fn deserialization_error_byte_offset() {
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Outer {
+        a: Inner,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Inner {
+        b: String,
+    }
+
+    // Layout: the nested document for "a" starts at byte 7, its "b" element at byte 11, and the
+    // value of "b" at byte 14. The "c" element starts at byte 18.
+    let bytes = crate::to_vec(&doc! { "a": { "b": 1, "c": "x" } }).unwrap();
+
+    // Type mismatches point at the offending value.
+    let err = crate::from_slice::<Outer>(&bytes).unwrap_err();
+    assert_eq!(err.byte_offset(), Some(14), "{}", err);
+    assert!(err.to_string().contains("at byte offset 14"), "{}", err);
+
+    // Malformed elements point at the start of the element.
+    let mut corrupt = bytes.clone();
+    corrupt[21] = 0xFF;
+    let err = crate::from_slice::<Document>(&corrupt).unwrap_err();
+    assert_eq!(err.byte_offset(), Some(18), "{}", err);
+
+    // Errors in the top-level document itself point at its start.
+    let err = crate::from_slice::<Document>(&bytes[..bytes.len() - 1]).unwrap_err();
+    assert_eq!(err.byte_offset(), Some(0), "{}", err);
+
+    // Offsets are not available when not deserializing from bytes.
+    let err = from_document::<Outer>(doc! { "a": { "b": 1 } }).unwrap_err();
+    assert_eq!(err.byte_offset(), None);
+}
+
+#[test]
+fn bson_as_extjson() {
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Foo {
+        #[serde(with = "serde_helpers::bson_as_extjson")]
+        value: Bson,
+    }
+
+    let values = vec![
+        Bson::Int64(5),
+        Bson::Double(1.0),
+        Bson::Decimal128("1.50".parse().unwrap()),
+        Bson::DateTime(DateTime::from_millis(1_000)),
+        Bson::Binary(Binary {
+            subtype: BinarySubtype::Generic,
+            bytes: vec![1, 2, 3],
+        }),
+        Bson::Document(doc! { "a": 1_i64, "b": [Bson::Null, 2.5] }),
+    ];
+    for value in values {
+        let foo = Foo { value };
+        let json = serde_json::to_value(&foo).unwrap();
+        assert_eq!(json["value"], foo.value.clone().into_canonical_extjson());
+        assert_eq!(serde_json::from_value::<Foo>(json).unwrap(), foo);
+
+        let bson = to_document(&foo).unwrap();
+        assert_eq!(bson, doc! { "value": foo.value.clone() });
+        assert_eq!(from_document::<Foo>(bson).unwrap(), foo);
+    }
+
+    // relaxed extended JSON is also accepted
+    let foo: Foo = serde_json::from_str(r#"{"value":{"$date":"1970-01-01T00:00:01Z"}}"#).unwrap();
+    assert_eq!(foo.value, Bson::DateTime(DateTime::from_millis(1_000)));
+}
+
+#[test]
+fn lenient_integer_deserialization() {
+    let _guard = LOCK.run_concurrently();
+
+    fn check<T>(value: Bson) -> Option<T>
+    where
+        T: serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        #[derive(Deserialize)]
+        struct Foo<T> {
+            x: T,
+        }
+
+        let doc = doc! { "x": value };
+        let from_raw = crate::from_slice::<Foo<T>>(&crate::to_vec(&doc).unwrap())
+            .ok()
+            .map(|f| f.x);
+        let from_doc = from_document::<Foo<T>>(doc).ok().map(|f| f.x);
+        assert_eq!(from_raw, from_doc);
+        from_raw
+    }
+
+    assert_eq!(check::<u8>(Bson::Int32(200)), Some(200));
+    assert_eq!(check::<u8>(Bson::Int32(256)), None);
+    assert_eq!(check::<u8>(Bson::Int32(-1)), None);
+    assert_eq!(check::<i16>(Bson::Int64(-300)), Some(-300));
+    assert_eq!(check::<i32>(Bson::Int64(i32::MAX as i64)), Some(i32::MAX));
+    assert_eq!(check::<i32>(Bson::Int64(i32::MAX as i64 + 1)), None);
+    assert_eq!(check::<u32>(Bson::Int64(u32::MAX as i64)), Some(u32::MAX));
+    assert_eq!(check::<u64>(Bson::Int64(-1)), None);
+
+    assert_eq!(check::<i64>(Bson::Double(3.0)), Some(3));
+    assert_eq!(check::<i64>(Bson::Double(-0.0)), Some(0));
+    assert_eq!(check::<u8>(Bson::Double(255.0)), Some(255));
+    assert_eq!(
+        check::<u64>(Bson::Double(1e19)),
+        Some(10_000_000_000_000_000_000)
+    );
+    assert_eq!(check::<i64>(Bson::Double(3.5)), None);
+    assert_eq!(check::<u8>(Bson::Double(256.0)), None);
+    assert_eq!(check::<i64>(Bson::Double(1e19)), None);
+    assert_eq!(check::<i64>(Bson::Double(f64::NAN)), None);
+    assert_eq!(check::<i64>(Bson::Double(f64::INFINITY)), None);
+
+    // non-integer targets are unaffected
+    assert_eq!(check::<f64>(Bson::Double(3.0)), Some(3.0));
+    assert_eq!(check::<Bson>(Bson::Double(3.0)), Some(Bson::Double(3.0)));
+}
+
+#[test]
+fn skip_serializing_empty_containers() {
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Serialize)]
+    struct Foo {
+        #[serde(skip_serializing_if = "Document::is_empty")]
+        doc: Document,
+        #[serde(skip_serializing_if = "Bson::is_empty_container")]
+        value: Bson,
+    }
+
+    let foo = Foo {
+        doc: doc! {},
+        value: Bson::Document(doc! {}),
+    };
+    assert_eq!(to_document(&foo).unwrap(), doc! {});
+
+    let foo = Foo {
+        doc: doc! {},
+        value: Bson::Array(vec![]),
+    };
+    assert_eq!(to_document(&foo).unwrap(), doc! {});
+
+    for value in [
+        Bson::Null,
+        Bson::String(String::new()),
+        Bson::Int32(0),
+        Bson::Array(vec![Bson::Null]),
+        Bson::Document(doc! { "a": 1 }),
+    ] {
+        let foo = Foo {
+            doc: doc! { "x": 1 },
+            value: value.clone(),
+        };
+        assert_eq!(
+            to_document(&foo).unwrap(),
+            doc! { "doc": { "x": 1 }, "value": value }
+        );
+    }
+}
+
+#[test]
+fn flexible_object_id() {
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Foo {
+        #[serde(with = "serde_helpers::flexible_object_id")]
+        id: ObjectId,
+    }
+
+    let oid = ObjectId::parse_str("507f1f77bcf86cd799439011").unwrap();
+    let expected = Foo { id: oid };
+
+    for json in [
+        json!({ "id": oid.to_hex() }),
+        json!({ "id": { "$oid": oid.to_hex() } }),
+    ] {
+        assert_eq!(serde_json::from_value::<Foo>(json).unwrap(), expected);
+    }
+
+    for doc in [
+        doc! { "id": oid },
+        doc! { "id": oid.to_hex() },
+        doc! { "id": { "$oid": oid.to_hex() } },
+    ] {
+        let bytes = crate::to_vec(&doc).unwrap();
+        assert_eq!(crate::from_slice::<Foo>(&bytes).unwrap(), expected);
+        assert_eq!(from_document::<Foo>(doc).unwrap(), expected);
+    }
+
+    assert_eq!(to_document(&expected).unwrap(), doc! { "id": oid });
+    assert_eq!(
+        serde_json::to_value(&expected).unwrap(),
+        json!({ "id": { "$oid": oid.to_hex() } })
+    );
+
+    assert!(serde_json::from_value::<Foo>(json!({ "id": "not an id" })).is_err());
+    assert!(from_document::<Foo>(doc! { "id": 1 }).is_err());
+    assert!(from_document::<Foo>(doc! { "id": { "a": 1 } }).is_err());
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Bar {
+        #[serde(with = "serde_helpers::object_id::flexible")]
+        id: ObjectId,
+    }
+
+    assert_eq!(
+        serde_json::from_value::<Bar>(json!({ "id": oid.to_hex() })).unwrap(),
+        Bar { id: oid }
+    );
+}
+
+#[test]
+fn externally_tagged_enum_round_trip() {
+    let _guard = LOCK.run_concurrently();
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Point(i32, i32),
+        Rect { width: i32, height: i32 },
+    }
+
+    let cases = [
+        (Shape::Empty, json!("Empty")),
+        (Shape::Circle(1.5), json!({ "Circle": 1.5 })),
+        (Shape::Point(1, 2), json!({ "Point": [1, 2] })),
+        (
+            Shape::Rect {
+                width: 3,
+                height: 4,
+            },
+            json!({ "Rect": { "width": 3, "height": 4 } }),
+        ),
+    ];
+
+    for (shape, json) in cases {
+        let bson = to_bson(&shape).unwrap();
+        assert_eq!(bson.clone().into_relaxed_extjson(), json);
+        assert_eq!(from_bson::<Shape>(bson).unwrap(), shape);
+
+        let from_extjson = Bson::try_from(json).unwrap();
+        assert_eq!(from_bson::<Shape>(from_extjson).unwrap(), shape);
+    }
+}
+
 #[test]
 fn fuzz_regression_00() {
     let buf: &[u8] = &[227, 0, 35, 4, 2, 0, 255, 255, 255, 127, 255, 255, 255, 47];
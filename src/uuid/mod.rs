@@ -363,8 +363,12 @@ impl From<Uuid> for uuid::Uuid {
 ///
 /// # Ok::<(), Box::<dyn std::error::Error>>(())
 /// ```
+///
+/// [`UuidRepresentation`] serializes as a string containing its variant name (e.g.
+/// `"PythonLegacy"`), so a configured representation can be stored in and loaded from settings
+/// files.
 #[non_exhaustive]
-#[derive(PartialEq, Clone, Copy, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum UuidRepresentation {
     /// The canonical representation of UUIDs in BSON (binary with subtype 0x04)
     Standard,
@@ -471,6 +475,26 @@ impl Binary {
     pub fn to_uuid(&self) -> Result<Uuid> {
         self.to_uuid_with_representation(UuidRepresentation::Standard)
     }
+
+    /// Converts a BSON [`Binary`] holding a UUID in the provided legacy representation into one
+    /// using the standard representation (binary subtype 0x04). If the representation does not
+    /// match the [`Binary`], an error will be returned.
+    ///
+    /// This is useful when migrating data written by drivers that used a legacy representation.
+    ///
+    /// ```rust
+    /// # use bson::{Binary, Uuid, UuidRepresentation};
+    /// let uuid = Uuid::new();
+    /// let legacy = Binary::from_uuid_with_representation(uuid, UuidRepresentation::JavaLegacy);
+    ///
+    /// let upgraded = legacy.upgrade_uuid(UuidRepresentation::JavaLegacy)?;
+    /// assert_eq!(upgraded, Binary::from_uuid(uuid));
+    /// # Ok::<(), Box::<dyn std::error::Error>>(())
+    /// ```
+    pub fn upgrade_uuid(&self, from: UuidRepresentation) -> Result<Binary> {
+        self.to_uuid_with_representation(from)
+            .map(Binary::from_uuid)
+    }
 }
 
 macro_rules! trait_impls {
@@ -240,6 +240,32 @@ fn test_binary_to_uuid_python_legacy_rep() {
     );
 }
 
+#[test]
+fn test_binary_upgrade_uuid() {
+    let uuid = crate::Uuid::parse_str("00112233445566778899AABBCCDDEEFF").unwrap();
+    let standard = Binary::from_uuid(uuid);
+
+    for rep in [
+        UuidRepresentation::CSharpLegacy,
+        UuidRepresentation::JavaLegacy,
+        UuidRepresentation::PythonLegacy,
+    ] {
+        let bin = Binary::from_uuid_with_representation(uuid, rep);
+        assert_eq!(bin.upgrade_uuid(rep).unwrap(), standard);
+        assert!(standard.upgrade_uuid(rep).is_err());
+    }
+    assert_eq!(
+        standard.upgrade_uuid(UuidRepresentation::Standard).unwrap(),
+        standard
+    );
+
+    let short = Binary {
+        subtype: BinarySubtype::UuidOld,
+        bytes: vec![0; 4],
+    };
+    assert!(short.upgrade_uuid(UuidRepresentation::JavaLegacy).is_err());
+}
+
 #[cfg(feature = "uuid-0_8")]
 #[test]
 fn interop_0_8() {
@@ -290,3 +316,34 @@ fn deserialize_uuid_from_string() {
         from_slice(raw_doc.as_bytes()).expect("failed to deserialize raw document");
     assert_eq!(wrapper.uuid, uuid);
 }
+
+#[test]
+fn uuid_representation_serde() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Settings {
+        representation: UuidRepresentation,
+    }
+
+    let cases = [
+        (UuidRepresentation::Standard, "Standard"),
+        (UuidRepresentation::CSharpLegacy, "CSharpLegacy"),
+        (UuidRepresentation::JavaLegacy, "JavaLegacy"),
+        (UuidRepresentation::PythonLegacy, "PythonLegacy"),
+    ];
+    for (representation, name) in cases {
+        let settings = Settings { representation };
+
+        let json = serde_json::to_value(&settings).unwrap();
+        assert_eq!(json, json!({ "representation": name }));
+        assert_eq!(serde_json::from_value::<Settings>(json).unwrap(), settings);
+
+        let doc = crate::to_document(&settings).unwrap();
+        assert_eq!(doc, crate::doc! { "representation": name });
+        assert_eq!(from_document::<Settings>(doc).unwrap(), settings);
+
+        let bytes = crate::to_vec(&settings).unwrap();
+        assert_eq!(from_slice::<Settings>(&bytes).unwrap(), settings);
+    }
+
+    assert!(serde_json::from_value::<Settings>(json!({ "representation": "Unknown" })).is_err());
+}